@@ -0,0 +1,80 @@
+use pokepra_wasm::cards::{card_to_str, pair_string};
+use pokepra_wasm::parser::{parse_cards, parse_hands_list};
+
+/// `rust_wasm_demo` のエンコード済みカード (`rank << 2 | suit`) を `"As"` 形式に戻す責務を持つ。
+///
+/// rank は 0 = 2 ... 12 = A、suit は 0 = s, 1 = h, 2 = d, 3 = c。範囲外の値は `?` で表す。
+pub fn decode_card(encoded: u32) -> String {
+    let rank = (encoded >> 2) as usize;
+    let suit = (encoded & 0b11) as usize;
+    let rank_char = "23456789TJQKA".chars().nth(rank).unwrap_or('?');
+    let suit_char = ['s', 'h', 'd', 'c'][suit];
+    format!("{rank_char}{suit_char}")
+}
+
+/// エンコード済みの 2 枚を `"As Ks"` 形式（強い方を先頭）に整形する責務を持つ。
+///
+/// `pokepra_wasm::cards::pair_string` と同じ表記に揃え、どちらのクレート由来の結果でも
+/// 同じハンドが同じ文字列になるようにする。
+pub fn decode_pair(a: u32, b: u32) -> String {
+    let (high, low) = if a >> 2 >= b >> 2 { (a, b) } else { (b, a) };
+    format!("{} {}", decode_card(high), decode_card(low))
+}
+
+/// カード列を `rust_wasm_demo` が受け付ける空白区切り (`"As Ks Qd"`) に正規化する責務を持つ。
+///
+/// `rust_wasm_demo` のパーサは「空白区切り」か「完全な連結」しか受けないので、
+/// どちらの書き方でも通るように `pokepra_wasm` のパーサで一度読み直す。
+pub fn normalize_cards(input: &str) -> Result<String, String> {
+    Ok(parse_cards(input)?
+        .iter()
+        .map(card_to_str)
+        .collect::<Vec<_>>()
+        .join(" "))
+}
+
+/// ハンドのリストを `"As Ks; Qd Jd"` 形式（各ハンドは強い方を先頭）に正規化する責務を持つ。
+pub fn normalize_hands(input: &str) -> Result<String, String> {
+    Ok(parse_hands_list(input)?
+        .iter()
+        .map(|&(a, b)| pair_string(a, b))
+        .collect::<Vec<_>>()
+        .join("; "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_card_maps_rank_and_suit() {
+        assert_eq!(decode_card(0), "2s");
+        assert_eq!(decode_card((12 << 2) | 1), "Ah");
+        assert_eq!(decode_card((8 << 2) | 3), "Tc");
+    }
+
+    #[test]
+    fn normalize_hands_accepts_compact_and_spaced_hands() {
+        assert_eq!(
+            normalize_hands("AhKh; Qs Qd;").expect("hands parse"),
+            "Ah Kh; Qs Qd"
+        );
+    }
+
+    #[test]
+    fn normalize_cards_splits_compact_board() {
+        assert_eq!(
+            normalize_cards(" 7s8h9d ").expect("cards parse"),
+            "7s 8h 9d"
+        );
+    }
+
+    #[test]
+    fn decode_pair_puts_higher_rank_first() {
+        let ace = 12 << 2;
+        let king = 11 << 2;
+
+        assert_eq!(decode_pair(king, ace), "As Ks");
+        assert_eq!(decode_pair(ace, king), "As Ks");
+    }
+}
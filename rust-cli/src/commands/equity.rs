@@ -0,0 +1,132 @@
+use std::cmp::Ordering;
+
+use pokepra_wasm::dto::{EquityEntry, EquityPayload};
+use pokepra_wasm::sim::vs_list_equity;
+
+use super::{weighted_mean, SimOptions};
+use crate::output::{format_equity, Report, Table};
+use crate::shard;
+
+/// `equity` サブコマンドの引数。hero 1 ハンド vs 相手ハンドリスト（各相手とヘッズアップ）。
+#[derive(Debug, Clone, clap::Args)]
+pub struct Args {
+    /// hero のハンド（例: "AhKh"）。
+    #[arg(long)]
+    pub hero: String,
+    /// 既知のボード（0〜5 枚, 例: "7s8h9d"）。
+    #[arg(long, default_value = "")]
+    pub board: String,
+    /// 相手ハンドのリスト（セミコロン区切り, 例: "QsQd; JcTc"）。
+    #[arg(long)]
+    pub villains: String,
+    #[command(flatten)]
+    pub sim: SimOptions,
+}
+
+/// `pokepra_wasm::sim::vs_list_equity::run` をシャード並列で実行する責務を持つ。
+///
+/// 各相手とは毎シャード同じ試行回数ずつ対戦するので、hero 全体の equity も
+/// 相手別の equity も、シャードの試行回数で重み付け平均すれば全試行分の値になる。
+pub fn run(args: &Args) -> Result<Report, String> {
+    let shards = args.sim.shards();
+    let results = shard::run(&shards, |s| {
+        vs_list_equity::run(
+            &args.hero,
+            &args.board,
+            &args.villains,
+            s.trials,
+            s.seed,
+            true,
+        )
+    })?;
+
+    let first = results.first().ok_or("simulation returned no records")?;
+    let mut data: Vec<EquityEntry> = first
+        .data
+        .iter()
+        .map(|entry| EquityEntry {
+            hand: entry.hand.clone(),
+            equity: weighted_mean(results.iter().zip(&shards).filter_map(|(payload, s)| {
+                payload
+                    .data
+                    .iter()
+                    .find(|e| e.hand == entry.hand)
+                    .map(|e| (e.equity, s.trials))
+            })),
+        })
+        .collect();
+    data.sort_by(|a, b| b.equity.partial_cmp(&a.equity).unwrap_or(Ordering::Equal));
+
+    let payload = EquityPayload {
+        hand: first.hand.clone(),
+        equity: weighted_mean(
+            results
+                .iter()
+                .zip(&shards)
+                .map(|(payload, s)| (payload.equity, s.trials)),
+        ),
+        data,
+    };
+
+    let mut rows: Vec<Vec<String>> = payload
+        .data
+        .iter()
+        .map(|e| {
+            vec![
+                e.hand.clone(),
+                format_equity(e.equity),
+                format_equity(1.0 - e.equity),
+            ]
+        })
+        .collect();
+    rows.push(vec![
+        format!("{} (hero total)", payload.hand),
+        format_equity(1.0 - payload.equity),
+        format_equity(payload.equity),
+    ]);
+
+    Ok(Report {
+        json: serde_json::to_value(&payload).map_err(|e| e.to_string())?,
+        table: Table {
+            headers: vec!["villain", "villain_equity", "hero_equity"],
+            rows,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(threads: usize) -> Args {
+        Args {
+            hero: "AhAd".to_string(),
+            board: String::new(),
+            villains: "7c2d; KsQs".to_string(),
+            sim: SimOptions {
+                trials: 400,
+                seed: 1,
+                threads: Some(threads),
+            },
+        }
+    }
+
+    #[test]
+    fn run_merges_every_villain_across_threads() {
+        let report = run(&args(3)).expect("simulation succeeds");
+
+        assert_eq!(report.json["data"].as_array().map(Vec::len), Some(2));
+        assert!(report.json["equity"].as_f64().unwrap_or_default() > 0.7);
+        assert_eq!(report.table.rows.len(), 3);
+    }
+
+    #[test]
+    fn run_is_identical_to_library_call_with_one_thread() {
+        let report = run(&args(1)).expect("simulation succeeds");
+        let direct = vs_list_equity::run("AhAd", "", "7c2d; KsQs", 400, 1, true)
+            .expect("library call succeeds");
+
+        assert_eq!(report.json["equity"], direct.equity);
+        assert_eq!(report.json["data"][0]["equity"], direct.data[0].equity);
+    }
+}
@@ -0,0 +1,49 @@
+use pokepra_wasm::sim::parse_range;
+
+use crate::output::{Report, Table};
+
+/// `expand-range` サブコマンドの引数。レンジ式を全コンボに展開する。
+#[derive(Debug, Clone, clap::Args)]
+pub struct Args {
+    /// レンジ式（例: "AKs+,QQ+"）。
+    #[arg(long)]
+    pub range: String,
+    /// 除外するカード（例: "As Kd"）。これを含むコンボは出力しない。
+    #[arg(long, default_value = "")]
+    pub exclude: String,
+}
+
+/// `pokepra_wasm::sim::parse_range::run` の結果を出力用にまとめる責務を持つ。
+pub fn run(args: &Args) -> Result<Report, String> {
+    let combos = parse_range::run(&args.range, &args.exclude)?;
+    let rows = combos
+        .iter()
+        .map(|[a, b]| vec![format!("{a} {b}")])
+        .collect();
+
+    Ok(Report {
+        json: serde_json::to_value(&combos).map_err(|e| e.to_string())?,
+        table: Table {
+            headers: vec!["hand"],
+            rows,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_drops_combos_with_excluded_cards() {
+        let args = Args {
+            range: "AA".to_string(),
+            exclude: "As".to_string(),
+        };
+
+        let report = run(&args).expect("range parses");
+
+        assert_eq!(report.table.rows.len(), 3);
+        assert!(report.table.rows.iter().all(|row| !row[0].contains("As")));
+    }
+}
@@ -0,0 +1,81 @@
+//! サブコマンドごとの引数定義と実行処理。
+//!
+//! 1 サブコマンド = 1 ファイル。各 `run` は WASM クレートと同じシミュレーション関数を呼び、
+//! 結果を [`crate::output::Report`] にまとめて返す。
+
+pub mod equity;
+pub mod expand_range;
+pub mod multiway;
+pub mod push_fold;
+pub mod range_vs_range;
+pub mod rank;
+
+use crate::shard::{default_threads, plan, Shard};
+
+/// フロントエンド (`src/lib/wasm-v2/simulation.ts`) と同じ既定シード。
+pub const DEFAULT_SEED: u64 = 123_456_789;
+
+/// Monte Carlo 系サブコマンドに共通するオプションを保持する責務を持つ。
+#[derive(Debug, Clone, clap::Args)]
+pub struct SimOptions {
+    /// 試行回数。スレッド数で分割して実行する。
+    #[arg(long, default_value_t = 10_000)]
+    pub trials: u32,
+    /// 乱数シード。同じシード・同じスレッド数なら結果は再現する
+    /// （`range-vs-range` / `rank` はスレッド数にも依存しない）。
+    #[arg(long, default_value_t = DEFAULT_SEED)]
+    pub seed: u64,
+    /// 使用スレッド数。省略時は論理コア数。
+    #[arg(long)]
+    pub threads: Option<usize>,
+}
+
+impl SimOptions {
+    /// 試行回数とシードをスレッドごとのシャードに分割する責務を持つ。
+    pub fn shards(&self) -> Vec<Shard> {
+        plan(
+            self.trials,
+            self.seed,
+            self.threads.unwrap_or_else(default_threads),
+        )
+    }
+
+    /// ライブラリの並列版に渡すスレッド数。未指定なら 0（= 論理コア数）。
+    pub fn thread_count(&self) -> usize {
+        self.threads.unwrap_or(0)
+    }
+}
+
+/// シャードの試行回数で重み付けした平均を計算する責務を持つ。
+///
+/// 各シャードは同じ組み合わせを試行回数に比例した回数だけ評価するので、
+/// 試行回数を重みにすれば 1 回で全試行を回した場合の equity と一致する。
+pub(crate) fn weighted_mean(values: impl IntoIterator<Item = (f64, u32)>) -> f64 {
+    let (sum, weight) = values
+        .into_iter()
+        .fold((0.0, 0u64), |(sum, weight), (value, trials)| {
+            (sum + value * f64::from(trials), weight + u64::from(trials))
+        });
+    if weight == 0 {
+        0.0
+    } else {
+        sum / weight as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weighted_mean_weights_by_trials() {
+        let mean = weighted_mean([(1.0, 3), (0.0, 1)]);
+
+        assert!((mean - 0.75).abs() < 1e-12);
+    }
+
+    #[test]
+    fn weighted_mean_returns_zero_without_weight() {
+        assert_eq!(weighted_mean([]), 0.0);
+    }
+}
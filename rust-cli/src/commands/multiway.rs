@@ -0,0 +1,94 @@
+use rust_wasm_demo::rs_poker_native::simulate_multi_hand_equity;
+use serde::Serialize;
+
+use super::{weighted_mean, SimOptions};
+use crate::cards::{decode_pair, normalize_cards, normalize_hands};
+use crate::output::{format_equity, Report, Table};
+use crate::shard;
+
+/// `multiway` サブコマンドの引数。2〜6 人のハンドを同時に比較する。
+#[derive(Debug, Clone, clap::Args)]
+pub struct Args {
+    /// 参加者全員のハンド（セミコロン区切り, 例: "AhKh; QsQd; 7c7d"）。
+    #[arg(long)]
+    pub hands: String,
+    /// 既知のボード（0〜5 枚）。
+    #[arg(long, default_value = "")]
+    pub board: String,
+    #[command(flatten)]
+    pub sim: SimOptions,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MultiwayRow {
+    pub hand: String,
+    pub equity: f64,
+}
+
+/// `rust_wasm_demo::simulate_multi_hand_equity` をシャード並列で実行する責務を持つ。
+///
+/// ライブラリは 1e6 倍した equity を返すので、小数に戻してから試行回数で重み付け平均する。
+pub fn run(args: &Args) -> Result<Report, String> {
+    let hands = normalize_hands(&args.hands)?;
+    let board = normalize_cards(&args.board)?;
+    let shards = args.sim.shards();
+    let results = shard::run(&shards, |s| {
+        simulate_multi_hand_equity(&hands, &board, s.trials, s.seed)
+    })?;
+
+    let first = results.first().ok_or("simulation returned no records")?;
+    let rows: Vec<MultiwayRow> = first
+        .iter()
+        .enumerate()
+        .map(|(idx, &(c1, c2, _))| MultiwayRow {
+            hand: decode_pair(c1, c2),
+            equity: weighted_mean(
+                results
+                    .iter()
+                    .zip(&shards)
+                    .map(|(records, s)| (f64::from(records[idx].2) / 1_000_000.0, s.trials)),
+            ),
+        })
+        .collect();
+
+    Ok(Report {
+        json: serde_json::to_value(&rows).map_err(|e| e.to_string())?,
+        table: Table {
+            headers: vec!["hand", "equity"],
+            rows: rows
+                .iter()
+                .map(|r| vec![r.hand.clone(), format_equity(r.equity)])
+                .collect(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_returns_equities_that_sum_to_one() {
+        let args = Args {
+            hands: "AhKh; QsQd; 7c7d".to_string(),
+            board: String::new(),
+            sim: SimOptions {
+                trials: 600,
+                seed: 9,
+                threads: Some(3),
+            },
+        };
+
+        let report = run(&args).expect("simulation succeeds");
+        let total: f64 = report
+            .json
+            .as_array()
+            .expect("array of rows")
+            .iter()
+            .filter_map(|row| row["equity"].as_f64())
+            .sum();
+
+        assert_eq!(report.table.rows.len(), 3);
+        assert!((total - 1.0).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,74 @@
+use pokepra_rust_experimental::simulate_heads_up_cfr;
+
+use super::DEFAULT_SEED;
+use crate::output::{format_equity, Report, Table};
+
+/// `push-fold` サブコマンドの引数。100BB shove に対する fold / call の実験。
+///
+/// 各ハンドは「過去に出ていない配牌」を選ぶ逐次処理なので、スレッド分割はしない。
+#[derive(Debug, Clone, clap::Args)]
+pub struct Args {
+    /// シミュレーションするハンド数。
+    #[arg(long, default_value_t = 100)]
+    pub trials: usize,
+    /// 乱数シード。
+    #[arg(long, default_value_t = DEFAULT_SEED)]
+    pub seed: u64,
+}
+
+/// `pokepra_rust_experimental::simulate_heads_up_cfr` の結果を出力用にまとめる責務を持つ。
+///
+/// JSON には集計値も含めた結果全体を、テーブルには 1 ハンド 1 行の詳細を出す。
+pub fn run(args: &Args) -> Result<Report, String> {
+    let result = simulate_heads_up_cfr(args.trials, args.seed);
+    let rows = result
+        .hands
+        .iter()
+        .map(|hand| {
+            vec![
+                hand.hand_number.to_string(),
+                hand.player_1_hand.clone(),
+                hand.player_2_hand.clone(),
+                hand.player_1_action.clone(),
+                hand.board.clone().unwrap_or_else(|| "-".to_string()),
+                format_equity(f64::from(hand.estimated_all_in_equity)),
+                format!("{:.1}", hand.all_in_ev_bb),
+                hand.player_1_profit_bb.to_string(),
+            ]
+        })
+        .collect();
+
+    Ok(Report {
+        json: serde_json::to_value(&result).map_err(|e| e.to_string())?,
+        table: Table {
+            headers: vec![
+                "hand",
+                "player_1",
+                "player_2",
+                "action",
+                "board",
+                "equity",
+                "all_in_ev_bb",
+                "player_1_profit_bb",
+            ],
+            rows,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_outputs_one_row_per_hand() {
+        let report = run(&Args {
+            trials: 5,
+            seed: 42,
+        })
+        .expect("simulation succeeds");
+
+        assert_eq!(report.table.rows.len(), 5);
+        assert_eq!(report.json["trials"], 5);
+    }
+}
@@ -0,0 +1,100 @@
+use pokepra_wasm::sim::range_vs_range;
+
+use super::SimOptions;
+use crate::output::{format_equity, Report, Table};
+
+/// `range-vs-range` サブコマンドの引数。
+#[derive(Debug, Clone, clap::Args)]
+pub struct Args {
+    /// hero のレンジ式（例: "AKs+,QQ+"）。
+    #[arg(long)]
+    pub hero: String,
+    /// villain のレンジ式。
+    #[arg(long)]
+    pub villain: String,
+    /// 既知のボード（0〜5 枚）。
+    #[arg(long, default_value = "")]
+    pub board: String,
+    #[command(flatten)]
+    pub sim: SimOptions,
+}
+
+/// `pokepra_wasm::sim::range_vs_range::run_parallel` を呼ぶ責務を持つ。
+///
+/// 並列化はライブラリ側で hero コンボ単位に行うので、結果は `--threads` に依存しない。
+pub fn run(args: &Args) -> Result<Report, String> {
+    let payload = range_vs_range::run_parallel(
+        &args.hero,
+        &args.villain,
+        &args.board,
+        args.sim.trials,
+        args.sim.seed,
+        args.sim.thread_count(),
+    )?;
+
+    let mut rows: Vec<Vec<String>> = Vec::with_capacity(payload.hero.len() + payload.villain.len());
+    for (side, entries) in [("hero", &payload.hero), ("villain", &payload.villain)] {
+        rows.extend(
+            entries
+                .iter()
+                .map(|e| vec![side.to_string(), e.hand.clone(), format_equity(e.equity)]),
+        );
+    }
+
+    Ok(Report {
+        json: serde_json::to_value(&payload).map_err(|e| e.to_string())?,
+        table: Table {
+            headers: vec!["side", "hand", "equity"],
+            rows,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_lists_every_combo_of_both_ranges() {
+        let args = Args {
+            hero: "AA".to_string(),
+            villain: "KK".to_string(),
+            board: String::new(),
+            sim: SimOptions {
+                trials: 8,
+                seed: 3,
+                threads: Some(2),
+            },
+        };
+
+        let report = run(&args).expect("simulation succeeds");
+
+        assert_eq!(report.json["hero"].as_array().map(Vec::len), Some(6));
+        assert_eq!(report.json["villain"].as_array().map(Vec::len), Some(6));
+        assert!(
+            report.json["hero"][0]["equity"]
+                .as_f64()
+                .unwrap_or_default()
+                > 0.5
+        );
+    }
+
+    #[test]
+    fn run_does_not_depend_on_thread_count() {
+        let args = |threads| Args {
+            hero: "QQ+,AKs".to_string(),
+            villain: "JJ-99".to_string(),
+            board: String::new(),
+            sim: SimOptions {
+                trials: 5,
+                seed: 11,
+                threads: Some(threads),
+            },
+        };
+
+        let single = run(&args(1)).expect("simulation succeeds");
+        let multi = run(&args(4)).expect("simulation succeeds");
+
+        assert_eq!(single.json, multi.json);
+    }
+}
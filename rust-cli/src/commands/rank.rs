@@ -0,0 +1,116 @@
+use pokepra_wasm::cards::pair_string;
+use pokepra_wasm::parser::parse_hands_list;
+use pokepra_wasm::rank::RANK_LABELS;
+use rust_wasm_demo::rs_poker_native::simulate_rank_distribution_parallel;
+use serde::Serialize;
+
+use super::SimOptions;
+use crate::cards::{normalize_cards, normalize_hands};
+use crate::output::{Report, Table};
+
+/// `rank` サブコマンドの引数。未完成ボードでの役カテゴリ分布。
+#[derive(Debug, Clone, clap::Args)]
+pub struct Args {
+    /// 評価するハンドのリスト（セミコロン区切り, 例: "AhKh; QsQd"）。
+    #[arg(long)]
+    pub hands: String,
+    /// 既知のボード（3〜5 枚）。
+    #[arg(long)]
+    pub board: String,
+    #[command(flatten)]
+    pub sim: SimOptions,
+}
+
+/// 1 ハンド分の役カテゴリ出現回数。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankDistributionRow {
+    pub hand: String,
+    pub trials: u32,
+    /// [`RANK_LABELS`] と同じ順の出現回数。
+    pub counts: [u32; 9],
+}
+
+/// `rust_wasm_demo::simulate_rank_distribution_parallel` を呼ぶ責務を持つ。
+///
+/// 試行はライブラリ側で固定サイズの作業単位に分けて並列化されるので、
+/// 結果は `--threads` に依存しない。
+pub fn run(args: &Args) -> Result<Report, String> {
+    let hands = parse_hands_list(&args.hands)?;
+    let hands_str = normalize_hands(&args.hands)?;
+    let board = normalize_cards(&args.board)?;
+    let counts = simulate_rank_distribution_parallel(
+        &hands_str,
+        &board,
+        args.sim.trials,
+        args.sim.seed,
+        args.sim.thread_count(),
+    )?;
+
+    let total_trials = args.sim.trials.max(1);
+    let rows: Vec<RankDistributionRow> = hands
+        .iter()
+        .zip(counts)
+        .map(|(&(a, b), counts)| RankDistributionRow {
+            hand: pair_string(a, b),
+            trials: total_trials,
+            counts,
+        })
+        .collect();
+
+    let mut headers = vec!["hand"];
+    headers.extend(RANK_LABELS);
+    let table_rows = rows
+        .iter()
+        .map(|row| {
+            let mut cells = vec![row.hand.clone()];
+            cells.extend(
+                row.counts
+                    .iter()
+                    .map(|&c| format!("{:.2}%", 100.0 * f64::from(c) / f64::from(row.trials))),
+            );
+            cells
+        })
+        .collect();
+
+    Ok(Report {
+        json: serde_json::to_value(&rows).map_err(|e| e.to_string())?,
+        table: Table {
+            headers,
+            rows: table_rows,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_counts_every_trial_once_per_hand() {
+        let args = Args {
+            hands: "AhKh; 7c7d".to_string(),
+            board: "2h3h9s".to_string(),
+            sim: SimOptions {
+                trials: 300,
+                seed: 5,
+                threads: Some(4),
+            },
+        };
+
+        let report = run(&args).expect("simulation succeeds");
+        let rows = report.json.as_array().expect("array of rows");
+
+        assert_eq!(rows.len(), 2);
+        for row in rows {
+            let total: u64 = row["counts"]
+                .as_array()
+                .expect("counts")
+                .iter()
+                .filter_map(|c| c.as_u64())
+                .sum();
+            assert_eq!(total, 300);
+        }
+        assert_eq!(report.table.headers.len(), 10);
+    }
+}
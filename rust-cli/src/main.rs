@@ -0,0 +1,106 @@
+//! pokepra のシミュレーションを端末から呼ぶためのネイティブ CLI。
+//!
+//! 計算は WASM クレート（`pokepra_wasm` / `rust_wasm_demo`）と実験クレート
+//! （`pokepra_rust_experimental`）の関数をそのまま呼ぶので、アプリと同じ入力・シードなら
+//! 同じロジックの結果を得られる。試行はスレッドごとのシャードに分けて全コアで実行する。
+
+mod cards;
+mod commands;
+mod output;
+mod shard;
+
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+use output::{Format, Report};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "pokepra",
+    version,
+    about = "pokepra equity simulations on the command line"
+)]
+struct Cli {
+    /// 出力フォーマット。
+    #[arg(long, value_enum, default_value_t = Format::Table, global = true)]
+    format: Format,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// hero 1 ハンド vs 相手ハンドリストの equity。
+    Equity(commands::equity::Args),
+    /// hero レンジ vs villain レンジのコンボ別 equity。
+    RangeVsRange(commands::range_vs_range::Args),
+    /// 未完成ボードから完成させたときの役カテゴリ分布。
+    Rank(commands::rank::Args),
+    /// レンジ式を全コンボに展開する。
+    ExpandRange(commands::expand_range::Args),
+    /// 2〜6 人のハンドを同時に比較したマルチウェイ equity。
+    Multiway(commands::multiway::Args),
+    /// 100BB shove に対する CFR fold / call 実験。
+    PushFold(commands::push_fold::Args),
+}
+
+impl Command {
+    /// サブコマンドを実行する責務を持つ。
+    fn run(&self) -> Result<Report, String> {
+        match self {
+            Command::Equity(args) => commands::equity::run(args),
+            Command::RangeVsRange(args) => commands::range_vs_range::run(args),
+            Command::Rank(args) => commands::rank::run(args),
+            Command::ExpandRange(args) => commands::expand_range::run(args),
+            Command::Multiway(args) => commands::multiway::run(args),
+            Command::PushFold(args) => commands::push_fold::run(args),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command.run() {
+        Ok(report) => {
+            print!("{}", report.render(cli.format));
+            if cli.format == Format::Json {
+                println!();
+            }
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn cli_parses_kebab_case_subcommands_and_global_format() {
+        let cli = Cli::try_parse_from([
+            "pokepra",
+            "range-vs-range",
+            "--hero",
+            "AA",
+            "--villain",
+            "KK",
+            "--format",
+            "csv",
+        ])
+        .expect("arguments parse");
+
+        assert_eq!(cli.format, Format::Csv);
+        assert!(matches!(cli.command, Command::RangeVsRange(_)));
+    }
+}
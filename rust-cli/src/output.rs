@@ -0,0 +1,153 @@
+use clap::ValueEnum;
+use serde_json::Value;
+
+/// 出力フォーマットを表す責務を持つ。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// 人間が端末で読むための桁揃えテーブル。
+    Table,
+    /// スクリプトから読むための JSON。
+    Json,
+    /// 表計算ソフトやスクリプトに渡すための CSV。
+    Csv,
+}
+
+/// 行と列で表せる結果を保持する責務を持つ。
+///
+/// `table` / `csv` 出力はこの形から生成する。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub headers: Vec<&'static str>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// 1 サブコマンドの実行結果を保持する責務を持つ。
+///
+/// JSON はライブラリが返す DTO をそのままシリアライズしたもの、
+/// テーブルは同じ結果を 1 行 1 レコードに平らにしたもの。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub json: Value,
+    pub table: Table,
+}
+
+impl Report {
+    /// 指定フォーマットの文字列へ変換する責務を持つ。
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Json => serde_json::to_string_pretty(&self.json)
+                .expect("serde_json::Value should always serialize"),
+            Format::Csv => render_csv(&self.table),
+            Format::Table => render_table(&self.table),
+        }
+    }
+}
+
+/// RFC 4180 相当の CSV に変換する責務を持つ。
+///
+/// カンマ・ダブルクォート・改行を含むセルだけをクォートする。
+fn render_csv(table: &Table) -> String {
+    let mut out = String::new();
+    let header: Vec<String> = table.headers.iter().map(|h| csv_cell(h)).collect();
+    out.push_str(&header.join(","));
+    out.push('\n');
+    for row in &table.rows {
+        let cells: Vec<String> = row.iter().map(|c| csv_cell(c)).collect();
+        out.push_str(&cells.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn csv_cell(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 列幅を揃えたプレーンテキストの表に変換する責務を持つ。
+fn render_table(table: &Table) -> String {
+    let mut widths: Vec<usize> = table.headers.iter().map(|h| h.chars().count()).collect();
+    for row in &table.rows {
+        for (i, cell) in row.iter().enumerate() {
+            if let Some(w) = widths.get_mut(i) {
+                *w = (*w).max(cell.chars().count());
+            }
+        }
+    }
+
+    let format_row = |cells: &[String]| -> String {
+        cells
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, w)| format!("{cell:<w$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut out = String::new();
+    let headers: Vec<String> = table.headers.iter().map(|h| h.to_string()).collect();
+    out.push_str(&format_row(&headers));
+    out.push('\n');
+    let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+    out.push_str(&format_row(&rule));
+    out.push('\n');
+    for row in &table.rows {
+        out.push_str(&format_row(row));
+        out.push('\n');
+    }
+    out
+}
+
+/// equity を表示用に小数 4 桁へ丸める責務を持つ。
+pub fn format_equity(equity: f64) -> String {
+    format!("{equity:.4}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> Report {
+        Report {
+            json: json!({ "hand": "As Ks", "equity": 0.5 }),
+            table: Table {
+                headers: vec!["hand", "equity"],
+                rows: vec![
+                    vec!["As Ks".to_string(), "0.5000".to_string()],
+                    vec!["AKs, QQ".to_string(), "0.1".to_string()],
+                ],
+            },
+        }
+    }
+
+    #[test]
+    fn render_csv_quotes_cells_with_commas() {
+        let output = sample().render(Format::Csv);
+
+        assert_eq!(output, "hand,equity\nAs Ks,0.5000\n\"AKs, QQ\",0.1\n");
+    }
+
+    #[test]
+    fn render_table_pads_columns_to_widest_cell() {
+        let output = sample().render(Format::Table);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines[0], "hand     equity");
+        assert_eq!(lines[1], "-------  ------");
+        assert_eq!(lines[2], "As Ks    0.5000");
+    }
+
+    #[test]
+    fn render_json_outputs_the_serialized_payload() {
+        let output = sample().render(Format::Json);
+        let parsed: Value = serde_json::from_str(&output).expect("valid json");
+
+        assert_eq!(parsed["hand"], "As Ks");
+    }
+}
@@ -0,0 +1,112 @@
+use std::num::NonZeroUsize;
+use std::thread;
+
+/// 1 スレッド分の試行回数とシードを保持する責務を持つ。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shard {
+    pub trials: u32,
+    pub seed: u64,
+}
+
+/// `--threads` 未指定時に使うスレッド数を決める責務を持つ。
+///
+/// OS が論理コア数を返せない環境では 1 スレッドにフォールバックする。
+pub fn default_threads() -> usize {
+    thread::available_parallelism()
+        .map(NonZeroUsize::get)
+        .unwrap_or(1)
+}
+
+/// 試行回数をスレッド数で分割する責務を持つ。
+///
+/// 余りは先頭のシャードから 1 回ずつ配る。試行回数がスレッド数より少ない場合は
+/// 0 回のシャードを作らないよう、シャード数を試行回数まで減らす。
+/// シャード 0 はユーザー指定のシードをそのまま使うので、`threads = 1` なら
+/// ライブラリを直接呼んだ場合と同じ結果になる。
+pub fn plan(trials: u32, seed: u64, threads: usize) -> Vec<Shard> {
+    let trials = trials.max(1);
+    let count = threads.clamp(1, trials as usize) as u32;
+    let base = trials / count;
+    let extra = trials % count;
+
+    (0..count)
+        .map(|i| Shard {
+            trials: base + u32::from(i < extra),
+            seed: seed.wrapping_add(u64::from(i).wrapping_mul(0x9E37_79B9_7F4A_7C15)),
+        })
+        .collect()
+}
+
+/// シャードごとにシミュレーションを別スレッドで実行する責務を持つ。
+///
+/// 戻り値はシャードと同じ順序に並ぶ。どれか 1 つでも失敗したら最初のエラーを返す。
+pub fn run<T, F>(shards: &[Shard], simulate: F) -> Result<Vec<T>, String>
+where
+    T: Send,
+    F: Fn(Shard) -> Result<T, String> + Sync,
+{
+    if shards.len() <= 1 {
+        return shards.iter().map(|shard| simulate(*shard)).collect();
+    }
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = shards
+            .iter()
+            .map(|shard| {
+                let simulate = &simulate;
+                scope.spawn(move || simulate(*shard))
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("simulation thread panicked"))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_distributes_remainder_to_leading_shards() {
+        let shards = plan(10, 7, 4);
+        let trials: Vec<u32> = shards.iter().map(|s| s.trials).collect();
+
+        assert_eq!(trials, vec![3, 3, 2, 2]);
+        assert_eq!(shards[0].seed, 7);
+    }
+
+    #[test]
+    fn plan_never_creates_empty_shards() {
+        let shards = plan(2, 1, 8);
+
+        assert_eq!(shards.len(), 2);
+        assert!(shards.iter().all(|s| s.trials == 1));
+    }
+
+    #[test]
+    fn plan_gives_each_shard_a_distinct_seed() {
+        let shards = plan(100, 42, 4);
+        let seeds: std::collections::HashSet<u64> = shards.iter().map(|s| s.seed).collect();
+
+        assert_eq!(seeds.len(), 4);
+    }
+
+    #[test]
+    fn run_returns_results_in_shard_order() {
+        let shards = plan(9, 0, 3);
+        let results = run(&shards, |shard| Ok(shard.trials)).expect("shards succeed");
+
+        assert_eq!(results, vec![3, 3, 3]);
+    }
+
+    #[test]
+    fn run_propagates_errors() {
+        let shards = plan(4, 0, 2);
+        let result: Result<Vec<u32>, String> = run(&shards, |_| Err("boom".to_string()));
+
+        assert_eq!(result, Err("boom".to_string()));
+    }
+}
@@ -1,7 +1,9 @@
+use serde::Serialize;
+
 /// 1 ハンドごとのシミュレーション結果を保持する責務を持つ DTO。
 ///
 /// 100 回の各ハンドで、配られたカード・CFR が選んだアクション・損益を後から確認できるようにする。
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct HeadsUpHandResult {
     pub hand_number: usize,
     pub player_1_hand: String,
@@ -19,7 +21,7 @@ pub struct HeadsUpHandResult {
 ///
 /// アプリ側で表示・検証しやすいように、勝敗数だけでなく fold 数、all-in 数、
 /// BB 単位の損益、1 ハンドあたりの平均終了スタック、各ハンドの詳細も保持する。
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct HeadsUpSimulationResult {
     pub trials: usize,
     pub player_1_strategy: String,
@@ -1,29 +1,49 @@
 //! WASM-friendly FFI that wraps `rs-poker` to evaluate hands and compare
 //! winners. Returns simple integers so JavaScript/TypeScript can consume
 //! without extra decoding.
+//!
+//! `rs_poker_native` is public because `rust-cli` calls it without going
+//! through the FFI buffers, and `sim` because it holds the native-only entry
+//! points (parallel runs, binary decoders) listed in the README.
+//!
+//! Building with `--features shards` adds map/reduce exports (`shards.rs`)
+//! so the web app can split one simulation across several workers.
 
-mod rs_poker_native;
-mod sim;
+pub mod rs_poker_native;
+pub mod sim;
+mod six_max;
+
+#[cfg(feature = "shards")]
+mod shards;
 
 use rs_poker_native::{
     parse_range_to_hands as parse_range_to_hands_internal,
     simulate_multi_hand_equity as simulate_multi_hand_equity_internal,
     simulate_multi_hand_equity_with_progress as simulate_multi_hand_equity_with_progress_internal,
     simulate_open_ranges_monte_carlo as simulate_open_ranges_monte_carlo_internal,
+    simulate_open_ranges_monte_carlo_with_variance_reduction as simulate_open_ranges_monte_carlo_with_variance_reduction_internal,
     simulate_range_vs_range_equity as simulate_range_vs_range_equity_internal,
     simulate_range_vs_range_equity_with_progress as simulate_range_vs_range_equity_with_progress_internal,
+    simulate_range_vs_range_equity_with_variance_reduction as simulate_range_vs_range_equity_with_variance_reduction_internal,
     simulate_rank_distribution as simulate_rank_distribution_internal,
     simulate_rank_distribution_with_progress as simulate_rank_distribution_with_progress_internal,
     simulate_vs_list_equity as simulate_vs_list_equity_internal,
     simulate_vs_list_equity_with_progress as simulate_vs_list_equity_with_progress_internal,
+    simulate_vs_list_equity_with_variance_reduction as simulate_vs_list_equity_with_variance_reduction_internal,
     simulate_vs_list_with_ranks_monte_carlo as simulate_vs_list_with_ranks_monte_carlo_internal,
+    EquityRecord,
+};
+use sim::{
+    binary, decode_hand_pair, parse_board, parse_hand_two, parse_hands_min1, Card as SimCard,
+    RankedRecord, VarianceReduction, VsListTrace, TRACE_RECORD_LEN,
 };
 use sim::{
-    eval::best_of, simulate_vs_list_with_ranks as simulate_vs_list_with_ranks_internal,
+    eval::best_five_of,
+    replay_vs_list_with_ranks_trial as replay_vs_list_with_ranks_trial_internal,
+    simulate_vs_list_with_ranks as simulate_vs_list_with_ranks_internal,
     simulate_vs_list_with_ranks_trace as simulate_vs_list_with_ranks_trace_internal,
     simulate_vs_list_with_ranks_with_progress as simulate_vs_list_with_ranks_with_progress_internal,
 };
-use sim::{parse_board, parse_hands_min1, Card as SimCard};
 
 #[cfg(target_arch = "wasm32")]
 #[link(wasm_import_module = "env")]
@@ -68,6 +88,7 @@ mod wasm_getrandom {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_simulation(
     hero_ptr: *const u8,
     hero_len: usize,
@@ -84,7 +105,7 @@ fn run_simulation(
         &str,
         &str,
     )
-        -> Result<Vec<(u32, u32, u32, u32, u32, [u32; 9], [u32; 9], [u32; 9])>, i32>,
+        -> Result<Vec<RankedRecord>, i32>,
 ) -> i32 {
     // used by closures passed in via `runner`
     let _ = (trials, seed);
@@ -112,6 +133,15 @@ fn run_simulation(
         Err(code) => return code,
     };
 
+    write_ranked_records(&results, out_ptr, out_len)
+}
+
+/// Write `simulate_vs_list_with_ranks` records (32 u32 each) into the caller buffer.
+fn write_ranked_records(
+    results: &[RankedRecord],
+    out_ptr: *mut u32,
+    out_len: usize,
+) -> i32 {
     let needed = results.len() * 32;
     if out_len < needed {
         return -6;
@@ -119,7 +149,7 @@ fn run_simulation(
 
     let out = unsafe { std::slice::from_raw_parts_mut(out_ptr, out_len) };
     for ((c1, c2, w, t, p, rank_wins, rank_ties, rank_lose_counts), chunk) in
-        results.iter().zip(out.chunks_exact_mut(32))
+        results.iter().zip(out.as_chunks_mut::<32>().0)
     {
         chunk[0] = *c1;
         chunk[1] = *c2;
@@ -134,6 +164,7 @@ fn run_simulation(
     results.len() as i32
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_simulation_trace(
     hero_ptr: *const u8,
     hero_len: usize,
@@ -178,13 +209,14 @@ fn run_simulation_trace(
     }
 
     let out = unsafe { std::slice::from_raw_parts_mut(out_ptr, out_len) };
-    for (row, chunk) in results.iter().zip(out.chunks_exact_mut(11)) {
+    for (row, chunk) in results.iter().zip(out.as_chunks_mut::<11>().0) {
         chunk.copy_from_slice(row);
     }
 
     results.len() as i32
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_rank_distribution(
     hands_ptr: *const u8,
     hands_len: usize,
@@ -216,19 +248,25 @@ fn run_rank_distribution(
         Err(code) => return code,
     };
 
+    write_rank_counts(&results, out_ptr, out_len)
+}
+
+/// Write rank distribution rows (9 u32 each) into the caller buffer.
+fn write_rank_counts(results: &[[u32; 9]], out_ptr: *mut u32, out_len: usize) -> i32 {
     let needed = results.len() * 9;
     if out_len < needed {
         return -6;
     }
 
     let out = unsafe { std::slice::from_raw_parts_mut(out_ptr, out_len) };
-    for (counts, chunk) in results.iter().zip(out.chunks_exact_mut(9)) {
+    for (counts, chunk) in results.iter().zip(out.as_chunks_mut::<9>().0) {
         chunk.copy_from_slice(counts);
     }
 
     results.len() as i32
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_equity(
     hero_ptr: *const u8,
     hero_len: usize,
@@ -241,7 +279,7 @@ fn run_equity(
     seed: u64,
     out_ptr: *mut u32,
     out_len: usize,
-    mut runner: impl FnMut(&str, &str, &str, u32) -> Result<Vec<(u32, u32, u32, u32, u32)>, i32>,
+    mut runner: impl FnMut(&str, &str, &str, u32) -> Result<Vec<EquityRecord>, i32>,
 ) -> i32 {
     let _ = (trials, seed);
     if hero_ptr.is_null() || out_ptr.is_null() || compare_ptr.is_null() {
@@ -274,7 +312,7 @@ fn run_equity(
     }
 
     let out = unsafe { std::slice::from_raw_parts_mut(out_ptr, out_len) };
-    for ((c1, c2, w, t, p), chunk) in results.iter().zip(out.chunks_exact_mut(5)) {
+    for ((c1, c2, w, t, p), chunk) in results.iter().zip(out.as_chunks_mut::<5>().0) {
         chunk[0] = *c1;
         chunk[1] = *c2;
         chunk[2] = *w;
@@ -285,6 +323,7 @@ fn run_equity(
     results.len() as i32
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_multi_equity(
     hands_ptr: *const u8,
     hands_len: usize,
@@ -322,7 +361,7 @@ fn run_multi_equity(
     }
 
     let out = unsafe { std::slice::from_raw_parts_mut(out_ptr, out_len) };
-    for ((c1, c2, eq), chunk) in results.iter().zip(out.chunks_exact_mut(3)) {
+    for ((c1, c2, eq), chunk) in results.iter().zip(out.as_chunks_mut::<3>().0) {
         chunk[0] = *c1;
         chunk[1] = *c2;
         chunk[2] = *eq;
@@ -331,6 +370,7 @@ fn run_multi_equity(
     results.len() as i32
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_range_equity(
     hero_ptr: *const u8,
     hero_len: usize,
@@ -375,7 +415,7 @@ fn run_range_equity(
     }
 
     let out = unsafe { std::slice::from_raw_parts_mut(out_ptr, out_len) };
-    for ((c1, c2, equity, role), chunk) in results.iter().zip(out.chunks_exact_mut(4)) {
+    for ((c1, c2, equity, role), chunk) in results.iter().zip(out.as_chunks_mut::<4>().0) {
         chunk[0] = *c1;
         chunk[1] = *c2;
         chunk[2] = *equity;
@@ -385,6 +425,39 @@ fn run_range_equity(
     results.len() as i32
 }
 
+/// Shared by the `*_binary` and trace page exports: decode three UTF-8 inputs, run,
+/// and copy the resulting words into the caller buffer. Returns the number of words.
+fn run_words(
+    inputs: [(*const u8, usize); 3],
+    out_ptr: *mut u32,
+    out_len: usize,
+    mut runner: impl FnMut(&str, &str, &str) -> Result<Vec<u32>, i32>,
+) -> i32 {
+    if inputs.iter().any(|(ptr, _)| ptr.is_null()) || out_ptr.is_null() {
+        return -1;
+    }
+    let mut strs = [""; 3];
+    for (i, (ptr, len)) in inputs.into_iter().enumerate() {
+        let slice = unsafe { std::slice::from_raw_parts(ptr, len) };
+        strs[i] = match std::str::from_utf8(slice) {
+            Ok(s) => s,
+            Err(_) => return -2 - i as i32,
+        };
+    }
+
+    let words = match runner(strs[0], strs[1], strs[2]) {
+        Ok(v) => v,
+        Err(code) => return code,
+    };
+    if out_len < words.len() {
+        return -6;
+    }
+
+    let out = unsafe { std::slice::from_raw_parts_mut(out_ptr, words.len()) };
+    out.copy_from_slice(&words);
+    words.len() as i32
+}
+
 fn run_parse_range(
     range_ptr: *const u8,
     range_len: usize,
@@ -412,7 +485,7 @@ fn run_parse_range(
     }
 
     let out = unsafe { std::slice::from_raw_parts_mut(out_ptr, out_len) };
-    for ((c1, c2), chunk) in results.iter().zip(out.chunks_exact_mut(2)) {
+    for ((c1, c2), chunk) in results.iter().zip(out.as_chunks_mut::<2>().0) {
         chunk[0] = *c1;
         chunk[1] = *c2;
     }
@@ -447,9 +520,12 @@ fn encode_sim_card(card: &SimCard) -> u32 {
 }
 
 /// Evaluate and rank multiple hands for a given board.
-/// Output per hand: [card1, card2, rankIndex, encoded, kicker1..kicker5] (9 u32s).
-/// out_len must be >= hands_count * 9. Returns record count or negative error.
+/// Output per hand: [card1, card2, rankIndex, encoded, kicker1..kicker5, best1..best5] (14 u32s).
+/// kicker1..kicker5 are the tie-break ranks; best1..best5 are the five cards forming the
+/// hand, category cards first and kickers after (see `order_best_five` in sim/eval.rs).
+/// out_len must be >= hands_count * 14. Returns record count or negative error.
 #[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref, clippy::type_complexity)]
 pub extern "C" fn evaluate_hands_ranking(
     hands_ptr: *const u8,
     hands_len: usize,
@@ -490,24 +566,26 @@ pub extern "C" fn evaluate_hands_ranking(
         }
     }
 
-    let needed = hands.len().saturating_mul(9);
+    let needed = hands.len().saturating_mul(14);
     if out_len < needed {
         return -6;
     }
 
-    let mut ranked: Vec<(u32, u32, u32, u32, [u8; 5])> = Vec::with_capacity(hands.len());
+    let mut ranked: Vec<(u32, u32, u32, u32, [u8; 5], [SimCard; 5])> =
+        Vec::with_capacity(hands.len());
     for hand in &hands {
         let mut cards: Vec<SimCard> = Vec::with_capacity(board.len() + 2);
         cards.extend_from_slice(&board);
         cards.push(hand[0]);
         cards.push(hand[1]);
-        let score = best_of(&cards);
+        let (score, best_five) = best_five_of(&cards);
         ranked.push((
             encode_sim_card(&hand[0]),
             encode_sim_card(&hand[1]),
             score.rank as u32,
             score.encoded,
             score.kickers,
+            best_five,
         ));
     }
 
@@ -518,13 +596,14 @@ pub extern "C" fn evaluate_hands_ranking(
     });
 
     let out = unsafe { std::slice::from_raw_parts_mut(out_ptr, out_len) };
-    for (row, chunk) in ranked.iter().zip(out.chunks_exact_mut(9)) {
+    for (row, chunk) in ranked.iter().zip(out.as_chunks_mut::<14>().0) {
         chunk[0] = row.0;
         chunk[1] = row.1;
         chunk[2] = row.2;
         chunk[3] = row.3;
         for i in 0..5 {
             chunk[4 + i] = row.4[i] as u32;
+            chunk[9 + i] = encode_sim_card(&row.5[i]);
         }
     }
 
@@ -565,6 +644,48 @@ pub extern "C" fn simulate_vs_list_with_ranks(
     )
 }
 
+/// Same simulation as `simulate_vs_list_with_ranks`, written in the versioned binary
+/// format of `sim::binary` (header + 35-word records, hero aggregate last).
+/// out_len must be >= 6 + (compareCount + 1) * 35. Returns words written or negative error.
+#[no_mangle]
+pub extern "C" fn simulate_vs_list_with_ranks_binary(
+    hero_ptr: *const u8,
+    hero_len: usize,
+    board_ptr: *const u8,
+    board_len: usize,
+    compare_ptr: *const u8,
+    compare_len: usize,
+    trials: u32,
+    seed: u64,
+    out_ptr: *mut u32,
+    out_len: usize,
+) -> i32 {
+    run_words(
+        [
+            (hero_ptr, hero_len),
+            (board_ptr, board_len),
+            (compare_ptr, compare_len),
+        ],
+        out_ptr,
+        out_len,
+        |hero_str, board_str, compare_str| {
+            let hero = parse_hand_two(hero_str).ok_or(-5)?;
+            let rows = simulate_vs_list_with_ranks_internal(
+                hero_str,
+                board_str,
+                compare_str,
+                trials,
+                seed,
+            )
+            .map_err(|_| -5)?;
+            Ok(binary::encode_vs_list_with_ranks(
+                decode_hand_pair(&hero),
+                &rows,
+            ))
+        },
+    )
+}
+
 /// Progress-reporting variant. Emits progress via imported `report_progress` callback (0-100),
 /// and writes the same output layout as `simulate_vs_list_with_ranks`.
 #[no_mangle]
@@ -598,7 +719,7 @@ pub extern "C" fn simulate_vs_list_with_ranks_with_progress(
                 compare_str,
                 trials,
                 seed,
-                Some(|p| emit_progress(p)),
+                Some(emit_progress),
             )
             .map_err(|_| -5)
         },
@@ -645,6 +766,96 @@ pub extern "C" fn simulate_vs_list_with_ranks_trace(
     )
 }
 
+/// Streaming variant of `simulate_vs_list_with_ranks_trace` (same runouts, same order).
+/// Writes records `start..` until the buffer is full, TRACE_RECORD_LEN (18) u32 each:
+/// [hero1, hero2, board1..board5, opp1, opp2, outcome, rankIndex, best1..best5,
+/// heroRankIndex, villainRankIndex], where rankIndex / best* describe the winning hand
+/// (hero's on a tie).
+/// Call again with `start` advanced by the returned record count until it returns 0.
+/// Returns record count or negative error.
+#[no_mangle]
+pub extern "C" fn simulate_vs_list_with_ranks_trace_page(
+    hero_ptr: *const u8,
+    hero_len: usize,
+    board_ptr: *const u8,
+    board_len: usize,
+    compare_ptr: *const u8,
+    compare_len: usize,
+    trials: u32,
+    seed: u64,
+    start: u64,
+    out_ptr: *mut u32,
+    out_len: usize,
+) -> i32 {
+    let words = run_words(
+        [
+            (hero_ptr, hero_len),
+            (board_ptr, board_len),
+            (compare_ptr, compare_len),
+        ],
+        out_ptr,
+        out_len,
+        |hero_str, board_str, compare_str| {
+            let mut trace =
+                VsListTrace::new(hero_str, board_str, compare_str, trials, seed).map_err(|_| -5)?;
+            trace.seek(start);
+            Ok(trace
+                .take(out_len / TRACE_RECORD_LEN)
+                .flat_map(|record| record.to_words())
+                .collect())
+        },
+    );
+    if words < 0 {
+        return words;
+    }
+    words / TRACE_RECORD_LEN as i32
+}
+
+/// Rebuild one trial of the trace from (seed, trial index) without running the
+/// others. `trial_index = opponentIndex * trials + trial`. Writes one 18-u32 record
+/// (same layout as `simulate_vs_list_with_ranks_trace_page`) and returns 1, or a
+/// negative error (-5 also for an out-of-range index).
+#[no_mangle]
+pub extern "C" fn replay_vs_list_with_ranks_trial(
+    hero_ptr: *const u8,
+    hero_len: usize,
+    board_ptr: *const u8,
+    board_len: usize,
+    compare_ptr: *const u8,
+    compare_len: usize,
+    trials: u32,
+    seed: u64,
+    trial_index: u64,
+    out_ptr: *mut u32,
+    out_len: usize,
+) -> i32 {
+    let words = run_words(
+        [
+            (hero_ptr, hero_len),
+            (board_ptr, board_len),
+            (compare_ptr, compare_len),
+        ],
+        out_ptr,
+        out_len,
+        |hero_str, board_str, compare_str| {
+            replay_vs_list_with_ranks_trial_internal(
+                hero_str,
+                board_str,
+                compare_str,
+                trials,
+                seed,
+                trial_index,
+            )
+            .map(|record| record.to_words().to_vec())
+            .map_err(|_| -5)
+        },
+    );
+    if words < 0 {
+        return words;
+    }
+    1
+}
+
 /// Hero vs provided opponent list (heads-up) Monte Carlo with rank distribution (winner ranks only).
 /// Output per record: [oppCard1, oppCard2, heroWins, ties, plays, rankWin0..rankWin8, rankTie0..rankTie8, rankLose0..rankLose8]
 /// out_len must be >= records * 32 (compareCount * 32). Returns record count or negative error.
@@ -698,6 +909,65 @@ pub extern "C" fn simulate_open_ranges_monte_carlo(
     seed: u64,
     out_ptr: *mut u32,
     out_len: usize,
+) -> i32 {
+    run_open_ranges(
+        hero_ptr,
+        hero_len,
+        opponents_ptr,
+        opponents_len,
+        out_ptr,
+        out_len,
+        |hero_str, opponents_str| {
+            simulate_open_ranges_monte_carlo_internal(hero_str, opponents_str, trials, seed)
+                .map_err(|_| -5)
+        },
+    )
+}
+
+/// `simulate_open_ranges_monte_carlo` with variance reduction. `variance` is a bit set:
+/// 1 = stratify the first board card, 2 = antithetic runouts (4, the control variate, is
+/// rejected because the hands change every deal). Same output layout.
+#[no_mangle]
+pub extern "C" fn simulate_open_ranges_monte_carlo_with_variance_reduction(
+    hero_ptr: *const u8,
+    hero_len: usize,
+    opponents_ptr: *const u8,
+    opponents_len: usize,
+    trials: u32,
+    seed: u64,
+    variance: u32,
+    out_ptr: *mut u32,
+    out_len: usize,
+) -> i32 {
+    run_open_ranges(
+        hero_ptr,
+        hero_len,
+        opponents_ptr,
+        opponents_len,
+        out_ptr,
+        out_len,
+        |hero_str, opponents_str| {
+            simulate_open_ranges_monte_carlo_with_variance_reduction_internal(
+                hero_str,
+                opponents_str,
+                trials,
+                seed,
+                VarianceReduction::from_bits(variance),
+            )
+            .map(|t| (t.wins, t.ties, t.plays, t.hero_rank_wins))
+            .map_err(|_| -5)
+        },
+    )
+}
+
+fn run_open_ranges(
+    hero_ptr: *const u8,
+    hero_len: usize,
+    opponents_ptr: *const u8,
+    opponents_len: usize,
+    out_ptr: *mut u32,
+    out_len: usize,
+    runner: impl FnOnce(&str, &str) -> Result<(u32, u32, u32, [u32; 9]), i32>,
 ) -> i32 {
     if hero_ptr.is_null() || opponents_ptr.is_null() || out_ptr.is_null() {
         return -1;
@@ -717,11 +987,10 @@ pub extern "C" fn simulate_open_ranges_monte_carlo(
         return -6;
     }
 
-    let result =
-        match simulate_open_ranges_monte_carlo_internal(hero_str, opponents_str, trials, seed) {
-            Ok(v) => v,
-            Err(_) => return -5,
-        };
+    let result = match runner(hero_str, opponents_str) {
+        Ok(v) => v,
+        Err(code) => return code,
+    };
 
     let out = unsafe { std::slice::from_raw_parts_mut(out_ptr, out_len) };
     out[0] = result.0;
@@ -731,6 +1000,99 @@ pub extern "C" fn simulate_open_ranges_monte_carlo(
     1
 }
 
+/// 6-max preflop scenario (see `six_max`): hero at `hero_position` (0 = UTG .. 5 = BB) takes
+/// `action` (0 = open, 1 = call, 2 = 3-bet), facing an open from `opener_position` (ignored
+/// for an open). Each range list is six semicolon-separated ranges, UTG first. Blinds and
+/// sizes are in chips; sizes are the total bet.
+/// Output layout (f64): [deals, folds, equity, averageOpponents, ev, potBefore, investment,
+/// continueUtg .. continueBb]. out_len must be >= 13. Returns 1 on success or negative error.
+#[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub extern "C" fn evaluate_six_max_preflop(
+    hero_ptr: *const u8,
+    hero_len: usize,
+    open_ptr: *const u8,
+    open_len: usize,
+    call_ptr: *const u8,
+    call_len: usize,
+    three_bet_ptr: *const u8,
+    three_bet_len: usize,
+    hero_position: u32,
+    action: u32,
+    opener_position: u32,
+    small_blind: f64,
+    big_blind: f64,
+    open_size: f64,
+    three_bet_size: f64,
+    trials: u32,
+    seed: u64,
+    out_ptr: *mut f64,
+    out_len: usize,
+) -> i32 {
+    if hero_ptr.is_null()
+        || open_ptr.is_null()
+        || call_ptr.is_null()
+        || three_bet_ptr.is_null()
+        || out_ptr.is_null()
+    {
+        return -1;
+    }
+    let read = |ptr: *const u8, len: usize| {
+        std::str::from_utf8(unsafe { std::slice::from_raw_parts(ptr, len) }).map_err(|_| -2)
+    };
+    let strings = (|| {
+        Ok::<_, i32>((
+            read(hero_ptr, hero_len)?,
+            read(open_ptr, open_len)?,
+            read(call_ptr, call_len)?,
+            read(three_bet_ptr, three_bet_len)?,
+        ))
+    })();
+    let (hero_str, open_str, call_str, three_bet_str) = match strings {
+        Ok(v) => v,
+        Err(code) => return code,
+    };
+    let (Some(hero), Some(action)) = (
+        six_max::Position::from_index(hero_position),
+        six_max::PreflopAction::from_index(action),
+    ) else {
+        return -4;
+    };
+    let opener = six_max::Position::from_index(opener_position);
+
+    if out_len < 13 {
+        return -6;
+    }
+
+    let ranges = match six_max::PositionRanges::parse(open_str, call_str, three_bet_str) {
+        Ok(r) => r,
+        Err(_) => return -5,
+    };
+    let sizes = six_max::SixMaxSizes {
+        small_blind,
+        big_blind,
+        open: open_size,
+        three_bet: three_bet_size,
+    };
+    let result = match six_max::evaluate_six_max_preflop(
+        hero, hero_str, action, opener, &ranges, sizes, trials, seed,
+    ) {
+        Ok(v) => v,
+        Err(_) => return -5,
+    };
+
+    let out = unsafe { std::slice::from_raw_parts_mut(out_ptr, out_len) };
+    out[0] = result.deals as f64;
+    out[1] = result.folds as f64;
+    out[2] = result.equity;
+    out[3] = result.average_opponents;
+    out[4] = result.ev;
+    out[5] = result.pot_before;
+    out[6] = result.investment;
+    out[7..13].copy_from_slice(&result.continue_frequency);
+    1
+}
+
 /// Hero vs provided opponent list, returning equity-only stats (wins/ties/plays).
 /// Output per record: [oppCard1, oppCard2, heroWins, ties, plays].
 /// When include_data == 0, returns only the hero aggregate record.
@@ -778,6 +1140,51 @@ pub extern "C" fn simulate_vs_list_equity(
     )
 }
 
+/// Heads-up `simulate_vs_list_equity` (one opponent at a time) with variance reduction.
+/// `variance` is a bit set: 1 = stratify the first missing board card, 2 = antithetic
+/// runouts, 4 = control variate on the next street. Same record layout.
+#[no_mangle]
+pub extern "C" fn simulate_vs_list_equity_with_variance_reduction(
+    hero_ptr: *const u8,
+    hero_len: usize,
+    board_ptr: *const u8,
+    board_len: usize,
+    compare_ptr: *const u8,
+    compare_len: usize,
+    trials: u32,
+    seed: u64,
+    include_data: u32,
+    variance: u32,
+    out_ptr: *mut u32,
+    out_len: usize,
+) -> i32 {
+    run_equity(
+        hero_ptr,
+        hero_len,
+        board_ptr,
+        board_len,
+        compare_ptr,
+        compare_len,
+        1,
+        trials,
+        seed,
+        out_ptr,
+        out_len,
+        |hero_str, board_str, compare_str, _| {
+            simulate_vs_list_equity_with_variance_reduction_internal(
+                hero_str,
+                board_str,
+                compare_str,
+                trials,
+                seed,
+                include_data != 0,
+                VarianceReduction::from_bits(variance),
+            )
+            .map_err(|_| -5)
+        },
+    )
+}
+
 /// Multi-hand equity (3-6 players), returning equity_scaled (1e6).
 /// Output per record: [card1, card2, equity_scaled].
 /// out_len must be >= hands_count * 3. Returns record count or negative error.
@@ -834,7 +1241,7 @@ pub extern "C" fn simulate_multi_hand_equity_with_progress(
                 board_str,
                 trials,
                 seed,
-                Some(|p| emit_progress(p)),
+                Some(emit_progress),
             )
             .map_err(|_| -5)
         },
@@ -878,7 +1285,7 @@ pub extern "C" fn simulate_vs_list_equity_with_progress(
                 opponents_count,
                 trials,
                 seed,
-                Some(|p| emit_progress(p)),
+                Some(emit_progress),
                 include_data != 0,
             )
             .map_err(|_| -5)
@@ -919,6 +1326,47 @@ pub extern "C" fn simulate_range_vs_range_equity(
     )
 }
 
+/// Range vs range equity with variance reduction, dealt from `seed`. `variance` uses the
+/// same bits as `simulate_vs_list_equity_with_variance_reduction`.
+#[no_mangle]
+pub extern "C" fn simulate_range_vs_range_equity_with_variance_reduction(
+    hero_ptr: *const u8,
+    hero_len: usize,
+    villain_ptr: *const u8,
+    villain_len: usize,
+    board_ptr: *const u8,
+    board_len: usize,
+    trials: u32,
+    seed: u64,
+    variance: u32,
+    out_ptr: *mut u32,
+    out_len: usize,
+) -> i32 {
+    run_range_equity(
+        hero_ptr,
+        hero_len,
+        villain_ptr,
+        villain_len,
+        board_ptr,
+        board_len,
+        trials,
+        seed,
+        out_ptr,
+        out_len,
+        |hero_str, villain_str, board_str| {
+            simulate_range_vs_range_equity_with_variance_reduction_internal(
+                hero_str,
+                villain_str,
+                board_str,
+                trials,
+                seed,
+                VarianceReduction::from_bits(variance),
+            )
+            .map_err(|_| -5)
+        },
+    )
+}
+
 /// Range vs range equity with progress. Emits progress via `report_progress`.
 #[no_mangle]
 pub extern "C" fn simulate_range_vs_range_equity_with_progress(
@@ -951,13 +1399,44 @@ pub extern "C" fn simulate_range_vs_range_equity_with_progress(
                 board_str,
                 trials,
                 seed,
-                Some(|p| emit_progress(p)),
+                Some(emit_progress),
             )
             .map_err(|_| -5)
         },
     )
 }
 
+/// Same simulation as `simulate_range_vs_range_equity`, written in the versioned binary
+/// format of `sim::binary` (header + 4-word records). Returns words written or negative error.
+#[no_mangle]
+pub extern "C" fn simulate_range_vs_range_equity_binary(
+    hero_ptr: *const u8,
+    hero_len: usize,
+    villain_ptr: *const u8,
+    villain_len: usize,
+    board_ptr: *const u8,
+    board_len: usize,
+    trials: u32,
+    seed: u64,
+    out_ptr: *mut u32,
+    out_len: usize,
+) -> i32 {
+    run_words(
+        [
+            (hero_ptr, hero_len),
+            (villain_ptr, villain_len),
+            (board_ptr, board_len),
+        ],
+        out_ptr,
+        out_len,
+        |hero_str, villain_str, board_str| {
+            simulate_range_vs_range_equity_internal(hero_str, villain_str, board_str, trials, seed)
+                .map(|rows| binary::encode_range_vs_range(&rows))
+                .map_err(|_| -5)
+        },
+    )
+}
+
 /// Multiple hands rank distribution for a partial board (>=3 cards).
 /// Output per hand: [rank0..rank8]. out_len must be >= hands_count * 9.
 #[no_mangle]
@@ -1013,7 +1492,7 @@ pub extern "C" fn simulate_rank_distribution_with_progress(
                 board_str,
                 trials,
                 seed,
-                Some(|p| emit_progress(p)),
+                Some(emit_progress),
             )
             .map_err(|_| -5)
         },
@@ -10,9 +10,17 @@ use crate::sim::{
   parse_hands_min1,
   Card as SimCard,
 };
-use crate::sim::{build_deck, eval::best_of, shuffle_slice, Lcg64};
+use crate::sim::parallel::{add_counts, chunk_count, chunk_trials, shard_units};
+use crate::sim::{
+  build_deck, deal_prefix, derive_seed, estimate_heads_up, eval::best_of, shuffle_slice,
+  DefaultRng, RankedRecord, RunoutSampler, SimRng, VarianceReduction,
+};
 use std::collections::HashSet;
 
+/// One row of the vs-list equity output: `(card1, card2, wins, ties, plays)`. The hero
+/// aggregate row has both cards set to `u32::MAX`.
+pub type EquityRecord = (u32, u32, u32, u32, u32);
+
 /// Hero vs provided opponent list, returning wins/ties/plays per opponent and hero aggregate
 /// (or hero aggregate only when include_data is false).
 pub fn simulate_vs_list_equity(
@@ -23,7 +31,7 @@ pub fn simulate_vs_list_equity(
   trials: u32,
   seed: u64,
   include_data: bool,
-) -> Result<Vec<(u32, u32, u32, u32, u32)>, String> {
+) -> Result<Vec<EquityRecord>, String> {
   simulate_vs_list_equity_inner::<fn(u32)>(
     hero_hand_str,
     board_str,
@@ -91,7 +99,7 @@ fn simulate_multi_hand_equity_inner<F: FnMut(u32)>(
   }
 
   let missing_board = 5usize.saturating_sub(board.len());
-  let mut rng = Lcg64::new(seed);
+  let mut rng = DefaultRng::new(seed);
   let trials = trials.max(1);
   let total_work = trials as u64;
   let mut completed = 0u64;
@@ -122,12 +130,9 @@ fn simulate_multi_hand_equity_inner<F: FnMut(u32)>(
       return Err("not enough cards to complete board".into());
     }
     let mut deck = build_deck(&exclude);
-    shuffle_slice(&mut deck, &mut rng);
 
     let mut full_board = board.clone();
-    for i in 0..missing_board {
-      full_board.push(deck[i]);
-    }
+    full_board.extend_from_slice(deal_prefix(&mut deck, missing_board, &mut rng));
 
     let mut scores = Vec::with_capacity(hands.len());
     for hand in &hands {
@@ -231,22 +236,8 @@ fn simulate_range_vs_range_equity_inner<F: FnMut(u32)>(
   mut progress: Option<F>,
 ) -> Result<Vec<(u32, u32, u32, u32)>, String> {
   let _ = seed;
-  let board = parse_board(board_str).ok_or("failed to parse board")?;
-  if board.len() > 5 {
-    return Err("board must be <=5 cards".into());
-  }
-
-  let hero_hands = parse_range_hands(hero_range_str)?;
-  let villain_hands = parse_range_hands(villain_range_str)?;
-  if hero_hands.is_empty() || villain_hands.is_empty() {
-    return Err("range must include at least 1 hand".into());
-  }
-
-  let hero_hands = filter_board_overlaps(hero_hands, &board);
-  let villain_hands = filter_board_overlaps(villain_hands, &board);
-  if hero_hands.is_empty() || villain_hands.is_empty() {
-    return Err("range overlaps board".into());
-  }
+  let (hero_hands, villain_hands, board) =
+    parse_range_vs_range_inputs(hero_range_str, villain_range_str, board_str)?;
 
   let total_work = (hero_hands.len() as u64)
     .saturating_mul(villain_hands.len() as u64)
@@ -339,6 +330,63 @@ fn simulate_range_vs_range_equity_inner<F: FnMut(u32)>(
   Ok(out)
 }
 
+/// [`simulate_range_vs_range_equity`] with optional variance reduction. Runouts are dealt
+/// from `seed` by the crate's own sampler instead of `MonteCarloGame`, so results are
+/// reproducible; plain options give ordinary independent sampling.
+///
+/// A control variate enumerates the next street once per hand pair (about 17k flops
+/// preflop), which only pays off with enough `trials` per pair.
+pub fn simulate_range_vs_range_equity_with_variance_reduction(
+  hero_range_str: &str,
+  villain_range_str: &str,
+  board_str: &str,
+  trials: u32,
+  seed: u64,
+  options: VarianceReduction,
+) -> Result<Vec<(u32, u32, u32, u32)>, String> {
+  let (mut hero_hands, mut villain_hands, board) =
+    parse_range_vs_range_inputs(hero_range_str, villain_range_str, board_str)?;
+  // the parser's order is not stable; sort so a seed always deals the same runouts
+  hero_hands.sort_by_key(decode_hand_pair);
+  villain_hands.sort_by_key(decode_hand_pair);
+  let trials = trials.max(1);
+  let mut rng = DefaultRng::new(seed);
+
+  // (sum of equity shares, plays) per hand
+  let mut hero_shares = vec![(0f64, 0u64); hero_hands.len()];
+  let mut villain_shares = vec![(0f64, 0u64); villain_hands.len()];
+  for (h_idx, hero) in hero_hands.iter().enumerate() {
+    for (v_idx, villain) in villain_hands.iter().enumerate() {
+      if hands_overlap(hero, villain) {
+        continue;
+      }
+      let estimate = estimate_heads_up(hero, villain, &board, trials, options, &mut rng);
+      let hero_share = estimate.win + estimate.tie / 2.0;
+      let weight = trials as f64;
+      hero_shares[h_idx].0 += hero_share * weight;
+      hero_shares[h_idx].1 += trials as u64;
+      villain_shares[v_idx].0 += (1.0 - hero_share) * weight;
+      villain_shares[v_idx].1 += trials as u64;
+    }
+  }
+
+  let mut out = Vec::with_capacity(hero_hands.len() + villain_hands.len());
+  let sides = [(0, &hero_hands, &hero_shares), (1, &villain_hands, &villain_shares)];
+  for (role, hands, shares) in sides {
+    for (hand, &(sum, plays)) in hands.iter().zip(shares.iter()) {
+      let encoded = decode_hand_pair(hand);
+      let equity_scaled = if plays == 0 {
+        0
+      } else {
+        (sum / plays as f64 * 1_000_000.0).round().clamp(0.0, 1_000_000.0) as u32
+      };
+      out.push((encoded.0, encoded.1, equity_scaled, role));
+    }
+  }
+  Ok(out)
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn simulate_vs_list_equity_with_progress<F: FnMut(u32)>(
   hero_hand_str: &str,
   board_str: &str,
@@ -348,7 +396,7 @@ pub fn simulate_vs_list_equity_with_progress<F: FnMut(u32)>(
   seed: u64,
   progress: Option<F>,
   include_data: bool,
-) -> Result<Vec<(u32, u32, u32, u32, u32)>, String> {
+) -> Result<Vec<EquityRecord>, String> {
   simulate_vs_list_equity_inner(
     hero_hand_str,
     board_str,
@@ -361,6 +409,7 @@ pub fn simulate_vs_list_equity_with_progress<F: FnMut(u32)>(
   )
 }
 
+#[allow(clippy::too_many_arguments)]
 fn simulate_vs_list_equity_inner<F: FnMut(u32)>(
   hero_hand_str: &str,
   board_str: &str,
@@ -370,29 +419,11 @@ fn simulate_vs_list_equity_inner<F: FnMut(u32)>(
   seed: u64,
   mut progress: Option<F>,
   include_data: bool,
-) -> Result<Vec<(u32, u32, u32, u32, u32)>, String> {
-  let hero_hand = parse_hand_two(hero_hand_str).ok_or("hero hand must have 2 cards")?;
+) -> Result<Vec<EquityRecord>, String> {
+  let (hero_hand, board, opponents) =
+    parse_vs_list_equity_inputs(hero_hand_str, board_str, compare_list)?;
 
-  let board = parse_board(board_str).ok_or("failed to parse board")?;
-  if board.len() > 5 {
-    return Err("board must be <=5 cards".into());
-  }
-
-  let mut opponents: Vec<[SimCard; 2]> = Vec::new();
-  for raw in compare_list.split(';') {
-    if raw.trim().is_empty() {
-      continue;
-    }
-    let hand = parse_hand_two(raw).ok_or("each compare hand must have 2 cards")?;
-    opponents.push(hand);
-  }
-  if opponents.is_empty() {
-    return Err("no compare hands provided".into());
-  }
-
-  validate_inputs(&hero_hand, &board, &opponents)?;
-
-  let opponents_count = opponents_count.max(1).min(5) as usize;
+  let opponents_count = opponents_count.clamp(1, 5) as usize;
   if opponents.len() < opponents_count {
     return Err("not enough compare hands for opponent count".into());
   }
@@ -414,9 +445,9 @@ fn simulate_vs_list_equity_inner<F: FnMut(u32)>(
   update_progress(0);
 
   let board_rs: Vec<Card> = board.iter().map(to_rs_card).collect();
-  let mut rng = Lcg64::new(seed);
+  let mut rng = DefaultRng::new(seed);
 
-  let mut stats: Vec<(u32, u32, u32, u32, u32)> =
+  let mut stats: Vec<EquityRecord> =
     Vec::with_capacity(if include_data { opponents.len() + 1 } else { 1 });
   let mut hero_wins_total = 0u32;
   let mut hero_ties_total = 0u32;
@@ -427,7 +458,7 @@ fn simulate_vs_list_equity_inner<F: FnMut(u32)>(
   }
 
   let opp_encoded: Vec<(u32, u32)> = if include_data {
-    opponents.iter().map(|h| decode_hand_pair(h)).collect()
+    opponents.iter().map(decode_hand_pair).collect()
   } else {
     Vec::new()
   };
@@ -466,10 +497,7 @@ fn simulate_vs_list_equity_inner<F: FnMut(u32)>(
         Vec::with_capacity(opponents_count.saturating_sub(1));
       if opponents_count > 1 {
         let mut pool = candidates.clone();
-        for i in (1..pool.len()).rev() {
-          let j = (rng.next_u32() as usize) % (i + 1);
-          pool.swap(i, j);
-        }
+        shuffle_slice(&mut pool, &mut rng);
         for c_idx in pool {
           if selected.len() >= opponents_count - 1 {
             break;
@@ -489,8 +517,8 @@ fn simulate_vs_list_equity_inner<F: FnMut(u32)>(
       }
 
       let mut participants = Vec::with_capacity(1 + opponents_count);
-      participants.push(hero_rs.clone());
-      participants.push(base_opp_rs.clone());
+      participants.push(hero_rs);
+      participants.push(base_opp_rs);
       for extra_idx in selected {
         let mut opp_rs = to_rs_hand(&opponents[extra_idx]);
         for c in &board_rs {
@@ -547,6 +575,41 @@ fn simulate_vs_list_equity_inner<F: FnMut(u32)>(
   Ok(stats)
 }
 
+/// Heads-up [`simulate_vs_list_equity`] (hero against each listed hand in turn) with
+/// optional variance reduction. Same records; with a control variate the win and tie
+/// counts are the adjusted estimates rounded to whole trials.
+pub fn simulate_vs_list_equity_with_variance_reduction(
+  hero_hand_str: &str,
+  board_str: &str,
+  compare_list: &str,
+  trials: u32,
+  seed: u64,
+  include_data: bool,
+  options: VarianceReduction,
+) -> Result<Vec<EquityRecord>, String> {
+  let (hero_hand, board, opponents) =
+    parse_vs_list_equity_inputs(hero_hand_str, board_str, compare_list)?;
+  let trials = trials.max(1);
+  let mut rng = DefaultRng::new(seed);
+
+  let mut stats = Vec::with_capacity(if include_data { opponents.len() + 1 } else { 1 });
+  let (mut wins_total, mut ties_total, mut plays_total) = (0u32, 0u32, 0u32);
+  for opp in &opponents {
+    let estimate = estimate_heads_up(&hero_hand, opp, &board, trials, options, &mut rng);
+    let wins = ((estimate.win * trials as f64).round() as u32).min(trials);
+    let ties = ((estimate.tie * trials as f64).round() as u32).min(trials - wins);
+    if include_data {
+      let encoded = decode_hand_pair(opp);
+      stats.push((encoded.0, encoded.1, wins, ties, trials));
+    }
+    wins_total = wins_total.saturating_add(wins);
+    ties_total = ties_total.saturating_add(ties);
+    plays_total = plays_total.saturating_add(trials);
+  }
+  stats.push((u32::MAX, u32::MAX, wins_total, ties_total, plays_total));
+  Ok(stats)
+}
+
 /// Hero vs provided opponent list, returning wins/ties and winner rank distribution via MonteCarloGame.
 pub fn simulate_vs_list_with_ranks_monte_carlo(
   hero_hand_str: &str,
@@ -554,7 +617,7 @@ pub fn simulate_vs_list_with_ranks_monte_carlo(
   compare_list: &str,
   trials: u32,
   seed: u64,
-) -> Result<Vec<(u32, u32, u32, u32, u32, [u32; 9], [u32; 9], [u32; 9])>, String> {
+) -> Result<Vec<RankedRecord>, String> {
   simulate_vs_list_with_ranks_monte_carlo_inner::<fn(u32)>(
     hero_hand_str,
     board_str,
@@ -572,7 +635,7 @@ fn simulate_vs_list_with_ranks_monte_carlo_inner<F: FnMut(u32)>(
   trials: u32,
   seed: u64,
   mut progress: Option<F>,
-) -> Result<Vec<(u32, u32, u32, u32, u32, [u32; 9], [u32; 9], [u32; 9])>, String> {
+) -> Result<Vec<RankedRecord>, String> {
   let _ = seed;
   let hero_hand = parse_hand_two(hero_hand_str).ok_or("hero hand must have 2 cards")?;
 
@@ -613,7 +676,7 @@ fn simulate_vs_list_with_ranks_monte_carlo_inner<F: FnMut(u32)>(
 
   let board_rs: Vec<Card> = board.iter().map(to_rs_card).collect();
 
-  let mut stats: Vec<(u32, u32, u32, u32, u32, [u32; 9], [u32; 9], [u32; 9])> = vec![
+  let mut stats: Vec<RankedRecord> = vec![
     (0, 0, 0, 0, 0, [0u32; 9], [0u32; 9], [0u32; 9]);
     opponents.len()
   ];
@@ -624,7 +687,7 @@ fn simulate_vs_list_with_ranks_monte_carlo_inner<F: FnMut(u32)>(
   let mut hero_ties_total = 0u32;
   let mut hero_plays_total = 0u32;
 
-  let opp_encoded: Vec<(u32, u32)> = opponents.iter().map(|h| decode_hand_pair(h)).collect();
+  let opp_encoded: Vec<(u32, u32)> = opponents.iter().map(decode_hand_pair).collect();
 
   for (idx, opp) in opponents.iter().enumerate() {
     let mut wins = 0u32;
@@ -662,11 +725,9 @@ fn simulate_vs_list_with_ranks_monte_carlo_inner<F: FnMut(u32)>(
           rank_lose_counts[idx] = rank_lose_counts[idx].saturating_add(1);
           hero_rank_wins[idx] = hero_rank_wins[idx].saturating_add(1);
         }
-      } else if opp_win {
-        if idx < 9 {
-          rank_wins[idx] = rank_wins[idx].saturating_add(1);
-          hero_rank_lose_counts[idx] = hero_rank_lose_counts[idx].saturating_add(1);
-        }
+      } else if opp_win && idx < 9 {
+        rank_wins[idx] = rank_wins[idx].saturating_add(1);
+        hero_rank_lose_counts[idx] = hero_rank_lose_counts[idx].saturating_add(1);
       }
       game.reset();
       completed = completed.saturating_add(1);
@@ -702,6 +763,29 @@ fn simulate_vs_list_with_ranks_monte_carlo_inner<F: FnMut(u32)>(
   Ok(stats)
 }
 
+/// Aggregate of [`simulate_open_ranges_monte_carlo_tally`].
+///
+/// Plain counters, so runs with different seeds (or shards, or earlier sessions) combine exactly
+/// with [`OpenRangesTally::merge`]. Merging runs that share a seed only counts the same deals twice.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpenRangesTally {
+  pub wins: u32,
+  pub ties: u32,
+  pub plays: u32,
+  /// Hero wins by hand category (0 = High Card .. 8 = Straight Flush).
+  pub hero_rank_wins: [u32; 9],
+}
+
+impl OpenRangesTally {
+  pub fn merge(&mut self, other: &OpenRangesTally) {
+    self.wins = self.wins.saturating_add(other.wins);
+    self.ties = self.ties.saturating_add(other.ties);
+    self.plays = self.plays.saturating_add(other.plays);
+    add_counts(&mut self.hero_rank_wins, &other.hero_rank_wins);
+  }
+}
+
 /// Hero range vs 1-8 opponent ranges (semicolon-separated), Monte Carlo with hero win ranks.
 /// Returns (wins, ties, plays, hero_rank_wins).
 pub fn simulate_open_ranges_monte_carlo(
@@ -710,6 +794,98 @@ pub fn simulate_open_ranges_monte_carlo(
   trials: u32,
   seed: u64,
 ) -> Result<(u32, u32, u32, [u32; 9]), String> {
+  let tally =
+    simulate_open_ranges_monte_carlo_tally(hero_range_str, opponent_ranges_str, trials, seed)?;
+  Ok((tally.wins, tally.ties, tally.plays, tally.hero_rank_wins))
+}
+
+/// Same as [`simulate_open_ranges_monte_carlo`], returning the mergeable aggregate.
+pub fn simulate_open_ranges_monte_carlo_tally(
+  hero_range_str: &str,
+  opponent_ranges_str: &str,
+  trials: u32,
+  seed: u64,
+) -> Result<OpenRangesTally, String> {
+  let (hero_set, opponent_sets) = parse_open_ranges_inputs(hero_range_str, opponent_ranges_str)?;
+
+  let trials = trials.max(1);
+  let mut rng = DefaultRng::new(seed);
+  let mut tally = OpenRangesTally::default();
+
+  let opponent_count = opponent_sets.len();
+  for _ in 0..trials {
+    let mut deck = build_deck(&[]);
+    // hero, each opponent, then the board
+    deal_prefix(&mut deck, 7 + 2 * opponent_count, &mut rng);
+
+    let Some((hero_hand, participants)) = seat_open_ranges(&deck, &hero_set, &opponent_sets)
+    else {
+      continue;
+    };
+
+    let offset = 2 + 2 * opponent_count;
+    let board = [
+      deck[offset],
+      deck[offset + 1],
+      deck[offset + 2],
+      deck[offset + 3],
+      deck[offset + 4],
+    ];
+    record_open_ranges_showdown(&hero_hand, &participants, &board, &mut tally);
+  }
+
+  Ok(tally)
+}
+
+/// [`simulate_open_ranges_monte_carlo_tally`] with stratified and/or antithetic runouts.
+/// An antithetic runout reuses the hands of the deal before it, so it always counts
+/// as a play. The hands change every deal, so the control variate is not available.
+pub fn simulate_open_ranges_monte_carlo_with_variance_reduction(
+  hero_range_str: &str,
+  opponent_ranges_str: &str,
+  trials: u32,
+  seed: u64,
+  options: VarianceReduction,
+) -> Result<OpenRangesTally, String> {
+  if options.control_variate {
+    return Err("control variate needs fixed hands".into());
+  }
+  let (hero_set, opponent_sets) = parse_open_ranges_inputs(hero_range_str, opponent_ranges_str)?;
+
+  let trials = trials.max(1);
+  let mut rng = DefaultRng::new(seed);
+  let mut tally = OpenRangesTally::default();
+
+  let hole_cards = 2 + 2 * opponent_sets.len();
+  let mut sampler = RunoutSampler::new(52 - hole_cards, options);
+  let mut seated: Option<([SimCard; 2], Vec<[SimCard; 2]>)> = None;
+  let mut live = Vec::new();
+  let mut board = [SimCard { rank: 0, suit: 0 }; 5];
+  for _ in 0..trials {
+    if !sampler.mirror_next() {
+      let mut deck = build_deck(&[]);
+      deal_prefix(&mut deck, hole_cards, &mut rng);
+      seated = seat_open_ranges(&deck, &hero_set, &opponent_sets);
+      if seated.is_none() {
+        continue;
+      }
+      live = build_deck(&deck[..hole_cards]);
+    }
+    let Some((hero_hand, participants)) = &seated else {
+      continue;
+    };
+    sampler.deal(&live, &mut board, &mut rng);
+    record_open_ranges_showdown(hero_hand, participants, &board, &mut tally);
+  }
+
+  Ok(tally)
+}
+
+#[allow(clippy::type_complexity)]
+fn parse_open_ranges_inputs(
+  hero_range_str: &str,
+  opponent_ranges_str: &str,
+) -> Result<(HashSet<(u32, u32)>, Vec<HashSet<(u32, u32)>>), String> {
   let hero_range = parse_range_hands(hero_range_str)?;
   if hero_range.is_empty() {
     return Err("hero range must include at least 1 hand".into());
@@ -724,100 +900,100 @@ pub fn simulate_open_ranges_monte_carlo(
   }
 
   let hero_set = build_range_set(&hero_range);
-  let opponent_sets: Vec<HashSet<(u32, u32)>> =
-    opponent_ranges.iter().map(|range| build_range_set(range)).collect();
-
-  let trials = trials.max(1);
-  let mut rng = Lcg64::new(seed);
-  let mut wins = 0u32;
-  let mut ties = 0u32;
-  let mut plays = 0u32;
-  let mut hero_rank_wins = [0u32; 9];
+  let opponent_sets = opponent_ranges.iter().map(|range| build_range_set(range)).collect();
+  Ok((hero_set, opponent_sets))
+}
 
-  let opponent_count = opponent_sets.len();
-  for _ in 0..trials {
-    let mut deck = build_deck(&[]);
-    shuffle_slice(&mut deck, &mut rng);
+/// Hero and the opponents whose ranges contain their hole cards, read from the front of
+/// `deck` (hero first). `None` when the hero's cards are outside the range or no opponent
+/// is left.
+fn seat_open_ranges(
+  deck: &[SimCard],
+  hero_set: &HashSet<(u32, u32)>,
+  opponent_sets: &[HashSet<(u32, u32)>],
+) -> Option<([SimCard; 2], Vec<[SimCard; 2]>)> {
+  let hero_hand = [deck[0], deck[1]];
+  if !hero_set.contains(&decode_hand_pair(&hero_hand)) {
+    return None;
+  }
 
-    let hero_hand = [deck[0], deck[1]];
-    let hero_key = decode_hand_pair(&hero_hand);
-    if !hero_set.contains(&hero_key) {
-      continue;
-    }
+  let participants: Vec<[SimCard; 2]> =
+    seat_opponents(deck, opponent_sets).into_iter().flatten().collect();
+  if participants.is_empty() {
+    return None;
+  }
+  Some((hero_hand, participants))
+}
 
-    let mut participants: Vec<[SimCard; 2]> = Vec::with_capacity(opponent_count);
-    let mut offset = 2usize;
-    for opp_set in &opponent_sets {
-      let opp_hand = [deck[offset], deck[offset + 1]];
-      offset += 2;
-      let opp_key = decode_hand_pair(&opp_hand);
-      if opp_set.contains(&opp_key) {
-        participants.push(opp_hand);
-      }
-    }
+/// Each opponent's hole cards (from `deck` after the hero's two) while its range contains
+/// them, `None` for a fold.
+pub(crate) fn seat_opponents(
+  deck: &[SimCard],
+  opponent_sets: &[HashSet<(u32, u32)>],
+) -> Vec<Option<[SimCard; 2]>> {
+  opponent_sets
+    .iter()
+    .enumerate()
+    .map(|(i, opp_set)| {
+      let opp_hand = [deck[2 + 2 * i], deck[3 + 2 * i]];
+      opp_set.contains(&decode_hand_pair(&opp_hand)).then_some(opp_hand)
+    })
+    .collect()
+}
 
-    if participants.is_empty() {
-      continue;
-    }
+fn record_open_ranges_showdown(
+  hero_hand: &[SimCard; 2],
+  participants: &[[SimCard; 2]],
+  board: &[SimCard; 5],
+  tally: &mut OpenRangesTally,
+) {
+  let hero_cards = [
+    hero_hand[0],
+    hero_hand[1],
+    board[0],
+    board[1],
+    board[2],
+    board[3],
+    board[4],
+  ];
+  let hero_score = best_of(&hero_cards);
 
-    let board = [
-      deck[offset],
-      deck[offset + 1],
-      deck[offset + 2],
-      deck[offset + 3],
-      deck[offset + 4],
-    ];
+  let mut max_score = hero_score.encoded;
+  let mut max_count = 1u32;
+  let mut hero_in_max = true;
 
-    let hero_cards = [
-      hero_hand[0],
-      hero_hand[1],
+  for opp in participants {
+    let opp_cards = [
+      opp[0],
+      opp[1],
       board[0],
       board[1],
       board[2],
       board[3],
       board[4],
     ];
-    let hero_score = best_of(&hero_cards);
-
-    let mut max_score = hero_score.encoded;
-    let mut max_count = 1u32;
-    let mut hero_in_max = true;
-
-    for opp in &participants {
-      let opp_cards = [
-        opp[0],
-        opp[1],
-        board[0],
-        board[1],
-        board[2],
-        board[3],
-        board[4],
-      ];
-      let opp_score = best_of(&opp_cards);
-      if opp_score.encoded > max_score {
-        max_score = opp_score.encoded;
-        max_count = 1;
-        hero_in_max = false;
-      } else if opp_score.encoded == max_score {
-        max_count = max_count.saturating_add(1);
-      }
+    let opp_score = best_of(&opp_cards);
+    if opp_score.encoded > max_score {
+      max_score = opp_score.encoded;
+      max_count = 1;
+      hero_in_max = false;
+    } else if opp_score.encoded == max_score {
+      max_count = max_count.saturating_add(1);
     }
+  }
 
-    plays = plays.saturating_add(1);
-    if hero_in_max {
-      if max_count == 1 {
-        wins = wins.saturating_add(1);
-        let idx = hero_score.rank as usize;
-        if idx < 9 {
-          hero_rank_wins[idx] = hero_rank_wins[idx].saturating_add(1);
-        }
-      } else {
-        ties = ties.saturating_add(1);
+  tally.plays = tally.plays.saturating_add(1);
+  if hero_in_max {
+    if max_count == 1 {
+      tally.wins = tally.wins.saturating_add(1);
+      let idx = hero_score.rank as usize;
+      if idx < 9 {
+        tally.hero_rank_wins[idx] = tally.hero_rank_wins[idx].saturating_add(1);
       }
+    } else {
+      tally.ties = tally.ties.saturating_add(1);
     }
   }
-
-  Ok((wins, ties, plays, hero_rank_wins))
 }
 
 /// Rank distribution for multiple hands given partial board using rs_poker evaluation.
@@ -847,6 +1023,131 @@ fn simulate_rank_distribution_inner<F: FnMut(u32)>(
   seed: u64,
   mut progress: Option<F>,
 ) -> Result<Vec<[u32; 9]>, String> {
+  let (hands, board) = parse_rank_distribution_inputs(hands_str, board_str)?;
+
+  let mut rng = DefaultRng::new(seed);
+  let mut counts = vec![[0u32; 9]; hands.len()];
+
+  let trials = trials.max(1);
+  let total_work = (trials as u64).saturating_mul(hands.len() as u64).max(1);
+  let mut completed = 0u64;
+  let mut last_progress: Option<u32> = None;
+  let mut update_progress = |done: u64| {
+    if let Some(cb) = progress.as_mut() {
+      let pct = ((done.saturating_mul(100)) / total_work) as u32;
+      let clamped = pct.min(100);
+      if last_progress != Some(clamped) {
+        last_progress = Some(clamped);
+        cb(clamped);
+      }
+    }
+  };
+  update_progress(0);
+
+  for _ in 0..trials {
+    for (idx, hand) in hands.iter().enumerate() {
+      let r_idx = deal_rank_index(hand, &board, &mut rng);
+      if r_idx < 9 {
+        counts[idx][r_idx] += 1;
+      }
+
+      completed = completed.saturating_add(1);
+      update_progress(completed);
+    }
+  }
+
+  update_progress(total_work);
+  Ok(counts)
+}
+
+/// Multi-threaded rank distribution (native only). Work units are
+/// `TRIALS_PER_UNIT` chunks of trials with their own sub-seed, so the counts
+/// are identical for any `threads` (0 = all cores).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn simulate_rank_distribution_parallel(
+  hands_str: &str,
+  board_str: &str,
+  trials: u32,
+  seed: u64,
+  threads: usize,
+) -> Result<Vec<[u32; 9]>, String> {
+  use crate::sim::parallel::fold_units;
+
+  let (hands, board) = parse_rank_distribution_inputs(hands_str, board_str)?;
+  let trials = trials.max(1);
+
+  Ok(fold_units(
+    chunk_count(trials),
+    threads,
+    || vec![[0u32; 9]; hands.len()],
+    |counts, unit| run_rank_distribution_unit(&hands, &board, trials, seed, unit, counts),
+    |total, part| add_rank_counts(total, &part),
+  ))
+}
+
+/// One shard of the parallel rank distribution (units `shard_index, shard_index +
+/// shard_count, ...`), for splitting a simulation across WASM workers.
+/// `merge_rank_distribution_shards` over all shards equals the parallel variant.
+pub fn simulate_rank_distribution_shard(
+  hands_str: &str,
+  board_str: &str,
+  trials: u32,
+  seed: u64,
+  shard_index: u32,
+  shard_count: u32,
+) -> Result<Vec<[u32; 9]>, String> {
+  let (hands, board) = parse_rank_distribution_inputs(hands_str, board_str)?;
+  let trials = trials.max(1);
+
+  let mut counts = vec![[0u32; 9]; hands.len()];
+  for unit in shard_units(chunk_count(trials), shard_index, shard_count)? {
+    run_rank_distribution_unit(&hands, &board, trials, seed, unit, &mut counts);
+  }
+  Ok(counts)
+}
+
+/// Sum shard outputs hand by hand.
+pub fn merge_rank_distribution_shards(shards: &[Vec<[u32; 9]>]) -> Result<Vec<[u32; 9]>, String> {
+  let (first, rest) = shards.split_first().ok_or("no shards to merge")?;
+  let mut merged = first.clone();
+  for shard in rest {
+    if shard.len() != merged.len() {
+      return Err("shards have different hand counts".into());
+    }
+    add_rank_counts(&mut merged, shard);
+  }
+  Ok(merged)
+}
+
+fn run_rank_distribution_unit(
+  hands: &[[SimCard; 2]],
+  board: &[SimCard],
+  trials: u32,
+  seed: u64,
+  unit: usize,
+  counts: &mut [[u32; 9]],
+) {
+  let mut rng = DefaultRng::new(derive_seed(seed, unit as u64));
+  for _ in 0..chunk_trials(trials, unit) {
+    for (idx, hand) in hands.iter().enumerate() {
+      let r_idx = deal_rank_index(hand, board, &mut rng);
+      if r_idx < 9 {
+        counts[idx][r_idx] += 1;
+      }
+    }
+  }
+}
+
+fn add_rank_counts(total: &mut [[u32; 9]], part: &[[u32; 9]]) {
+  for (acc, row) in total.iter_mut().zip(part) {
+    add_counts(acc, row);
+  }
+}
+
+fn parse_rank_distribution_inputs(
+  hands_str: &str,
+  board_str: &str,
+) -> Result<(Vec<[SimCard; 2]>, Vec<SimCard>), String> {
   let hands = parse_hands_min1(hands_str).ok_or("failed to parse hands")?;
   let board = parse_board(board_str).ok_or("failed to parse board")?;
   if board.len() < 3 {
@@ -880,61 +1181,26 @@ fn simulate_rank_distribution_inner<F: FnMut(u32)>(
     }
   }
 
-  let missing_board = 5usize.saturating_sub(board.len());
-
-  let mut rng = Lcg64::new(seed);
-  let mut counts = vec![[0u32; 9]; hands.len()];
-
-  let trials = trials.max(1);
-  let total_work = (trials as u64).saturating_mul(hands.len() as u64).max(1);
-  let mut completed = 0u64;
-  let mut last_progress: Option<u32> = None;
-  let mut update_progress = |done: u64| {
-    if let Some(cb) = progress.as_mut() {
-      let pct = ((done.saturating_mul(100)) / total_work) as u32;
-      let clamped = pct.min(100);
-      if last_progress != Some(clamped) {
-        last_progress = Some(clamped);
-        cb(clamped);
-      }
-    }
-  };
-  update_progress(0);
-
-  for _ in 0..trials {
-    for (idx, hand) in hands.iter().enumerate() {
-      let mut exclude = board.clone();
-      exclude.push(hand[0]);
-      exclude.push(hand[1]);
-      let remaining_cards = 52usize.saturating_sub(exclude.len());
-      if remaining_cards < missing_board {
-        return Err("not enough cards to complete board".into());
-      }
-      let mut deck = build_deck(&exclude);
-      shuffle_slice(&mut deck, &mut rng);
+  Ok((hands, board))
+}
 
-      let mut full_board = board.clone();
-      for i in 0..missing_board {
-        full_board.push(deck[i]);
-      }
+/// Complete the board for one hand and return its rank category index.
+/// A 3+ card board plus 2 hole cards always leaves enough cards in the deck.
+fn deal_rank_index<R: SimRng>(hand: &[SimCard; 2], board: &[SimCard], rng: &mut R) -> usize {
+  let missing_board = 5usize.saturating_sub(board.len());
+  let mut exclude = board.to_vec();
+  exclude.push(hand[0]);
+  exclude.push(hand[1]);
+  let mut deck = build_deck(&exclude);
 
-      let mut rs_hand = to_rs_hand(hand);
-      for c in &full_board {
-        rs_hand.insert(to_rs_card(c));
-      }
-      let rank = rs_hand.rank();
-      let r_idx = rank_index(&rank);
-      if r_idx < 9 {
-        counts[idx][r_idx] += 1;
-      }
+  let mut full_board = board.to_vec();
+  full_board.extend_from_slice(deal_prefix(&mut deck, missing_board, rng));
 
-      completed = completed.saturating_add(1);
-      update_progress(completed);
-    }
+  let mut rs_hand = to_rs_hand(hand);
+  for c in &full_board {
+    rs_hand.insert(to_rs_card(c));
   }
-
-  update_progress(total_work);
-  Ok(counts)
+  rank_index(&rs_hand.rank())
 }
 
 /// Parse a range string into encoded hand pairs.
@@ -954,6 +1220,61 @@ pub fn parse_range_to_hands(range_str: &str) -> Result<Vec<(u32, u32)>, String>
   Ok(encoded)
 }
 
+#[allow(clippy::type_complexity)]
+fn parse_vs_list_equity_inputs(
+  hero_hand_str: &str,
+  board_str: &str,
+  compare_list: &str,
+) -> Result<([SimCard; 2], Vec<SimCard>, Vec<[SimCard; 2]>), String> {
+  let hero_hand = parse_hand_two(hero_hand_str).ok_or("hero hand must have 2 cards")?;
+
+  let board = parse_board(board_str).ok_or("failed to parse board")?;
+  if board.len() > 5 {
+    return Err("board must be <=5 cards".into());
+  }
+
+  let mut opponents: Vec<[SimCard; 2]> = Vec::new();
+  for raw in compare_list.split(';') {
+    if raw.trim().is_empty() {
+      continue;
+    }
+    let hand = parse_hand_two(raw).ok_or("each compare hand must have 2 cards")?;
+    opponents.push(hand);
+  }
+  if opponents.is_empty() {
+    return Err("no compare hands provided".into());
+  }
+
+  validate_inputs(&hero_hand, &board, &opponents)?;
+  Ok((hero_hand, board, opponents))
+}
+
+/// Hero and villain ranges without the combos that overlap the board, and the board.
+#[allow(clippy::type_complexity)]
+fn parse_range_vs_range_inputs(
+  hero_range_str: &str,
+  villain_range_str: &str,
+  board_str: &str,
+) -> Result<(Vec<[SimCard; 2]>, Vec<[SimCard; 2]>, Vec<SimCard>), String> {
+  let board = parse_board(board_str).ok_or("failed to parse board")?;
+  if board.len() > 5 {
+    return Err("board must be <=5 cards".into());
+  }
+
+  let hero_hands = parse_range_hands(hero_range_str)?;
+  let villain_hands = parse_range_hands(villain_range_str)?;
+  if hero_hands.is_empty() || villain_hands.is_empty() {
+    return Err("range must include at least 1 hand".into());
+  }
+
+  let hero_hands = filter_board_overlaps(hero_hands, &board);
+  let villain_hands = filter_board_overlaps(villain_hands, &board);
+  if hero_hands.is_empty() || villain_hands.is_empty() {
+    return Err("range overlaps board".into());
+  }
+  Ok((hero_hands, villain_hands, board))
+}
+
 fn validate_inputs(
   hero_hand: &[SimCard; 2],
   board: &[SimCard],
@@ -993,7 +1314,7 @@ fn validate_inputs(
   Ok(())
 }
 
-fn parse_range_hands(range_str: &str) -> Result<Vec<[SimCard; 2]>, String> {
+pub(crate) fn parse_range_hands(range_str: &str) -> Result<Vec<[SimCard; 2]>, String> {
   let hands = RangeParser::parse_many(range_str).map_err(|e| format!("{e:?}"))?;
   let mut out = Vec::with_capacity(hands.len());
   for hand in hands {
@@ -1023,7 +1344,7 @@ fn parse_range_list(range_list_str: &str) -> Result<Vec<Vec<[SimCard; 2]>>, Stri
   Ok(ranges)
 }
 
-fn build_range_set(range: &[[SimCard; 2]]) -> HashSet<(u32, u32)> {
+pub(crate) fn build_range_set(range: &[[SimCard; 2]]) -> HashSet<(u32, u32)> {
   let mut set = HashSet::with_capacity(range.len());
   for hand in range {
     set.insert(decode_hand_pair(hand));
@@ -0,0 +1,189 @@
+//! Map/reduce exports for splitting one simulation across several workers.
+//!
+//! Each worker calls a `*_shard` export with the same inputs and its own
+//! `shard_index` (0..shard_count) and gets partial counts in the usual record
+//! layout. One worker then passes all partial buffers, concatenated in shard
+//! order, to the matching `merge_*` export. The merged result does not depend
+//! on `shard_count` and equals the native `*_parallel` functions.
+
+use crate::rs_poker_native::{
+    merge_rank_distribution_shards as merge_rank_distribution_shards_internal,
+    simulate_rank_distribution_shard as simulate_rank_distribution_shard_internal,
+};
+use crate::sim::{
+    merge_vs_list_with_ranks_shards as merge_vs_list_with_ranks_shards_internal,
+    simulate_vs_list_with_ranks_shard as simulate_vs_list_with_ranks_shard_internal,
+};
+use crate::{run_rank_distribution, run_simulation, write_rank_counts, write_ranked_records};
+
+/// One shard of `simulate_vs_list_with_ranks`. Same output layout (32 u32 per record,
+/// hero aggregate last) with partial counts.
+#[no_mangle]
+pub extern "C" fn simulate_vs_list_with_ranks_shard(
+    hero_ptr: *const u8,
+    hero_len: usize,
+    board_ptr: *const u8,
+    board_len: usize,
+    compare_ptr: *const u8,
+    compare_len: usize,
+    trials: u32,
+    seed: u64,
+    shard_index: u32,
+    shard_count: u32,
+    out_ptr: *mut u32,
+    out_len: usize,
+) -> i32 {
+    run_simulation(
+        hero_ptr,
+        hero_len,
+        board_ptr,
+        board_len,
+        compare_ptr,
+        compare_len,
+        trials,
+        seed,
+        out_ptr,
+        out_len,
+        |hero_str, board_str, compare_str| {
+            simulate_vs_list_with_ranks_shard_internal(
+                hero_str,
+                board_str,
+                compare_str,
+                trials,
+                seed,
+                shard_index,
+                shard_count,
+            )
+            .map_err(|_| -5)
+        },
+    )
+}
+
+/// Merge `shard_count` outputs of `simulate_vs_list_with_ranks_shard`.
+/// `in_ptr` holds the shard buffers back to back (`in_len` = shard_count * records * 32).
+/// Returns the record count or a negative error.
+#[no_mangle]
+pub extern "C" fn merge_vs_list_with_ranks_shards(
+    in_ptr: *const u32,
+    in_len: usize,
+    shard_count: u32,
+    out_ptr: *mut u32,
+    out_len: usize,
+) -> i32 {
+    let Some(blocks) = read_shard_blocks(in_ptr, in_len, shard_count, 32) else {
+        return -1;
+    };
+    let shards: Vec<Vec<_>> = blocks
+        .iter()
+        .map(|block| {
+            block
+                .as_chunks::<32>()
+                .0
+                .iter()
+                .map(|r| {
+                    let mut rank_wins = [0u32; 9];
+                    let mut rank_ties = [0u32; 9];
+                    let mut rank_lose_counts = [0u32; 9];
+                    rank_wins.copy_from_slice(&r[5..14]);
+                    rank_ties.copy_from_slice(&r[14..23]);
+                    rank_lose_counts.copy_from_slice(&r[23..32]);
+                    (
+                        r[0],
+                        r[1],
+                        r[2],
+                        r[3],
+                        r[4],
+                        rank_wins,
+                        rank_ties,
+                        rank_lose_counts,
+                    )
+                })
+                .collect()
+        })
+        .collect();
+
+    match merge_vs_list_with_ranks_shards_internal(&shards) {
+        Ok(merged) => write_ranked_records(&merged, out_ptr, out_len),
+        Err(_) => -5,
+    }
+}
+
+/// One shard of `simulate_rank_distribution`. Output per hand: [rank0..rank8] (partial counts).
+#[no_mangle]
+pub extern "C" fn simulate_rank_distribution_shard(
+    hands_ptr: *const u8,
+    hands_len: usize,
+    board_ptr: *const u8,
+    board_len: usize,
+    trials: u32,
+    seed: u64,
+    shard_index: u32,
+    shard_count: u32,
+    out_ptr: *mut u32,
+    out_len: usize,
+) -> i32 {
+    run_rank_distribution(
+        hands_ptr,
+        hands_len,
+        board_ptr,
+        board_len,
+        trials,
+        seed,
+        out_ptr,
+        out_len,
+        |hands_str, board_str| {
+            simulate_rank_distribution_shard_internal(
+                hands_str,
+                board_str,
+                trials,
+                seed,
+                shard_index,
+                shard_count,
+            )
+            .map_err(|_| -5)
+        },
+    )
+}
+
+/// Merge `shard_count` outputs of `simulate_rank_distribution_shard`
+/// (`in_len` = shard_count * hands * 9). Returns the hand count or a negative error.
+#[no_mangle]
+pub extern "C" fn merge_rank_distribution_shards(
+    in_ptr: *const u32,
+    in_len: usize,
+    shard_count: u32,
+    out_ptr: *mut u32,
+    out_len: usize,
+) -> i32 {
+    let Some(blocks) = read_shard_blocks(in_ptr, in_len, shard_count, 9) else {
+        return -1;
+    };
+    let shards: Vec<Vec<[u32; 9]>> = blocks
+        .iter()
+        .map(|block| block.as_chunks::<9>().0.to_vec())
+        .collect();
+
+    match merge_rank_distribution_shards_internal(&shards) {
+        Ok(merged) => write_rank_counts(&merged, out_ptr, out_len),
+        Err(_) => -5,
+    }
+}
+
+/// Split the concatenated input into one block per shard. `None` when the
+/// pointer is null or the length is not `shard_count * records * record_len`.
+fn read_shard_blocks<'a>(
+    in_ptr: *const u32,
+    in_len: usize,
+    shard_count: u32,
+    record_len: usize,
+) -> Option<Vec<&'a [u32]>> {
+    if in_ptr.is_null() || shard_count == 0 {
+        return None;
+    }
+    let block_len = in_len / shard_count as usize;
+    if block_len * shard_count as usize != in_len || !block_len.is_multiple_of(record_len) {
+        return None;
+    }
+    let input = unsafe { std::slice::from_raw_parts(in_ptr, in_len) };
+    Some(input.chunks(block_len.max(1)).collect())
+}
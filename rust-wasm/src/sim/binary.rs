@@ -0,0 +1,217 @@
+//! Compact binary result format shared with `pokepra_wasm` (`rust/src/binary.rs`).
+//!
+//! Results are a flat `u32` array: a 6-word header followed by fixed-size
+//! records. Header words: `[MAGIC, VERSION, kind, header_len, record_len,
+//! record_count]`. Readers must locate records via `header_len` / `record_len`
+//! so trailing words can be added later without a version bump.
+//!
+//! Cards use the same numbering as the other exports (`rank << 2 | suit`,
+//! suits s/h/d/c = 0..3). Equity is fixed point: `floor((2w + t) * 1e6 / 2n)`.
+//!
+//! Record layouts:
+//! - `KIND_RANGE_VS_RANGE`: `[card1, card2, side (0 hero / 1 villain), equity]`
+//! - `KIND_VS_LIST_WITH_RANKS`: `[card1, card2, flags, count, win, tie, lose,
+//!   equity, rankWin x9, rankTie x9, rankLose x9]`, with the same meaning as
+//!   the web app's `CombinedEntry` (opponent rows from the opponent's side).
+//!   The hero aggregate has `FLAG_HERO_AGGREGATE` set and is always last.
+
+use super::RankedRecord;
+
+pub const MAGIC: u32 = u32::from_le_bytes(*b"PPRB");
+pub const VERSION: u32 = 1;
+pub const HEADER_LEN: usize = 6;
+
+pub const KIND_RANGE_VS_RANGE: u32 = 1;
+pub const KIND_VS_LIST_WITH_RANKS: u32 = 2;
+
+pub const RANGE_VS_RANGE_RECORD_LEN: usize = 4;
+pub const VS_LIST_WITH_RANKS_RECORD_LEN: usize = 35;
+
+pub const EQUITY_SCALE: u64 = 1_000_000;
+pub const FLAG_HERO_AGGREGATE: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+  pub kind: u32,
+  pub header_len: usize,
+  pub record_len: usize,
+  pub record_count: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeEquityRecord {
+  pub cards: [u32; 2],
+  pub villain: bool,
+  pub equity: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CombinedRecord {
+  pub cards: [u32; 2],
+  pub hero_aggregate: bool,
+  pub count: u32,
+  pub win: u32,
+  pub tie: u32,
+  pub lose: u32,
+  pub equity: u32,
+  pub rank_win: [u32; 9],
+  pub rank_tie: [u32; 9],
+  pub rank_lose: [u32; 9],
+}
+
+/// Encode `simulate_range_vs_range_equity` rows (`[c1, c2, equity, role]`).
+pub fn encode_range_vs_range(rows: &[(u32, u32, u32, u32)]) -> Vec<u32> {
+  let mut out = header(KIND_RANGE_VS_RANGE, RANGE_VS_RANGE_RECORD_LEN, rows.len());
+  for &(c1, c2, equity, role) in rows {
+    out.extend_from_slice(&[c1, c2, role, equity]);
+  }
+  out
+}
+
+/// Encode `simulate_vs_list_with_ranks` rows. Opponent rows carry hero wins,
+/// so win/lose are swapped here; the sentinel hero row gets `hero_cards`.
+pub fn encode_vs_list_with_ranks(
+  hero_cards: (u32, u32),
+  rows: &[RankedRecord],
+) -> Vec<u32> {
+  let mut out = header(
+    KIND_VS_LIST_WITH_RANKS,
+    VS_LIST_WITH_RANKS_RECORD_LEN,
+    rows.len(),
+  );
+  for (c1, c2, hero_wins, ties, plays, rank_win, rank_tie, rank_lose) in rows {
+    let others = plays.saturating_sub(hero_wins.saturating_add(*ties));
+    let is_hero = *c1 == u32::MAX && *c2 == u32::MAX;
+    let (cards, flags, win, lose) = if is_hero {
+      (
+        (hero_cards.0, hero_cards.1),
+        FLAG_HERO_AGGREGATE,
+        *hero_wins,
+        others,
+      )
+    } else {
+      ((*c1, *c2), 0, others, *hero_wins)
+    };
+    out.extend_from_slice(&[
+      cards.0,
+      cards.1,
+      flags,
+      *plays,
+      win,
+      *ties,
+      lose,
+      fixed_equity(win, *ties, *plays),
+    ]);
+    out.extend_from_slice(rank_win);
+    out.extend_from_slice(rank_tie);
+    out.extend_from_slice(rank_lose);
+  }
+  out
+}
+
+/// Validate and read the header. Fails if the array is shorter than the records it declares.
+pub fn decode_header(words: &[u32]) -> Result<Header, String> {
+  if words.len() < HEADER_LEN {
+    return Err("binary result is shorter than the header".into());
+  }
+  if words[0] != MAGIC {
+    return Err("binary result has wrong magic".into());
+  }
+  if words[1] != VERSION {
+    return Err(format!("unsupported binary result version {}", words[1]));
+  }
+  let header = Header {
+    kind: words[2],
+    header_len: words[3] as usize,
+    record_len: words[4] as usize,
+    record_count: words[5] as usize,
+  };
+  let needed = header
+    .record_len
+    .checked_mul(header.record_count)
+    .and_then(|n| n.checked_add(header.header_len));
+  if header.header_len < HEADER_LEN || needed.is_none_or(|n| n > words.len()) {
+    return Err("binary result is truncated".into());
+  }
+  Ok(header)
+}
+
+pub fn decode_range_vs_range(words: &[u32]) -> Result<Vec<RangeEquityRecord>, String> {
+  Ok(
+    records(words, KIND_RANGE_VS_RANGE, RANGE_VS_RANGE_RECORD_LEN)?
+      .map(|r| RangeEquityRecord {
+        cards: [r[0], r[1]],
+        villain: r[2] == 1,
+        equity: r[3],
+      })
+      .collect(),
+  )
+}
+
+pub fn decode_vs_list_with_ranks(words: &[u32]) -> Result<Vec<CombinedRecord>, String> {
+  Ok(records(
+    words,
+    KIND_VS_LIST_WITH_RANKS,
+    VS_LIST_WITH_RANKS_RECORD_LEN,
+  )?
+  .map(|r| {
+    let counts = |start: usize| -> [u32; 9] { r[start..start + 9].try_into().unwrap() };
+    CombinedRecord {
+      cards: [r[0], r[1]],
+      hero_aggregate: r[2] & FLAG_HERO_AGGREGATE != 0,
+      count: r[3],
+      win: r[4],
+      tie: r[5],
+      lose: r[6],
+      equity: r[7],
+      rank_win: counts(8),
+      rank_tie: counts(17),
+      rank_lose: counts(26),
+    }
+  })
+  .collect())
+}
+
+fn header(kind: u32, record_len: usize, record_count: usize) -> Vec<u32> {
+  let mut out = Vec::with_capacity(HEADER_LEN + record_len * record_count);
+  out.extend_from_slice(&[
+    MAGIC,
+    VERSION,
+    kind,
+    HEADER_LEN as u32,
+    record_len as u32,
+    record_count as u32,
+  ]);
+  out
+}
+
+/// Check the kind and yield the first `min_len` words of each record
+/// (anything after that is a later extension).
+fn records(
+  words: &[u32],
+  kind: u32,
+  min_len: usize,
+) -> Result<impl Iterator<Item = &[u32]>, String> {
+  let h = decode_header(words)?;
+  if h.kind != kind {
+    return Err(format!(
+      "expected binary result kind {}, got {}",
+      kind, h.kind
+    ));
+  }
+  if h.record_len < min_len {
+    return Err("binary result records are too short".into());
+  }
+  Ok((0..h.record_count).map(move |i| {
+    let start = h.header_len + i * h.record_len;
+    &words[start..start + min_len]
+  }))
+}
+
+fn fixed_equity(wins: u32, ties: u32, plays: u32) -> u32 {
+  if plays == 0 {
+    return 0;
+  }
+  let scaled = (u64::from(wins) * 2 + u64::from(ties)) * EQUITY_SCALE / (u64::from(plays) * 2);
+  scaled.min(EQUITY_SCALE) as u32
+}
@@ -1,5 +1,5 @@
 use super::card::Card;
-use super::rng::Lcg64;
+use super::rng::SimRng;
 
 pub(crate) fn build_deck(exclude: &[Card]) -> Vec<Card> {
   let mut deck = Vec::with_capacity(52 - exclude.len());
@@ -14,13 +14,27 @@ pub(crate) fn build_deck(exclude: &[Card]) -> Vec<Card> {
   deck
 }
 
-pub(crate) fn shuffle_slice(deck: &mut [Card], rng: &mut Lcg64) {
-  for i in (1..deck.len()).rev() {
-    let j = (rng.next_u32() as usize) % (i + 1);
-    deck.swap(i, j);
+/// Uniform in-place Fisher-Yates shuffle of the whole slice.
+pub(crate) fn shuffle_slice<T, R: SimRng>(items: &mut [T], rng: &mut R) {
+  for i in (1..items.len()).rev() {
+    let j = rng.below(i as u32 + 1) as usize;
+    items.swap(i, j);
   }
 }
 
+/// Partial Fisher-Yates: move a uniform random sample of `count` items, in random
+/// order, to the front and return it. Draws only `count` numbers instead of
+/// shuffling the whole slice; the rest of the slice is left in an unspecified order.
+pub(crate) fn deal_prefix<'a, T, R: SimRng>(items: &'a mut [T], count: usize, rng: &mut R) -> &'a [T] {
+  let count = count.min(items.len());
+  let len = items.len() as u32;
+  for i in 0..count {
+    let j = i + rng.below(len - i as u32) as usize;
+    items.swap(i, j);
+  }
+  &items[..count]
+}
+
 pub(crate) fn encode_card(c: Card) -> u32 {
   ((c.rank as u32) << 2) | (c.suit as u32)
 }
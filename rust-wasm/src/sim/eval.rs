@@ -52,7 +52,7 @@ fn evaluate_five(cards: &[Card]) -> HandScore {
     mask |= 1 << c.rank;
   }
 
-  let is_flush = suit_counts.iter().any(|&c| c == 5);
+  let is_flush = suit_counts.contains(&5);
   let straight_high_rank = straight_high(mask);
 
   let mut by_count: Vec<(u8, u8)> = (0u8..13)
@@ -70,8 +70,8 @@ fn evaluate_five(cards: &[Card]) -> HandScore {
   let mut kickers = [0u8; 5];
   let mut fill = 0usize;
 
-  let rank = if is_flush && straight_high_rank.is_some() {
-    kickers[0] = straight_high_rank.unwrap();
+  let rank = if let (true, Some(high)) = (is_flush, straight_high_rank) {
+    kickers[0] = high;
     HandRank::StraightFlush
   } else if by_count[0].0 == 4 {
     kickers[0] = by_count[0].1;
@@ -128,9 +128,23 @@ fn evaluate_five(cards: &[Card]) -> HandScore {
 }
 
 pub(crate) fn best_of(cards: &[Card]) -> HandScore {
+  best_subset(cards).0
+}
+
+/// Best hand of 5-7 cards together with the five cards that make it, ordered by
+/// significance (see [`order_best_five`]).
+pub(crate) fn best_five_of(cards: &[Card]) -> (HandScore, [Card; 5]) {
+  let (best, mut best_cards) = best_subset(cards);
+  order_best_five(&best, &mut best_cards);
+  (best, best_cards)
+}
+
+/// First best 5-card subset, in input order.
+fn best_subset(cards: &[Card]) -> (HandScore, [Card; 5]) {
   assert!(cards.len() >= 5 && cards.len() <= 7);
   let n = cards.len();
-  let mut best = evaluate_five(&cards[0..5]);
+  let mut best_cards = [cards[0], cards[1], cards[2], cards[3], cards[4]];
+  let mut best = evaluate_five(&best_cards);
   for i in 0..n - 4 {
     for j in i + 1..n - 3 {
       for k in j + 1..n - 2 {
@@ -140,11 +154,29 @@ pub(crate) fn best_of(cards: &[Card]) -> HandScore {
             let score = evaluate_five(&subset);
             if score.encoded > best.encoded {
               best = score;
+              best_cards = subset;
             }
           }
         }
       }
     }
   }
-  best
+  (best, best_cards)
+}
+
+/// Order a made hand for display: larger rank groups first, then higher ranks
+/// (so the category-forming cards lead and kickers follow). A five-high straight
+/// puts the ace last.
+pub(crate) fn order_best_five(score: &HandScore, five: &mut [Card; 5]) {
+  let mut counts = [0u8; 13];
+  for c in five.iter() {
+    counts[c.rank as usize] += 1;
+  }
+  five.sort_by(|a, b| {
+    (counts[b.rank as usize], b.rank, a.suit).cmp(&(counts[a.rank as usize], a.rank, b.suit))
+  });
+  let straight = matches!(score.rank, HandRank::Straight | HandRank::StraightFlush);
+  if straight && score.kickers[0] == 3 {
+    five.rotate_left(1);
+  }
 }
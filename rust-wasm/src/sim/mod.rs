@@ -1,20 +1,34 @@
+pub mod binary;
 mod card;
 mod deck;
 pub(crate) mod eval;
+pub mod parallel;
 mod parse;
 mod rng;
 mod simulate_vs_list;
+mod variance;
 
 pub(crate) use card::Card;
-pub(crate) use deck::{build_deck, decode_hand_pair, shuffle_slice};
+pub(crate) use deck::{build_deck, deal_prefix, decode_hand_pair, shuffle_slice};
 pub(crate) use parse::{parse_board, parse_hand_two, parse_hands_min1};
-pub(crate) use rng::Lcg64;
+pub(crate) use rng::{derive_seed, DefaultRng, SimRng};
+pub(crate) use variance::{estimate_heads_up, RunoutSampler};
+pub use variance::VarianceReduction;
 
 pub use simulate_vs_list::{
+  merge_vs_list_with_ranks_shards,
+  replay_vs_list_with_ranks_trial,
   simulate_vs_list_with_ranks,
+  simulate_vs_list_with_ranks_shard,
   simulate_vs_list_with_ranks_trace,
   simulate_vs_list_with_ranks_with_progress,
+  TraceRecord,
+  RankedRecord,
+  VsListTrace,
+  TRACE_RECORD_LEN,
 };
+#[cfg(not(target_arch = "wasm32"))]
+pub use simulate_vs_list::simulate_vs_list_with_ranks_parallel;
 
 #[cfg(test)]
 mod tests;
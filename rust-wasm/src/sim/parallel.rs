@@ -0,0 +1,112 @@
+//! Work-unit splitting for parallel simulations.
+//!
+//! Simulations are split into numbered work units; each unit seeds its own
+//! generator from `derive_seed(seed, unit)` and results are merged by adding
+//! counters, so the output does not depend on how units are distributed.
+//! Native builds spread units over threads (`fold_units`); the browser runs
+//! one shard of units per worker and merges the partial counts afterwards.
+//!
+//! `fold_units`, `default_threads` and `derive_seed` are deliberately copied in
+//! `rust/src/parallel.rs` and `rust/src/rng.rs`: the two crates ship as separate
+//! wasm bundles with no shared dependency, and their generators differ anyway,
+//! so editing one copy never changes the other crate's results.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::num::NonZeroUsize;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(not(target_arch = "wasm32"))]
+use std::thread;
+
+/// Trials per work unit. Part of the reproducibility contract: changing it
+/// changes which sub-seed drives which trial.
+pub(crate) const TRIALS_PER_UNIT: u32 = 1024;
+
+/// Number of logical cores, or 1 when it cannot be determined.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn default_threads() -> usize {
+  thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// Hand out `0..units` to `threads` workers (0 = all cores), fold each unit
+/// into a per-thread accumulator, then merge the accumulators.
+/// `work`/`merge` must be order-independent (counter additions) for the
+/// result to be independent of the thread count.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn fold_units<T, I, W, M>(units: usize, threads: usize, init: I, work: W, merge: M) -> T
+where
+  T: Send,
+  I: Fn() -> T + Sync,
+  W: Fn(&mut T, usize) + Sync,
+  M: Fn(&mut T, T),
+{
+  let threads = if threads == 0 { default_threads() } else { threads }.min(units.max(1));
+
+  let mut total = init();
+  if threads <= 1 {
+    for unit in 0..units {
+      work(&mut total, unit);
+    }
+    return total;
+  }
+
+  let next = AtomicUsize::new(0);
+  let partials: Vec<T> = thread::scope(|scope| {
+    let handles: Vec<_> = (0..threads)
+      .map(|_| {
+        scope.spawn(|| {
+          let mut acc = init();
+          loop {
+            let unit = next.fetch_add(1, Ordering::Relaxed);
+            if unit >= units {
+              break;
+            }
+            work(&mut acc, unit);
+          }
+          acc
+        })
+      })
+      .collect();
+    handles
+      .into_iter()
+      .map(|h| h.join().expect("simulation worker panicked"))
+      .collect()
+  });
+
+  for partial in partials {
+    merge(&mut total, partial);
+  }
+  total
+}
+
+/// Number of `TRIALS_PER_UNIT`-sized chunks needed for `trials`.
+pub(crate) fn chunk_count(trials: u32) -> usize {
+  trials.div_ceil(TRIALS_PER_UNIT) as usize
+}
+
+/// Trials in chunk `chunk`; only the last chunk may be short.
+pub(crate) fn chunk_trials(trials: u32, chunk: usize) -> u32 {
+  let start = chunk as u32 * TRIALS_PER_UNIT;
+  trials.saturating_sub(start).min(TRIALS_PER_UNIT)
+}
+
+/// Units owned by shard `shard_index` of `shard_count` (round-robin), so every
+/// shard gets a similar mix of opponents / chunks.
+pub(crate) fn shard_units(
+  units: usize,
+  shard_index: u32,
+  shard_count: u32,
+) -> Result<impl Iterator<Item = usize>, String> {
+  if shard_count == 0 || shard_index >= shard_count {
+    return Err("shard_index must be < shard_count".into());
+  }
+  Ok((shard_index as usize..units).step_by(shard_count as usize))
+}
+
+/// Adds per-category counters (saturating). Partial results are plain sums, so the
+/// merge order never matters.
+pub(crate) fn add_counts(acc: &mut [u32; 9], other: &[u32; 9]) {
+  for (a, b) in acc.iter_mut().zip(other) {
+    *a = a.saturating_add(*b);
+  }
+}
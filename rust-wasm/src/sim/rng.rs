@@ -1,14 +1,85 @@
+//! Random numbers for the simulation core.
+//!
+//! Simulations are generic over [`SimRng`] and construct [`DefaultRng`], so the
+//! generator can be swapped in one place. Bounded integers use Lemire's
+//! multiply-and-reject reduction, which is exactly uniform (no modulo bias) and
+//! usually costs a single multiplication.
+
+/// Generator used by every simulation entry point.
+pub(crate) type DefaultRng = Xoshiro256pp;
+
+/// Source of uniformly distributed 64-bit words.
+pub(crate) trait SimRng {
+  fn next_u64(&mut self) -> u64;
+
+  /// High half of [`SimRng::next_u64`] (the better bits for xoshiro-style generators).
+  fn next_u32(&mut self) -> u32 {
+    (self.next_u64() >> 32) as u32
+  }
+
+  /// Uniform integer in `0..bound` (Lemire, "Fast Random Integer Generation in an
+  /// Interval", 2019). `bound` must be non-zero.
+  fn below(&mut self, bound: u32) -> u32 {
+    debug_assert!(bound > 0, "below(0) has no valid result");
+    let mut m = u64::from(self.next_u32()) * u64::from(bound);
+    if (m as u32) < bound {
+      // 2^32 mod bound: low words under this would make some results more likely
+      let threshold = bound.wrapping_neg() % bound;
+      while (m as u32) < threshold {
+        m = u64::from(self.next_u32()) * u64::from(bound);
+      }
+    }
+    (m >> 32) as u32
+  }
+}
+
+/// xoshiro256++ (Blackman & Vigna). 256 bits of state, period 2^256 - 1,
+/// passes BigCrush; seeded by expanding the 64-bit seed with SplitMix64.
 #[derive(Clone, Debug)]
-pub(crate) struct Lcg64 {
-  state: u64,
+pub(crate) struct Xoshiro256pp {
+  s: [u64; 4],
 }
 
-impl Lcg64 {
+impl Xoshiro256pp {
   pub(crate) fn new(seed: u64) -> Self {
-    Self { state: seed | 1 } // avoid zero
+    let mut state = seed;
+    let mut next = || {
+      state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+      splitmix64(state)
+    };
+    Self { s: [next(), next(), next(), next()] }
   }
-  pub(crate) fn next_u32(&mut self) -> u32 {
-    self.state = self.state.wrapping_mul(6364136223846793005).wrapping_add(1);
-    (self.state >> 32) as u32
+
+  #[cfg(test)]
+  pub(crate) fn from_state(s: [u64; 4]) -> Self {
+    Self { s }
+  }
+}
+
+impl SimRng for Xoshiro256pp {
+  fn next_u64(&mut self) -> u64 {
+    let s = &mut self.s;
+    let result = s[0].wrapping_add(s[3]).rotate_left(23).wrapping_add(s[0]);
+    let t = s[1] << 17;
+    s[2] ^= s[0];
+    s[3] ^= s[1];
+    s[1] ^= s[2];
+    s[0] ^= s[3];
+    s[2] ^= t;
+    s[3] = s[3].rotate_left(45);
+    result
   }
 }
+
+/// Derive an independent sub-seed for work unit `stream` (one SplitMix64 step).
+/// Depends only on the unit index, so any thread or worker can run any unit.
+pub(crate) fn derive_seed(seed: u64, stream: u64) -> u64 {
+  splitmix64(seed.wrapping_add(stream.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15)))
+}
+
+/// The SplitMix64 output function.
+fn splitmix64(mut z: u64) -> u64 {
+  z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+  z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+  z ^ (z >> 31)
+}
@@ -1,19 +1,31 @@
 use std::cmp::Ordering;
 
 use super::card::Card;
-use super::deck::{build_deck, decode_hand_pair, encode_card, shuffle_slice};
-use super::eval::best_of;
+use super::deck::{build_deck, deal_prefix, decode_hand_pair, encode_card};
+use super::eval::{best_five_of, best_of, HandScore};
 use super::parse::{parse_board, parse_hand_two};
-use super::rng::Lcg64;
+#[cfg(not(target_arch = "wasm32"))]
+use super::parallel::fold_units;
+use super::parallel::{add_counts, chunk_count, chunk_trials, shard_units};
+use super::rng::{derive_seed, DefaultRng, SimRng};
+
+/// One row of the ranked vs-list output: `(card1, card2, wins, ties, plays, rank_wins,
+/// rank_ties, rank_lose_counts)`. Opponent rows count the opponent's categories; the last
+/// row is the hero aggregate with both cards set to `u32::MAX`.
+pub type RankedRecord = (u32, u32, u32, u32, u32, [u32; 9], [u32; 9], [u32; 9]);
 
 /// Hero vs provided opponent list, returning wins/ties and rank distribution per opponent.
+///
+/// Trial `t` against opponent `i` draws from `derive_seed(seed, i * trials + t)`, the
+/// generator [`VsListTrace`] uses for the record with that `trial_index`, so the counts
+/// are exactly the tally of the trace for the same inputs and seed.
 pub fn simulate_vs_list_with_ranks(
   hero_hand_str: &str,
   board_str: &str,
   compare_list: &str,
   trials: u32,
   seed: u64,
-) -> Result<Vec<(u32, u32, u32, u32, u32, [u32; 9], [u32; 9], [u32; 9])>, String> {
+) -> Result<Vec<RankedRecord>, String> {
   simulate_vs_list_with_ranks_inner::<fn(u32)>(
     hero_hand_str,
     board_str,
@@ -32,7 +44,7 @@ pub fn simulate_vs_list_with_ranks_with_progress<F: FnMut(u32)>(
   trials: u32,
   seed: u64,
   progress: Option<F>,
-) -> Result<Vec<(u32, u32, u32, u32, u32, [u32; 9], [u32; 9], [u32; 9])>, String> {
+) -> Result<Vec<RankedRecord>, String> {
   simulate_vs_list_with_ranks_inner(
     hero_hand_str,
     board_str,
@@ -45,6 +57,8 @@ pub fn simulate_vs_list_with_ranks_with_progress<F: FnMut(u32)>(
 
 /// Hero vs provided opponent list, returning per-trial outcomes with ranks.
 /// Output per record: [hero1, hero2, board1..board5, opp1, opp2, outcome, rankIndex]
+///
+/// Materializes `opponents × trials` records; use [`VsListTrace`] to stream them instead.
 pub fn simulate_vs_list_with_ranks_trace(
   hero_hand_str: &str,
   board_str: &str,
@@ -52,156 +66,345 @@ pub fn simulate_vs_list_with_ranks_trace(
   trials: u32,
   seed: u64,
 ) -> Result<Vec<[u32; 11]>, String> {
-  let hero_hand = parse_hand_two(hero_hand_str).ok_or("hero hand must have 2 cards")?;
+  let trace = VsListTrace::new(hero_hand_str, board_str, compare_list, trials, seed)?;
+  Ok(trace.map(|record| record.legacy_words()).collect())
+}
 
-  let board = parse_board(board_str).ok_or("failed to parse board")?;
-  if board.len() > 5 {
-    return Err("board must be <=5 cards".into());
+/// Rebuild trial `trial_index` of the trace for the same inputs and seed without
+/// running the trials before it.
+pub fn replay_vs_list_with_ranks_trial(
+  hero_hand_str: &str,
+  board_str: &str,
+  compare_list: &str,
+  trials: u32,
+  seed: u64,
+  trial_index: u64,
+) -> Result<TraceRecord, String> {
+  let mut trace = VsListTrace::new(hero_hand_str, board_str, compare_list, trials, seed)?;
+  trace.seek(trial_index);
+  trace.next().ok_or_else(|| "trial index out of range".into())
+}
+
+/// Words per record written by [`TraceRecord::to_words`].
+pub const TRACE_RECORD_LEN: usize = 18;
+
+/// One runout of the trace. Cards use the `rank << 2 | suit` numbering.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TraceRecord {
+  /// `opponent_index * trials + trial`, usable with [`replay_vs_list_with_ranks_trial`].
+  pub trial_index: u64,
+  pub hero: [u32; 2],
+  pub board: [u32; 5],
+  pub villain: [u32; 2],
+  /// 0 = hero wins, 1 = villain wins, 2 = tie.
+  pub outcome: u32,
+  /// Category of the winning hand (hero's on a tie), 0 = high card .. 8 = straight flush.
+  pub rank_index: u32,
+  /// The five cards of the winning hand (hero's on a tie), category cards first.
+  pub best_five: [u32; 5],
+  /// Each player's own category, e.g. to find hands hero lost holding a flush.
+  pub hero_rank_index: u32,
+  pub villain_rank_index: u32,
+}
+
+impl TraceRecord {
+  /// `[hero1, hero2, board1..board5, opp1, opp2, outcome, rankIndex, best1..best5,
+  /// heroRankIndex, villainRankIndex]`
+  pub fn to_words(&self) -> [u32; TRACE_RECORD_LEN] {
+    let mut words = [0u32; TRACE_RECORD_LEN];
+    words[..11].copy_from_slice(&self.legacy_words());
+    words[11..16].copy_from_slice(&self.best_five);
+    words[16] = self.hero_rank_index;
+    words[17] = self.villain_rank_index;
+    words
   }
 
-  let mut opponents: Vec<[Card; 2]> = Vec::new();
-  for raw in compare_list.split(';') {
-    if raw.trim().is_empty() {
-      continue;
-    }
-    let hand = parse_hand_two(raw).ok_or("each compare hand must have 2 cards")?;
-    opponents.push(hand);
+  fn legacy_words(&self) -> [u32; 11] {
+    let (h, b, v) = (self.hero, self.board, self.villain);
+    [h[0], h[1], b[0], b[1], b[2], b[3], b[4], v[0], v[1], self.outcome, self.rank_index]
   }
-  if opponents.is_empty() {
-    return Err("no compare hands provided".into());
+}
+
+/// Streaming trace: yields one [`TraceRecord`] per (opponent, trial), opponent-major,
+/// without materializing all `opponents × trials` records.
+///
+/// Each trial draws from its own generator seeded with `derive_seed(seed, trial_index)`,
+/// so [`VsListTrace::seek`] jumps straight to any trial, and the runouts are the ones
+/// [`simulate_vs_list_with_ranks`] counts.
+pub struct VsListTrace {
+  inputs: VsListInputs,
+  dealers: Vec<Dealer>,
+  trials: u32,
+  seed: u64,
+  next_index: u64,
+}
+
+impl VsListTrace {
+  pub fn new(
+    hero_hand_str: &str,
+    board_str: &str,
+    compare_list: &str,
+    trials: u32,
+    seed: u64,
+  ) -> Result<Self, String> {
+    let inputs = parse_vs_list_inputs(hero_hand_str, board_str, compare_list)?;
+    let dealers = inputs
+      .opponents
+      .iter()
+      .map(|opp| inputs.dealer(opp))
+      .collect::<Result<Vec<_>, String>>()?;
+    Ok(Self {
+      inputs,
+      dealers,
+      trials: trials.max(1),
+      seed,
+      next_index: 0,
+    })
   }
 
-  {
-    let mut seen = Vec::new();
-    for c in &board {
-      if seen.iter().any(|x: &Card| x.rank == c.rank && x.suit == c.suit) {
-        return Err("duplicate cards detected in board".into());
-      }
-      seen.push(*c);
-    }
+  /// Total number of records (`opponents × trials`).
+  pub fn total(&self) -> u64 {
+    self.inputs.opponents.len() as u64 * self.trials as u64
   }
 
-  {
-    if hero_hand[0].rank == hero_hand[1].rank && hero_hand[0].suit == hero_hand[1].suit {
-      return Err("duplicate cards inside hero hand".into());
+  /// Position the stream so the next record is `trial_index`.
+  pub fn seek(&mut self, trial_index: u64) {
+    self.next_index = trial_index;
+  }
+}
+
+impl Iterator for VsListTrace {
+  type Item = TraceRecord;
+
+  fn next(&mut self) -> Option<TraceRecord> {
+    if self.next_index >= self.total() {
+      return None;
     }
-    for c in &hero_hand {
-      if board
-        .iter()
-        .any(|x| x.rank == c.rank && x.suit == c.suit)
-      {
-        return Err("hero overlaps board".into());
+    let trial_index = self.next_index;
+    self.next_index += 1;
+
+    let idx = (trial_index / self.trials as u64) as usize;
+    let hero = self.inputs.hero;
+    let opp = self.inputs.opponents[idx];
+    let dealer = &mut self.dealers[idx];
+    let mut rng = DefaultRng::new(derive_seed(self.seed, trial_index));
+    let (hero_score, opp_score) = dealer.play(&hero, &opp, &mut rng);
+    let full_board = dealer.full_board;
+
+    let (outcome, winner) = match hero_score.encoded.cmp(&opp_score.encoded) {
+      Ordering::Greater => (0u32, hero),
+      Ordering::Equal => (2u32, hero),
+      Ordering::Less => (1u32, opp),
+    };
+    let mut seven = [winner[0]; 7];
+    seven[1] = winner[1];
+    seven[2..].copy_from_slice(&full_board);
+    let (score, best) = best_five_of(&seven);
+
+    Some(TraceRecord {
+      trial_index,
+      hero: [encode_card(hero[0]), encode_card(hero[1])],
+      board: full_board.map(encode_card),
+      villain: [encode_card(opp[0]), encode_card(opp[1])],
+      outcome,
+      rank_index: score.rank as u32,
+      best_five: best.map(encode_card),
+      hero_rank_index: hero_score.rank as u32,
+      villain_rank_index: opp_score.rank as u32,
+    })
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let left = self.total().saturating_sub(self.next_index) as usize;
+    (left, Some(left))
+  }
+}
+
+/// Multi-threaded variant (native only). Each (opponent, `TRIALS_PER_UNIT` chunk)
+/// is a work unit with its own sub-seed, so the result is identical for any
+/// `threads` (0 = all cores), though it differs from the serial variant.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn simulate_vs_list_with_ranks_parallel(
+  hero_hand_str: &str,
+  board_str: &str,
+  compare_list: &str,
+  trials: u32,
+  seed: u64,
+  threads: usize,
+) -> Result<Vec<RankedRecord>, String> {
+  let units = UnitPlan::new(hero_hand_str, board_str, compare_list, trials, seed)?;
+  let tallies = fold_units(
+    units.count(),
+    threads,
+    || vec![OpponentTally::default(); units.inputs.opponents.len()],
+    |tallies, unit| units.run(unit, tallies),
+    |total, part| {
+      for (t, p) in total.iter_mut().zip(&part) {
+        t.add(p);
       }
+    },
+  );
+
+  Ok(build_records(&units.inputs.opponents, &tallies))
+}
+
+/// One shard of the parallel variant, for splitting a simulation across WASM workers.
+/// Runs the work units `shard_index, shard_index + shard_count, ...` and returns the
+/// same record layout with partial counts; `merge_vs_list_with_ranks_shards` over all
+/// `shard_count` shards equals `simulate_vs_list_with_ranks_parallel`.
+pub fn simulate_vs_list_with_ranks_shard(
+  hero_hand_str: &str,
+  board_str: &str,
+  compare_list: &str,
+  trials: u32,
+  seed: u64,
+  shard_index: u32,
+  shard_count: u32,
+) -> Result<Vec<RankedRecord>, String> {
+  let units = UnitPlan::new(hero_hand_str, board_str, compare_list, trials, seed)?;
+  let mut tallies = vec![OpponentTally::default(); units.inputs.opponents.len()];
+  for unit in shard_units(units.count(), shard_index, shard_count)? {
+    units.run(unit, &mut tallies);
+  }
+
+  Ok(build_records(&units.inputs.opponents, &tallies))
+}
+
+/// Sum shard outputs record by record. Every shard must list the same opponents
+/// in the same order (same inputs, different `shard_index`).
+pub fn merge_vs_list_with_ranks_shards(
+  shards: &[Vec<RankedRecord>],
+) -> Result<Vec<RankedRecord>, String> {
+  let (first, rest) = shards.split_first().ok_or("no shards to merge")?;
+  let mut merged = first.clone();
+  for shard in rest {
+    if shard.len() != merged.len() {
+      return Err("shards have different record counts".into());
     }
-    for opp in &opponents {
-      if opp[0].rank == opp[1].rank && opp[0].suit == opp[1].suit {
-        return Err("duplicate cards inside opponent hand".into());
-      }
-      for c in opp {
-        if hero_hand.iter().any(|x| x.rank == c.rank && x.suit == c.suit) {
-          return Err("opponent overlaps hero".into());
-        }
-        if board.iter().any(|x| x.rank == c.rank && x.suit == c.suit) {
-          return Err("opponent overlaps board".into());
-        }
+    for (acc, rec) in merged.iter_mut().zip(shard) {
+      if (acc.0, acc.1) != (rec.0, rec.1) {
+        return Err("shards list different hands".into());
       }
+      acc.2 = acc.2.saturating_add(rec.2);
+      acc.3 = acc.3.saturating_add(rec.3);
+      acc.4 = acc.4.saturating_add(rec.4);
+      add_counts(&mut acc.5, &rec.5);
+      add_counts(&mut acc.6, &rec.6);
+      add_counts(&mut acc.7, &rec.7);
     }
   }
+  Ok(merged)
+}
 
-  let trials = trials.max(1);
-  let board_len = board.len();
-  let missing_board = 5usize.saturating_sub(board_len);
-  let mut board_buf = [Card { rank: 0, suit: 0 }; 5];
-  board_buf[..board_len].copy_from_slice(&board);
-  let mut rng = Lcg64::new(seed);
-
-  let total_records = opponents.len().saturating_mul(trials as usize);
-  let mut records: Vec<[u32; 11]> = Vec::with_capacity(total_records);
+/// Work units of the parallel / sharded variants: one per (opponent, trial chunk).
+struct UnitPlan {
+  inputs: VsListInputs,
+  dealers: Vec<Dealer>,
+  trials: u32,
+  chunks: usize,
+  seed: u64,
+}
 
-  let hero1 = encode_card(hero_hand[0]);
-  let hero2 = encode_card(hero_hand[1]);
+impl UnitPlan {
+  fn new(
+    hero_hand_str: &str,
+    board_str: &str,
+    compare_list: &str,
+    trials: u32,
+    seed: u64,
+  ) -> Result<Self, String> {
+    let inputs = parse_vs_list_inputs(hero_hand_str, board_str, compare_list)?;
+    let trials = trials.max(1);
+    let dealers = inputs
+      .opponents
+      .iter()
+      .map(|opp| inputs.dealer(opp))
+      .collect::<Result<Vec<_>, String>>()?;
+    Ok(Self {
+      inputs,
+      dealers,
+      trials,
+      chunks: chunk_count(trials),
+      seed,
+    })
+  }
 
-  for opp in &opponents {
-    let opp1 = encode_card(opp[0]);
-    let opp2 = encode_card(opp[1]);
+  fn count(&self) -> usize {
+    self.inputs.opponents.len() * self.chunks
+  }
 
-    let mut exclude = Vec::with_capacity(board_len + 4);
-    exclude.extend_from_slice(&board);
-    exclude.push(hero_hand[0]);
-    exclude.push(hero_hand[1]);
-    exclude.push(opp[0]);
-    exclude.push(opp[1]);
-    let remaining_cards = 52usize.saturating_sub(exclude.len());
-    if remaining_cards < missing_board {
-      return Err("not enough cards to complete board".into());
+  fn run(&self, unit: usize, tallies: &mut [OpponentTally]) {
+    let idx = unit / self.chunks;
+    let opp = &self.inputs.opponents[idx];
+    let mut dealer = self.dealers[idx].clone();
+    let mut rng = DefaultRng::new(derive_seed(self.seed, unit as u64));
+    for _ in 0..chunk_trials(self.trials, unit % self.chunks) {
+      let (hero_score, opp_score) = dealer.play(&self.inputs.hero, opp, &mut rng);
+      tallies[idx].record(&hero_score, &opp_score);
     }
-    let deck_template = build_deck(&exclude);
-    let mut deck = deck_template.clone();
-    let mut full_board = board_buf;
+  }
+}
 
-    for _ in 0..trials {
-      deck.clone_from(&deck_template);
-      shuffle_slice(&mut deck, &mut rng);
+fn simulate_vs_list_with_ranks_inner<F: FnMut(u32)>(
+  hero_hand_str: &str,
+  board_str: &str,
+  compare_list: &str,
+  trials: u32,
+  seed: u64,
+  mut progress: Option<F>,
+) -> Result<Vec<RankedRecord>, String> {
+  let inputs = parse_vs_list_inputs(hero_hand_str, board_str, compare_list)?;
 
-      for i in 0..missing_board {
-        full_board[board_len + i] = deck[i];
+  let trials = trials.max(1);
+  let total_work = (inputs.opponents.len() as u64)
+    .saturating_mul(trials as u64)
+    .max(1);
+  let mut completed = 0u64;
+  let mut last_progress: Option<u32> = None;
+  let mut update_progress = |done: u64| {
+    if let Some(cb) = progress.as_mut() {
+      let pct = ((done.saturating_mul(100)) / total_work) as u32;
+      let clamped = pct.min(100);
+      if last_progress != Some(clamped) {
+        last_progress = Some(clamped);
+        cb(clamped);
       }
+    }
+  };
+  update_progress(0);
+  let mut tallies = vec![OpponentTally::default(); inputs.opponents.len()];
 
-      let hero_cards = [
-        hero_hand[0],
-        hero_hand[1],
-        full_board[0],
-        full_board[1],
-        full_board[2],
-        full_board[3],
-        full_board[4],
-      ];
-      let hero_score = best_of(&hero_cards);
-
-      let opp_cards = [
-        opp[0],
-        opp[1],
-        full_board[0],
-        full_board[1],
-        full_board[2],
-        full_board[3],
-        full_board[4],
-      ];
-      let opp_score = best_of(&opp_cards);
-
-      let (outcome, rank_idx) = match hero_score.encoded.cmp(&opp_score.encoded) {
-        Ordering::Greater => (0u32, hero_score.rank as u32),
-        Ordering::Equal => (2u32, hero_score.rank as u32),
-        Ordering::Less => (1u32, opp_score.rank as u32),
-      };
-
-      records.push([
-        hero1,
-        hero2,
-        encode_card(full_board[0]),
-        encode_card(full_board[1]),
-        encode_card(full_board[2]),
-        encode_card(full_board[3]),
-        encode_card(full_board[4]),
-        opp1,
-        opp2,
-        outcome,
-        rank_idx,
-      ]);
+  for (idx, (opp, tally)) in inputs.opponents.iter().zip(tallies.iter_mut()).enumerate() {
+    // deck per opponent: exclude hero + board + this opponent (built once)
+    let mut dealer = inputs.dealer(opp)?;
+
+    for trial in 0..trials {
+      // same per-trial generator as the trace record `idx * trials + trial`
+      let trial_index = idx as u64 * trials as u64 + trial as u64;
+      let mut rng = DefaultRng::new(derive_seed(seed, trial_index));
+      let (hero_score, opp_score) = dealer.play(&inputs.hero, opp, &mut rng);
+      tally.record(&hero_score, &opp_score);
+      completed = completed.saturating_add(1);
+      update_progress(completed);
     }
   }
 
-  Ok(records)
+  update_progress(total_work);
+  Ok(build_records(&inputs.opponents, &tallies))
 }
 
-fn simulate_vs_list_with_ranks_inner<F: FnMut(u32)>(
+/// Parsed and validated hero / board / opponent list.
+struct VsListInputs {
+  hero: [Card; 2],
+  board: Vec<Card>,
+  opponents: Vec<[Card; 2]>,
+}
+
+fn parse_vs_list_inputs(
   hero_hand_str: &str,
   board_str: &str,
   compare_list: &str,
-  trials: u32,
-  seed: u64,
-  mut progress: Option<F>,
-) -> Result<Vec<(u32, u32, u32, u32, u32, [u32; 9], [u32; 9], [u32; 9])>, String> {
+) -> Result<VsListInputs, String> {
   let hero_hand = parse_hand_two(hero_hand_str).ok_or("hero hand must have 2 cards")?;
 
   let board = parse_board(board_str).ok_or("failed to parse board")?;
@@ -261,159 +464,180 @@ fn simulate_vs_list_with_ranks_inner<F: FnMut(u32)>(
     }
   }
 
-  let trials = trials.max(1);
-  let total_work = (opponents.len() as u64)
-    .saturating_mul(trials as u64)
-    .max(1);
-  let mut completed = 0u64;
-  let mut last_progress: Option<u32> = None;
-  let mut update_progress = |done: u64| {
-    if let Some(cb) = progress.as_mut() {
-      let pct = ((done.saturating_mul(100)) / total_work) as u32;
-      let clamped = pct.min(100);
-      if last_progress != Some(clamped) {
-        last_progress = Some(clamped);
-        cb(clamped);
-      }
-    }
-  };
-  update_progress(0);
-  let board_len = board.len();
-  let missing_board = 5usize.saturating_sub(board_len);
-  let mut board_buf = [Card { rank: 0, suit: 0 }; 5];
-  board_buf[..board_len].copy_from_slice(&board);
-  let mut rng = Lcg64::new(seed);
-  let mut stats: Vec<(u32, u32, u32, u32, u32, [u32; 9], [u32; 9], [u32; 9])> = vec![
-    (0, 0, 0, 0, 0, [0u32; 9], [0u32; 9], [0u32; 9]);
-    opponents.len()
-  ];
-  let mut hero_rank_wins = [0u32; 9];
-  let mut hero_rank_ties = [0u32; 9];
-  let mut hero_rank_lose_counts = [0u32; 9];
-  let mut hero_wins_total = 0u32;
-  let mut hero_ties_total = 0u32;
-  let mut hero_plays_total = 0u32;
-
-  let opp_encoded: Vec<(u32, u32)> = opponents.iter().map(|h| decode_hand_pair(h)).collect();
-
-  for (idx, opp) in opponents.iter().enumerate() {
-    let mut wins = 0u32;
-    let mut ties = 0u32;
-    let mut plays = 0u32;
-    let mut rank_wins = [0u32; 9];
-    let mut rank_ties = [0u32; 9];
-    let mut rank_lose_counts = [0u32; 9];
+  Ok(VsListInputs {
+    hero: hero_hand,
+    board,
+    opponents,
+  })
+}
 
-    // deck per opponent: exclude hero + board + this opponent (built once)
+impl VsListInputs {
+  /// Dealer for one opponent: the deck excludes hero + board + this opponent.
+  fn dealer(&self, opp: &[Card; 2]) -> Result<Dealer, String> {
+    let board_len = self.board.len();
     let mut exclude = Vec::with_capacity(board_len + 4);
-    exclude.extend_from_slice(&board);
-    exclude.push(hero_hand[0]);
-    exclude.push(hero_hand[1]);
+    exclude.extend_from_slice(&self.board);
+    exclude.push(self.hero[0]);
+    exclude.push(self.hero[1]);
     exclude.push(opp[0]);
     exclude.push(opp[1]);
+    let missing_board = 5usize.saturating_sub(board_len);
     let remaining_cards = 52usize.saturating_sub(exclude.len());
     if remaining_cards < missing_board {
       return Err("not enough cards to complete board".into());
     }
     let deck_template = build_deck(&exclude);
-    let mut deck = deck_template.clone();
-    let mut full_board = board_buf;
+    let mut full_board = [Card { rank: 0, suit: 0 }; 5];
+    full_board[..board_len].copy_from_slice(&self.board);
+    Ok(Dealer {
+      deck: deck_template.clone(),
+      deck_template,
+      full_board,
+      board_len,
+    })
+  }
+}
 
-    for _ in 0..trials {
-      deck.clone_from(&deck_template);
-      shuffle_slice(&mut deck, &mut rng);
+/// Reusable buffers for completing the board against one opponent.
+#[derive(Clone)]
+struct Dealer {
+  deck_template: Vec<Card>,
+  deck: Vec<Card>,
+  full_board: [Card; 5],
+  board_len: usize,
+}
 
-      for i in 0..missing_board {
-        full_board[board_len + i] = deck[i];
-      }
+impl Dealer {
+  /// Shuffle, complete the board and score both hands for one trial.
+  fn play<R: SimRng>(&mut self, hero: &[Card; 2], opp: &[Card; 2], rng: &mut R) -> (HandScore, HandScore) {
+    self.deck.clone_from(&self.deck_template);
+
+    let board_len = self.board_len;
+    self.full_board[board_len..].copy_from_slice(deal_prefix(&mut self.deck, 5 - board_len, rng));
+    let full_board = self.full_board;
+
+    let hero_cards = [
+      hero[0],
+      hero[1],
+      full_board[0],
+      full_board[1],
+      full_board[2],
+      full_board[3],
+      full_board[4],
+    ];
+    let opp_cards = [
+      opp[0],
+      opp[1],
+      full_board[0],
+      full_board[1],
+      full_board[2],
+      full_board[3],
+      full_board[4],
+    ];
+    (best_of(&hero_cards), best_of(&opp_cards))
+  }
+}
 
-      let hero_cards = [
-        hero_hand[0],
-        hero_hand[1],
-        full_board[0],
-        full_board[1],
-        full_board[2],
-        full_board[3],
-        full_board[4],
-      ];
-      let hero_score = best_of(&hero_cards);
-
-      let opp_cards = [
-        opp[0],
-        opp[1],
-        full_board[0],
-        full_board[1],
-        full_board[2],
-        full_board[3],
-        full_board[4],
-      ];
-      let opp_score = best_of(&opp_cards);
-
-      plays += 1;
-      match hero_score.encoded.cmp(&opp_score.encoded) {
-        Ordering::Greater => {
-          wins += 1;
-          let hero_idx = hero_score.rank as usize;
-          if hero_idx < 9 {
-            hero_rank_wins[hero_idx] = hero_rank_wins[hero_idx].saturating_add(1);
-          }
-          let opp_idx = opp_score.rank as usize;
-          if opp_idx < 9 {
-            rank_lose_counts[opp_idx] = rank_lose_counts[opp_idx].saturating_add(1);
-          }
+/// Per-opponent counters, including the hero side of the same trials.
+#[derive(Clone, Copy, Default)]
+struct OpponentTally {
+  wins: u32,
+  ties: u32,
+  plays: u32,
+  rank_wins: [u32; 9],
+  rank_ties: [u32; 9],
+  rank_lose_counts: [u32; 9],
+  hero_rank_wins: [u32; 9],
+  hero_rank_ties: [u32; 9],
+  hero_rank_lose_counts: [u32; 9],
+}
+
+impl OpponentTally {
+  fn record(&mut self, hero_score: &HandScore, opp_score: &HandScore) {
+    self.plays += 1;
+    let hero_idx = hero_score.rank as usize;
+    let opp_idx = opp_score.rank as usize;
+    match hero_score.encoded.cmp(&opp_score.encoded) {
+      Ordering::Greater => {
+        self.wins += 1;
+        if hero_idx < 9 {
+          self.hero_rank_wins[hero_idx] = self.hero_rank_wins[hero_idx].saturating_add(1);
         }
-        Ordering::Equal => {
-          ties += 1;
-          let hero_idx = hero_score.rank as usize;
-          if hero_idx < 9 {
-            hero_rank_ties[hero_idx] = hero_rank_ties[hero_idx].saturating_add(1);
-          }
-          let opp_idx = opp_score.rank as usize;
-          if opp_idx < 9 {
-            rank_ties[opp_idx] = rank_ties[opp_idx].saturating_add(1);
-          }
+        if opp_idx < 9 {
+          self.rank_lose_counts[opp_idx] = self.rank_lose_counts[opp_idx].saturating_add(1);
         }
-        Ordering::Less => {
-          let hero_idx = hero_score.rank as usize;
-          if hero_idx < 9 {
-            hero_rank_lose_counts[hero_idx] = hero_rank_lose_counts[hero_idx].saturating_add(1);
-          }
-          let opp_idx = opp_score.rank as usize;
-          if opp_idx < 9 {
-            rank_wins[opp_idx] = rank_wins[opp_idx].saturating_add(1);
-          }
+      }
+      Ordering::Equal => {
+        self.ties += 1;
+        if hero_idx < 9 {
+          self.hero_rank_ties[hero_idx] = self.hero_rank_ties[hero_idx].saturating_add(1);
+        }
+        if opp_idx < 9 {
+          self.rank_ties[opp_idx] = self.rank_ties[opp_idx].saturating_add(1);
+        }
+      }
+      Ordering::Less => {
+        if hero_idx < 9 {
+          self.hero_rank_lose_counts[hero_idx] =
+            self.hero_rank_lose_counts[hero_idx].saturating_add(1);
+        }
+        if opp_idx < 9 {
+          self.rank_wins[opp_idx] = self.rank_wins[opp_idx].saturating_add(1);
         }
       }
-      completed = completed.saturating_add(1);
-      update_progress(completed);
     }
+  }
 
-    stats[idx].0 = opp_encoded[idx].0;
-    stats[idx].1 = opp_encoded[idx].1;
-    stats[idx].2 = wins;
-    stats[idx].3 = ties;
-    stats[idx].4 = plays;
-    stats[idx].5 = rank_wins;
-    stats[idx].6 = rank_ties;
-    stats[idx].7 = rank_lose_counts;
-
-    hero_wins_total = hero_wins_total.saturating_add(wins);
-    hero_ties_total = hero_ties_total.saturating_add(ties);
-    hero_plays_total = hero_plays_total.saturating_add(plays);
+  #[cfg(not(target_arch = "wasm32"))]
+  fn add(&mut self, other: &OpponentTally) {
+    self.wins = self.wins.saturating_add(other.wins);
+    self.ties = self.ties.saturating_add(other.ties);
+    self.plays = self.plays.saturating_add(other.plays);
+    add_counts(&mut self.rank_wins, &other.rank_wins);
+    add_counts(&mut self.rank_ties, &other.rank_ties);
+    add_counts(&mut self.rank_lose_counts, &other.rank_lose_counts);
+    add_counts(&mut self.hero_rank_wins, &other.hero_rank_wins);
+    add_counts(&mut self.hero_rank_ties, &other.hero_rank_ties);
+    add_counts(&mut self.hero_rank_lose_counts, &other.hero_rank_lose_counts);
+  }
+}
+
+/// One record per opponent, then the hero aggregate with sentinel cards = u32::MAX.
+fn build_records(
+  opponents: &[[Card; 2]],
+  tallies: &[OpponentTally],
+) -> Vec<RankedRecord> {
+  let mut hero = OpponentTally::default();
+  let mut stats = Vec::with_capacity(opponents.len() + 1);
+  for (opp, tally) in opponents.iter().zip(tallies) {
+    let (c1, c2) = decode_hand_pair(opp);
+    stats.push((
+      c1,
+      c2,
+      tally.wins,
+      tally.ties,
+      tally.plays,
+      tally.rank_wins,
+      tally.rank_ties,
+      tally.rank_lose_counts,
+    ));
+    hero.wins = hero.wins.saturating_add(tally.wins);
+    hero.ties = hero.ties.saturating_add(tally.ties);
+    hero.plays = hero.plays.saturating_add(tally.plays);
+    add_counts(&mut hero.hero_rank_wins, &tally.hero_rank_wins);
+    add_counts(&mut hero.hero_rank_ties, &tally.hero_rank_ties);
+    add_counts(&mut hero.hero_rank_lose_counts, &tally.hero_rank_lose_counts);
   }
 
-  // append hero aggregate record with sentinel cards = u32::MAX
   stats.push((
     u32::MAX,
     u32::MAX,
-    hero_wins_total,
-    hero_ties_total,
-    hero_plays_total,
-    hero_rank_wins,
-    hero_rank_ties,
-    hero_rank_lose_counts,
+    hero.wins,
+    hero.ties,
+    hero.plays,
+    hero.hero_rank_wins,
+    hero.hero_rank_ties,
+    hero.hero_rank_lose_counts,
   ));
-
-  update_progress(total_work);
-  Ok(stats)
+  stats
 }
@@ -1,6 +1,35 @@
-use super::card::Card;
-use super::eval::best_of;
-use super::simulate_vs_list_with_ranks;
+use super::binary;
+use super::card::{parse_card, Card};
+use super::deck::{deal_prefix, shuffle_slice};
+use super::eval::{best_five_of, best_of};
+use super::rng::{DefaultRng, SimRng, Xoshiro256pp};
+use super::{
+  merge_vs_list_with_ranks_shards,
+  replay_vs_list_with_ranks_trial,
+  simulate_vs_list_with_ranks,
+  simulate_vs_list_with_ranks_parallel,
+  simulate_vs_list_with_ranks_shard,
+  simulate_vs_list_with_ranks_trace,
+  VarianceReduction,
+  VsListTrace,
+};
+use crate::rs_poker_native::{
+  merge_rank_distribution_shards,
+  simulate_open_ranges_monte_carlo_tally,
+  simulate_open_ranges_monte_carlo_with_variance_reduction,
+  simulate_range_vs_range_equity,
+  simulate_range_vs_range_equity_with_variance_reduction,
+  simulate_rank_distribution_parallel,
+  simulate_rank_distribution_shard,
+  simulate_vs_list_equity_with_variance_reduction,
+};
+use crate::six_max::{
+  evaluate_six_max_preflop,
+  Position,
+  PositionRanges,
+  PreflopAction,
+  SixMaxSizes,
+};
 
 fn decode_card(v: u32) -> String {
   let rank = v >> 2;
@@ -73,3 +102,435 @@ fn hero_vs_qq_regression() {
   let hero_win_rate_vs_qq = w as f64 / p as f64;
   assert!(hero_win_rate_vs_qq < 0.5, "hero should not beat QQ");
 }
+
+#[test]
+fn parallel_vs_list_is_independent_of_thread_count() {
+  // 2500 trials = 3 work units per opponent, the last one short
+  let run = |threads| {
+    simulate_vs_list_with_ranks_parallel("5s 5h", "8d Qc 5c", "Kc Ks; Qs Qh; 9c 9h", 2500, 42, threads)
+      .expect("simulation ok")
+  };
+  let single = run(1);
+  assert_eq!(single, run(3));
+  assert_eq!(single, run(8));
+
+  let hero = single.last().expect("hero aggregate");
+  assert_eq!((hero.0, hero.1), (u32::MAX, u32::MAX));
+  assert_eq!(hero.4, 3 * 2500);
+  let rank_total: u32 = hero.5.iter().chain(&hero.6).chain(&hero.7).sum();
+  assert_eq!(rank_total, hero.4);
+}
+
+#[test]
+fn parallel_rank_distribution_is_independent_of_thread_count() {
+  let run = |threads| {
+    simulate_rank_distribution_parallel("Ah Kh; 7c 7d", "2h 3h 9s", 3000, 7, threads)
+      .expect("simulation ok")
+  };
+  let single = run(1);
+  assert_eq!(single, run(4));
+  for counts in &single {
+    assert_eq!(counts.iter().sum::<u32>(), 3000);
+  }
+}
+
+#[test]
+fn merged_shards_match_parallel_run() {
+  let expected =
+    simulate_vs_list_with_ranks_parallel("Ah Kh", "2h 3h 9s", "Qs Qd; 7c 7d", 2500, 99, 2)
+      .expect("simulation ok");
+  for shard_count in [1u32, 2, 5] {
+    let shards: Vec<_> = (0..shard_count)
+      .map(|i| {
+        simulate_vs_list_with_ranks_shard("Ah Kh", "2h 3h 9s", "Qs Qd; 7c 7d", 2500, 99, i, shard_count)
+          .expect("shard ok")
+      })
+      .collect();
+    assert_eq!(merge_vs_list_with_ranks_shards(&shards), Ok(expected.clone()));
+  }
+
+  let expected = simulate_rank_distribution_parallel("Ah Kh; 7c 7d", "2h 3h 9s", 3000, 5, 2)
+    .expect("simulation ok");
+  let shards: Vec<_> = (0..4)
+    .map(|i| simulate_rank_distribution_shard("Ah Kh; 7c 7d", "2h 3h 9s", 3000, 5, i, 4).expect("shard ok"))
+    .collect();
+  assert_eq!(merge_rank_distribution_shards(&shards), Ok(expected));
+}
+
+#[test]
+fn shard_index_must_be_below_shard_count() {
+  assert!(simulate_vs_list_with_ranks_shard("Ah Kh", "", "Qs Qd", 100, 1, 2, 2).is_err());
+  assert!(simulate_rank_distribution_shard("Ah Kh", "2h 3h 9s", 100, 1, 0, 0).is_err());
+}
+
+#[test]
+fn open_ranges_tallies_merge_by_adding_counts() {
+  let a = simulate_open_ranges_monte_carlo_tally("QQ+,AKs", "22+; A2s+", 2_000, 1).unwrap();
+  let b = simulate_open_ranges_monte_carlo_tally("QQ+,AKs", "22+; A2s+", 3_000, 2).unwrap();
+
+  let mut merged = a;
+  merged.merge(&b);
+  assert_eq!(merged.plays, a.plays + b.plays);
+  assert_eq!(merged.wins, a.wins + b.wins);
+  assert_eq!(merged.ties, a.ties + b.ties);
+  for i in 0..9 {
+    assert_eq!(merged.hero_rank_wins[i], a.hero_rank_wins[i] + b.hero_rank_wins[i]);
+  }
+  assert_eq!(merged.hero_rank_wins.iter().sum::<u32>(), merged.wins);
+}
+
+#[test]
+fn binary_vs_list_matches_record_output() {
+  let rows = simulate_vs_list_with_ranks("Ah Kh", "2h 7c 9d", "Qs Qd; Jc Tc", 500, 3).unwrap();
+  let words = binary::encode_vs_list_with_ranks((45, 49), &rows);
+  let records = binary::decode_vs_list_with_ranks(&words).unwrap();
+
+  assert_eq!(records.len(), rows.len());
+  for (record, row) in records.iter().zip(&rows) {
+    assert_eq!(record.count, row.4);
+    assert_eq!(record.tie, row.3);
+    assert_eq!(record.win + record.tie + record.lose, record.count);
+    assert_eq!(record.rank_win, row.5);
+    assert_eq!(record.rank_lose, row.7);
+  }
+  let (hero, opponents) = records.split_last().unwrap();
+  assert!(hero.hero_aggregate && hero.cards == [45, 49]);
+  assert_eq!(hero.win, rows.last().unwrap().2);
+  for (record, row) in opponents.iter().zip(&rows) {
+    assert!(!record.hero_aggregate);
+    assert_eq!((record.cards, record.lose), ([row.0, row.1], row.2));
+  }
+}
+
+#[test]
+fn binary_range_vs_range_matches_record_output_and_checks_header() {
+  let rows = simulate_range_vs_range_equity("QQ+", "JJ-99", "2h 7c 9d", 20, 1).unwrap();
+  let words = binary::encode_range_vs_range(&rows);
+  let records = binary::decode_range_vs_range(&words).unwrap();
+  for (record, (c1, c2, equity, role)) in records.iter().zip(&rows) {
+    assert_eq!(record.cards, [*c1, *c2]);
+    assert_eq!((record.equity, record.villain), (*equity, *role == 1));
+  }
+
+  assert!(binary::decode_vs_list_with_ranks(&words).is_err());
+  assert!(binary::decode_header(&words[..words.len() - 1]).is_err());
+  let mut extended = words.clone();
+  extended[4] += 1;
+  extended.truncate(binary::HEADER_LEN);
+  for record in words[binary::HEADER_LEN..].chunks(binary::RANGE_VS_RANGE_RECORD_LEN) {
+    extended.extend_from_slice(record);
+    extended.push(0);
+  }
+  assert_eq!(binary::decode_range_vs_range(&extended).unwrap(), records);
+}
+
+#[test]
+fn trace_stream_matches_materialized_trace_and_replays_any_trial() {
+  let (hero, board, compare, trials, seed) = ("Ah Kh", "2h 7c", "Qs Qd; Jc Tc; 7s 7d", 40, 9);
+  let legacy = simulate_vs_list_with_ranks_trace(hero, board, compare, trials, seed).unwrap();
+  let streamed: Vec<_> = VsListTrace::new(hero, board, compare, trials, seed).unwrap().collect();
+  assert_eq!(streamed.len(), legacy.len());
+
+  for (i, (record, words)) in streamed.iter().zip(&legacy).enumerate() {
+    assert_eq!(record.trial_index, i as u64);
+    assert_eq!(record.to_words()[..11], words[..]);
+
+    // best five come from the winner's seven cards and make the reported category
+    let winner = if record.outcome == 1 { record.villain } else { record.hero };
+    let seven: Vec<u32> = winner.iter().chain(&record.board).copied().collect();
+    assert!(record.best_five.iter().all(|c| seven.contains(c)));
+    let five = record.best_five.map(|c| Card { rank: (c >> 2) as u8, suit: (c & 3) as u8 });
+    assert_eq!(best_of(&five).rank as u32, record.rank_index);
+    let winner_rank = if record.outcome == 1 { record.villain_rank_index } else { record.hero_rank_index };
+    assert_eq!(winner_rank, record.rank_index);
+  }
+
+  for index in [0u64, 1, 39, 40, 77, 119] {
+    let replayed =
+      replay_vs_list_with_ranks_trial(hero, board, compare, trials, seed, index).unwrap();
+    assert_eq!(replayed, streamed[index as usize]);
+  }
+  assert!(replay_vs_list_with_ranks_trial(hero, board, compare, trials, seed, 120).is_err());
+
+  let mut resumed = VsListTrace::new(hero, board, compare, trials, seed).unwrap();
+  resumed.seek(50);
+  assert_eq!(resumed.size_hint(), (70, Some(70)));
+  assert_eq!(resumed.collect::<Vec<_>>(), streamed[50..]);
+}
+
+#[test]
+fn vs_list_counts_are_the_tally_of_the_trace() {
+  let (hero, board, compare, trials, seed) = ("Ah Kh", "2h 7c", "Qs Qd; Jc Tc; 7s 7d", 60, 17);
+  let rows = simulate_vs_list_with_ranks(hero, board, compare, trials, seed).unwrap();
+  let trace: Vec<_> = VsListTrace::new(hero, board, compare, trials, seed).unwrap().collect();
+
+  let (opponents, hero_row) = rows.split_at(rows.len() - 1);
+  for (idx, row) in opponents.iter().enumerate() {
+    let records = &trace[idx * trials as usize..(idx + 1) * trials as usize];
+    let count = |outcome: u32| records.iter().filter(|r| r.outcome == outcome).count() as u32;
+    assert_eq!((row.2, row.3, row.4), (count(0), count(2), trials));
+
+    // opponent's category counts by how the trial ended for the opponent
+    let mut rank_wins = [0u32; 9];
+    let mut rank_lose = [0u32; 9];
+    for r in records {
+      match r.outcome {
+        1 => rank_wins[r.villain_rank_index as usize] += 1,
+        0 => rank_lose[r.villain_rank_index as usize] += 1,
+        _ => {}
+      }
+    }
+    assert_eq!((row.5, row.7), (rank_wins, rank_lose));
+  }
+  let hero_wins = trace.iter().filter(|r| r.outcome == 0).count() as u32;
+  assert_eq!((hero_row[0].2, hero_row[0].4), (hero_wins, trace.len() as u32));
+}
+
+#[test]
+fn best_five_lists_category_cards_before_kickers() {
+  let cases = [
+    ("Kh 5d Qs Ks 5c 2h 3d", "Ks Kh 5d 5c Qs"),
+    ("Ah 7c 7d 2s 9h Jd 7s", "7s 7d 7c Ah Jd"),
+    ("5c Kd Ks 5h 5s 2c Kh", "Ks Kh Kd 5h 5c"),
+    ("Ah 2d 3c 4s 5h 9d Kc", "5h 4s 3c 2d Ah"),
+    ("9h 8h 7h 6h 5h 2c Ah", "9h 8h 7h 6h 5h"),
+    ("As Kd 9c 7h 4s 3d 2c", "As Kd 9c 7h 4s"),
+  ];
+  for (seven, expected) in cases {
+    let cards: Vec<Card> = seven.split_whitespace().map(|t| parse_card(t).unwrap()).collect();
+    let (score, five) = best_five_of(&cards);
+    assert_eq!(score, best_of(&cards));
+    let words: Vec<String> =
+      five.iter().map(|c| decode_card((c.rank as u32) << 2 | c.suit as u32)).collect();
+    assert_eq!(words.join(" "), expected, "{seven}");
+  }
+}
+
+/// Pearson chi-square of `observed` counts against a uniform expectation.
+fn chi_square(observed: &[u64], expected: f64) -> f64 {
+  observed.iter().map(|&o| (o as f64 - expected).powi(2) / expected).sum()
+}
+
+#[test]
+fn xoshiro_matches_reference_outputs() {
+  let mut rng = Xoshiro256pp::from_state([1, 2, 3, 4]);
+  let outputs: Vec<u64> = (0..3).map(|_| rng.next_u64()).collect();
+  assert_eq!(outputs, [41943041, 58720359, 3588806011781223]);
+
+  // every residue of a small bound shows up about equally often
+  let mut rng = DefaultRng::new(3);
+  let mut counts = [0u64; 7];
+  for _ in 0..70_000 {
+    counts[rng.below(7) as usize] += 1;
+  }
+  // dof 6, p = 0.001 critical value 22.46
+  assert!(chi_square(&counts, 10_000.0) < 22.46, "{counts:?}");
+}
+
+#[test]
+fn shuffle_puts_every_card_in_every_position_uniformly() {
+  let shuffles = 20_000u64;
+  let mut rng = DefaultRng::new(11);
+  let mut counts = vec![0u64; 52 * 52];
+  for _ in 0..shuffles {
+    let mut cards: Vec<usize> = (0..52).collect();
+    shuffle_slice(&mut cards, &mut rng);
+    for (pos, &card) in cards.iter().enumerate() {
+      counts[card * 52 + pos] += 1;
+    }
+  }
+  // dof 51 × 51 = 2601, p = 0.001 critical value about 2835
+  let stat = chi_square(&counts, shuffles as f64 / 52.0);
+  assert!(stat < 2835.0, "chi-square {stat}");
+}
+
+#[test]
+fn partial_deal_draws_a_uniform_ordered_sample() {
+  let deals = 47_000u64;
+  let mut rng = DefaultRng::new(5);
+  let mut counts = vec![0u64; 5 * 47];
+  for _ in 0..deals {
+    let mut cards: Vec<usize> = (0..47).collect();
+    let dealt = deal_prefix(&mut cards, 5, &mut rng);
+    assert_eq!(dealt.len(), 5);
+    for (pos, &card) in dealt.iter().enumerate() {
+      counts[pos * 47 + card] += 1;
+    }
+  }
+  // each of the 5 positions is uniform over 47 cards: dof 5 × 46 = 230,
+  // p = 0.001 critical value about 300
+  let stat = chi_square(&counts, deals as f64 / 47.0);
+  assert!(stat < 300.0, "chi-square {stat}");
+
+  let mut cards = [1, 2, 3];
+  assert_eq!(deal_prefix(&mut cards, 9, &mut rng).len(), 3);
+}
+
+/// Mean and sample variance of `estimate(seed)` over `seeds` seeds. The variance of an
+/// estimator is proportional to 1 / trials, so the ratio of two variances is the share
+/// of trials one needs for the other's standard error.
+fn spread_over_seeds(seeds: u64, estimate: impl Fn(u64) -> f64) -> (f64, f64) {
+  let xs: Vec<f64> = (0..seeds).map(estimate).collect();
+  let mean = xs.iter().sum::<f64>() / xs.len() as f64;
+  let var = xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (xs.len() - 1) as f64;
+  (mean, var)
+}
+
+#[test]
+fn stratified_control_variate_vs_list_needs_fewer_trials() {
+  // one trial per turn card; the overcards need the turn or the river
+  let hero_equity = |options: VarianceReduction| {
+    move |seed| {
+      let rows = simulate_vs_list_equity_with_variance_reduction(
+        "Jc Jd", "2c 7d 9h", "As Ks", 45, seed, false, options,
+      )
+      .unwrap();
+      let (_, _, wins, ties, plays) = rows[0];
+      (wins as f64 + ties as f64 / 2.0) / plays as f64
+    }
+  };
+  let options = VarianceReduction { stratify: true, control_variate: true, ..Default::default() };
+  let plain = spread_over_seeds(300, hero_equity(VarianceReduction::default()));
+  let reduced = spread_over_seeds(300, hero_equity(options));
+  // about 0.57 of the trials over 3000 seeds
+  assert!(reduced.1 < 0.8 * plain.1, "{reduced:?} vs {plain:?}");
+  assert!((reduced.0 - plain.0).abs() < 0.01, "{reduced:?} vs {plain:?}");
+}
+
+#[test]
+fn stratified_control_variate_range_vs_range_needs_fewer_trials() {
+  let hero_equity = |options: VarianceReduction| {
+    move |seed| {
+      let rows = simulate_range_vs_range_equity_with_variance_reduction(
+        "JcJd", "AsKs", "2c 7d 9h", 45, seed, options,
+      )
+      .unwrap();
+      rows.iter().find(|r| r.3 == 0).unwrap().2 as f64 / 1e6
+    }
+  };
+  let options = VarianceReduction { stratify: true, control_variate: true, ..Default::default() };
+  let plain = spread_over_seeds(300, hero_equity(VarianceReduction::default()));
+  let reduced = spread_over_seeds(300, hero_equity(options));
+  assert!(reduced.1 < 0.8 * plain.1, "{reduced:?} vs {plain:?}");
+  assert!((reduced.0 - plain.0).abs() < 0.01, "{reduced:?} vs {plain:?}");
+}
+
+#[test]
+fn stratified_control_variate_preflop_vs_list_needs_fewer_trials() {
+  // preflop the control is the flop, enumerated exactly for the pair
+  let hero_equity = |options: VarianceReduction| {
+    move |seed| {
+      let rows = simulate_vs_list_equity_with_variance_reduction(
+        "Jc Jd", "", "As Ks", 48, seed, false, options,
+      )
+      .unwrap();
+      let (_, _, wins, ties, plays) = rows[0];
+      (wins as f64 + ties as f64 / 2.0) / plays as f64
+    }
+  };
+  let options = VarianceReduction { stratify: true, control_variate: true, ..Default::default() };
+  let plain = spread_over_seeds(80, hero_equity(VarianceReduction::default()));
+  let reduced = spread_over_seeds(80, hero_equity(options));
+  // about 0.63 of the trials over 150 seeds
+  assert!(reduced.1 < 0.8 * plain.1, "{reduced:?} vs {plain:?}");
+  assert!((reduced.0 - plain.0).abs() < 0.02, "{reduced:?} vs {plain:?}");
+}
+
+#[test]
+fn stratified_control_variate_preflop_range_vs_range_needs_fewer_trials() {
+  let hero_equity = |options: VarianceReduction| {
+    move |seed| {
+      let rows = simulate_range_vs_range_equity_with_variance_reduction(
+        "JcJd", "AsKs", "", 48, seed, options,
+      )
+      .unwrap();
+      rows.iter().find(|r| r.3 == 0).unwrap().2 as f64 / 1e6
+    }
+  };
+  let options = VarianceReduction { stratify: true, control_variate: true, ..Default::default() };
+  let plain = spread_over_seeds(80, hero_equity(VarianceReduction::default()));
+  let reduced = spread_over_seeds(80, hero_equity(options));
+  assert!(reduced.1 < 0.8 * plain.1, "{reduced:?} vs {plain:?}");
+  assert!((reduced.0 - plain.0).abs() < 0.02, "{reduced:?} vs {plain:?}");
+}
+
+#[test]
+fn antithetic_open_ranges_needs_fewer_trials() {
+  let hero_equity = |options: VarianceReduction| {
+    move |seed| {
+      let t = simulate_open_ranges_monte_carlo_with_variance_reduction(
+        "22+,A2s+,A2o+",
+        "22+,A2s+,K2s+,A2o+,K2o+",
+        400,
+        seed,
+        options,
+      )
+      .unwrap();
+      (t.wins as f64 + t.ties as f64 / 2.0) / t.plays as f64
+    }
+  };
+  let plain = spread_over_seeds(300, hero_equity(VarianceReduction::default()));
+  let options = VarianceReduction { antithetic: true, ..Default::default() };
+  let reduced = spread_over_seeds(300, hero_equity(options));
+  // about 0.63 of the trials over 1000 seeds
+  assert!(reduced.1 < 0.85 * plain.1, "{reduced:?} vs {plain:?}");
+  assert!((reduced.0 - plain.0).abs() < 0.02, "{reduced:?} vs {plain:?}");
+
+  let control = VarianceReduction { control_variate: true, ..Default::default() };
+  assert!(
+    simulate_open_ranges_monte_carlo_with_variance_reduction("AA", "KK", 10, 1, control).is_err()
+  );
+}
+
+const SIX_MAX_SIZES: SixMaxSizes =
+  SixMaxSizes { small_blind: 50.0, big_blind: 100.0, open: 250.0, three_bet: 900.0 };
+
+#[test]
+fn six_max_open_takes_the_blinds_when_everyone_folds() {
+  let ranges = PositionRanges::parse("AA;AA;AA;AA;AA;", ";;;;;", ";;;;;").unwrap();
+  let r = evaluate_six_max_preflop(
+    Position::Btn, "72o", PreflopAction::Open, None, &ranges, SIX_MAX_SIZES, 200, 3,
+  )
+  .unwrap();
+  assert_eq!(r.deals, 200);
+  assert_eq!(r.folds, 200);
+  assert_eq!(r.pot_before, 150.0);
+  assert_eq!(r.investment, 250.0);
+  assert_eq!(r.ev, 150.0);
+  assert_eq!(r.continue_frequency, [0.0; 6]);
+
+  assert!(evaluate_six_max_preflop(
+    Position::Bb, "AA", PreflopAction::Open, None, &ranges, SIX_MAX_SIZES, 10, 3,
+  )
+  .is_err());
+  assert!(evaluate_six_max_preflop(
+    Position::Co, "AA", PreflopAction::Call, Some(Position::Btn), &ranges, SIX_MAX_SIZES, 10, 3,
+  )
+  .is_err());
+}
+
+#[test]
+fn six_max_three_bet_narrows_the_opener_to_its_continuing_hands() {
+  // UTG opens QQ+,AKs and only continues against a 3-bet with AA
+  let ranges = PositionRanges::parse("QQ+,AKs;;;;;", "AA;;;;;", ";;;;;").unwrap();
+  let r = evaluate_six_max_preflop(
+    Position::Btn,
+    "KK",
+    PreflopAction::ThreeBet,
+    Some(Position::Utg),
+    &ranges,
+    SIX_MAX_SIZES,
+    4000,
+    11,
+  )
+  .unwrap();
+  // hero's kings leave 6 AA, 1 KK, 6 QQ and 2 AKs open combos; only the aces continue
+  let showdowns = (r.deals - r.folds) as f64 / r.deals as f64;
+  assert!((showdowns - 6.0 / 15.0).abs() < 0.03, "{r:?}");
+  assert!((r.continue_frequency[Position::Utg.index()] - showdowns).abs() < 1e-12);
+  assert!((r.equity - 0.18).abs() < 0.03, "{r:?}");
+  assert_eq!(r.average_opponents, 1.0);
+  // blinds, the open and hero's 3-bet
+  assert_eq!(r.pot_before, 50.0 + 100.0 + 250.0);
+  assert_eq!(r.investment, 900.0);
+}
@@ -0,0 +1,266 @@
+//! Optional variance reduction for Monte Carlo runouts.
+//!
+//! Every technique keeps each runout uniformly distributed and only changes how
+//! runouts relate to each other. Stratification and antithetic pairing keep the
+//! estimates unbiased; the control variate does not quite (see below):
+//!
+//! - **Stratification** cycles the first missing community card through the live
+//!   deck (a fresh random order per cycle), so every card leads equally often.
+//! - **Antithetic pairing** follows each runout with its mirror: the live deck
+//!   sorted by (rank, suit) is reversed, so high cards become low cards and suits
+//!   are swapped. Outcomes that move with board height then cancel within a pair.
+//! - **Control variate** (fixed heads-up hands only): the hero's result with the
+//!   board completed to the next street (the flop preflop, the turn on the flop)
+//!   is enumerated exactly once, and each estimate is corrected by how far the
+//!   sampled next-street results drifted from that exact value. The correction
+//!   slope is estimated from the same runouts and the result is clamped to 0-1, so
+//!   the estimate carries an O(1/trials) bias in exchange for the lower variance.
+//!
+//! The control is the next street rather than the exact preflop equity of the hand
+//! pair: that would need every board enumerated per pair (about 1.7M) or a
+//! precomputed table of all suit-distinct matchups, neither of which fits the wasm
+//! build. Because the control is enumerated per fixed pair, it is not offered when
+//! the hands are sampled every deal (open ranges).
+
+use super::card::Card;
+use super::deck::{build_deck, deal_prefix, shuffle_slice};
+use super::eval::best_of;
+use super::rng::SimRng;
+
+/// Variance-reduction switches. The default is plain independent sampling.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VarianceReduction {
+  pub stratify: bool,
+  pub antithetic: bool,
+  pub control_variate: bool,
+}
+
+impl VarianceReduction {
+  pub const STRATIFY: u32 = 1;
+  pub const ANTITHETIC: u32 = 2;
+  pub const CONTROL_VARIATE: u32 = 4;
+
+  /// Switches from the `STRATIFY | ANTITHETIC | CONTROL_VARIATE` bit flags used by
+  /// the wasm exports. Unknown bits are ignored.
+  pub fn from_bits(bits: u32) -> Self {
+    Self {
+      stratify: bits & Self::STRATIFY != 0,
+      antithetic: bits & Self::ANTITHETIC != 0,
+      control_variate: bits & Self::CONTROL_VARIATE != 0,
+    }
+  }
+}
+
+/// Deals runouts from a live deck sorted by (rank, suit), applying stratification
+/// and antithetic pairing. The live deck may change between calls (e.g. when the
+/// hole cards are sampled too) as long as its size does not.
+pub(crate) struct RunoutSampler {
+  options: VarianceReduction,
+  /// Live-deck indices; the runout is dealt from the front.
+  order: Vec<usize>,
+  /// Random order of first-card strata for the current cycle.
+  strata: Vec<usize>,
+  cursor: usize,
+  mirror_pending: bool,
+}
+
+impl RunoutSampler {
+  pub(crate) fn new(live_len: usize, options: VarianceReduction) -> Self {
+    Self {
+      options,
+      order: (0..live_len).collect(),
+      strata: (0..live_len).collect(),
+      cursor: 0,
+      mirror_pending: false,
+    }
+  }
+
+  /// Whether the next runout mirrors the previous one. Callers that sample hole
+  /// cards must keep the previous hands for it.
+  pub(crate) fn mirror_next(&self) -> bool {
+    self.mirror_pending
+  }
+
+  /// Fill `out` with the next runout drawn from `live`.
+  pub(crate) fn deal<R: SimRng>(&mut self, live: &[Card], out: &mut [Card], rng: &mut R) {
+    let count = out.len();
+    debug_assert_eq!(live.len(), self.order.len());
+    if self.mirror_pending {
+      let last = live.len() - 1;
+      for (slot, &i) in out.iter_mut().zip(&self.order) {
+        *slot = live[last - i];
+      }
+      self.mirror_pending = false;
+      return;
+    }
+
+    if self.options.stratify && count > 0 {
+      if self.cursor == 0 {
+        shuffle_slice(&mut self.strata, rng);
+      }
+      let first = self.strata[self.cursor];
+      self.cursor = (self.cursor + 1) % self.strata.len();
+      let pos = self.order.iter().position(|&i| i == first).unwrap_or(0);
+      self.order.swap(0, pos);
+      deal_prefix(&mut self.order[1..], count - 1, rng);
+    } else {
+      deal_prefix(&mut self.order, count, rng);
+    }
+    for (slot, &i) in out.iter_mut().zip(&self.order) {
+      *slot = live[i];
+    }
+    self.mirror_pending = self.options.antithetic;
+  }
+}
+
+/// Hero's estimated win and tie probabilities against one fixed hand.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct HeadsUpEstimate {
+  pub(crate) win: f64,
+  pub(crate) tie: f64,
+}
+
+/// Sample `trials` runouts of `hero` vs `villain` on `board` (0-5 cards, no
+/// overlaps) with the given techniques.
+pub(crate) fn estimate_heads_up<R: SimRng>(
+  hero: &[Card; 2],
+  villain: &[Card; 2],
+  board: &[Card],
+  trials: u32,
+  options: VarianceReduction,
+  rng: &mut R,
+) -> HeadsUpEstimate {
+  let mut dead = board.to_vec();
+  dead.extend_from_slice(hero);
+  dead.extend_from_slice(villain);
+  let live = build_deck(&dead);
+  let missing = 5 - board.len();
+  if missing == 0 {
+    let (win, tie) = showdown(hero, villain, board);
+    return HeadsUpEstimate { win, tie };
+  }
+
+  // cards up to the next street; on the turn that is the river itself
+  let next_street = if board.len() < 3 { 3 - board.len() } else { 1 };
+  let control = options
+    .control_variate
+    .then(|| exact_next_street(hero, villain, board, &live, next_street));
+
+  let mut sampler = RunoutSampler::new(live.len(), options);
+  let mut full = board.to_vec();
+  full.resize(5, live[0]);
+  let trials = trials.max(1);
+  let (mut win, mut tie) = (Regression::default(), Regression::default());
+  for _ in 0..trials {
+    sampler.deal(&live, &mut full[board.len()..], rng);
+    let (y_win, y_tie) = showdown(hero, villain, &full);
+    let (x_win, x_tie) = if control.is_some() {
+      showdown(hero, villain, &full[..board.len() + next_street])
+    } else {
+      (0.0, 0.0)
+    };
+    win.add(x_win, y_win);
+    tie.add(x_tie, y_tie);
+  }
+
+  match control {
+    Some((exact_win, exact_tie)) => HeadsUpEstimate {
+      win: win.adjusted_mean(exact_win),
+      tie: tie.adjusted_mean(exact_tie),
+    },
+    None => HeadsUpEstimate {
+      win: win.mean_y(),
+      tie: tie.mean_y(),
+    },
+  }
+}
+
+/// (win, tie) for hero on a board of 3-5 cards.
+fn showdown(hero: &[Card; 2], villain: &[Card; 2], board: &[Card]) -> (f64, f64) {
+  let mut cards = [hero[0]; 7];
+  let n = board.len() + 2;
+  cards[2..n].copy_from_slice(board);
+  cards[1] = hero[1];
+  let h = best_of(&cards[..n]).encoded;
+  cards[0] = villain[0];
+  cards[1] = villain[1];
+  let v = best_of(&cards[..n]).encoded;
+  match h.cmp(&v) {
+    core::cmp::Ordering::Greater => (1.0, 0.0),
+    core::cmp::Ordering::Equal => (0.0, 1.0),
+    core::cmp::Ordering::Less => (0.0, 0.0),
+  }
+}
+
+/// Exact (win, tie) for hero over every way to deal `count` more cards.
+fn exact_next_street(
+  hero: &[Card; 2],
+  villain: &[Card; 2],
+  board: &[Card],
+  live: &[Card],
+  count: usize,
+) -> (f64, f64) {
+  let mut cards = board.to_vec();
+  let (mut win, mut tie, mut total) = (0.0, 0.0, 0.0);
+  let mut visit = |cards: &[Card]| {
+    let (w, t) = showdown(hero, villain, cards);
+    win += w;
+    tie += t;
+    total += 1.0;
+  };
+  combinations(live, count, &mut cards, &mut visit);
+  (win / total, tie / total)
+}
+
+fn combinations<F: FnMut(&[Card])>(
+  pool: &[Card],
+  count: usize,
+  cards: &mut Vec<Card>,
+  visit: &mut F,
+) {
+  if count == 0 {
+    visit(cards);
+    return;
+  }
+  for i in 0..=pool.len() - count {
+    cards.push(pool[i]);
+    combinations(&pool[i + 1..], count - 1, cards, visit);
+    cards.pop();
+  }
+}
+
+/// Running sums for a regression-adjusted (control variate) mean of `y` given `x`.
+#[derive(Default)]
+struct Regression {
+  n: f64,
+  sum_x: f64,
+  sum_y: f64,
+  sum_xx: f64,
+  sum_xy: f64,
+}
+
+impl Regression {
+  fn add(&mut self, x: f64, y: f64) {
+    self.n += 1.0;
+    self.sum_x += x;
+    self.sum_y += y;
+    self.sum_xx += x * x;
+    self.sum_xy += x * y;
+  }
+
+  fn mean_y(&self) -> f64 {
+    self.sum_y / self.n
+  }
+
+  /// `mean(y) - b (mean(x) - exact_x)` with the least-squares slope `b` from the same
+  /// sample, clamped to 0-1 (slightly biased for small samples).
+  fn adjusted_mean(&self, exact_x: f64) -> f64 {
+    let mean_x = self.sum_x / self.n;
+    let var_x = self.sum_xx / self.n - mean_x * mean_x;
+    if var_x <= f64::EPSILON {
+      return self.mean_y();
+    }
+    let cov = self.sum_xy / self.n - mean_x * self.mean_y();
+    (self.mean_y() - cov / var_x * (mean_x - exact_x)).clamp(0.0, 1.0)
+  }
+}
@@ -0,0 +1,380 @@
+//! 6-max preflop scenarios: hero's equity and a simplified EV for opening, calling an
+//! open, or 3-betting, against the players who continue with their position's ranges.
+//!
+//! Deals reuse the open-ranges sampling ([`crate::rs_poker_native::seat_opponents`]): every
+//! other player gets random hole cards and stays in the pot only when they fall in the
+//! range for that spot. Hands that are already known to be in a range (hero's, and the
+//! opener's when hero faces an open) are drawn from that range instead of being rejected.
+//!
+//! The model is deliberately small:
+//!
+//! - Open: players behind continue with their call or 3-bet range vs an open, and every
+//!   continuing player is treated as calling the open (no 4-bet tree).
+//! - Call: the opener stays in; players behind continue with their call or 3-bet range.
+//! - 3-bet: the opener continues with the part of its open range that is also in its call
+//!   or 3-bet range (as a stand-in for 4-bet / call); players behind continue only with
+//!   their 3-bet range. Everyone who continues matches the 3-bet.
+//! - Players that continue see all five board cards; EV is hero's pot share at showdown
+//!   minus the chips hero adds, and the whole pot when everyone folds (folding now is 0).
+
+use std::collections::HashSet;
+
+use crate::rs_poker_native::{build_range_set, parse_range_hands, seat_opponents};
+use crate::sim::{
+  build_deck, deal_prefix, decode_hand_pair, eval::best_of, Card, DefaultRng, SimRng,
+};
+
+/// 6-max seats in preflop action order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Position {
+  Utg,
+  Mp,
+  Co,
+  Btn,
+  Sb,
+  Bb,
+}
+
+impl Position {
+  pub const ALL: [Position; 6] = [
+    Position::Utg,
+    Position::Mp,
+    Position::Co,
+    Position::Btn,
+    Position::Sb,
+    Position::Bb,
+  ];
+
+  /// Index in action order (UTG = 0 .. BB = 5), as used by the FFI export.
+  pub fn from_index(index: u32) -> Option<Self> {
+    Self::ALL.get(index as usize).copied()
+  }
+
+  pub fn index(self) -> usize {
+    self as usize
+  }
+
+  /// Blind posted before the action.
+  fn posted(self, small_blind: f64, big_blind: f64) -> f64 {
+    match self {
+      Position::Sb => small_blind,
+      Position::Bb => big_blind,
+      _ => 0.0,
+    }
+  }
+}
+
+/// Hero's preflop action.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PreflopAction {
+  /// First in (everyone before hero folded).
+  Open,
+  /// Flat call of an open.
+  Call,
+  ThreeBet,
+}
+
+impl PreflopAction {
+  /// 0 = open, 1 = call, 2 = 3-bet.
+  pub fn from_index(index: u32) -> Option<Self> {
+    match index {
+      0 => Some(PreflopAction::Open),
+      1 => Some(PreflopAction::Call),
+      2 => Some(PreflopAction::ThreeBet),
+      _ => None,
+    }
+  }
+}
+
+/// One range string per position, in [`Position::ALL`] order. An empty string is an empty range.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PositionRanges {
+  pub open: [String; 6],
+  /// Flat calls vs an open.
+  pub call: [String; 6],
+  /// 3-bets vs an open.
+  pub three_bet: [String; 6],
+}
+
+impl PositionRanges {
+  /// Each argument is six semicolon-separated ranges, UTG first.
+  pub fn parse(open: &str, call: &str, three_bet: &str) -> Result<Self, String> {
+    Ok(Self {
+      open: split_positions(open)?,
+      call: split_positions(call)?,
+      three_bet: split_positions(three_bet)?,
+    })
+  }
+}
+
+fn split_positions(ranges: &str) -> Result<[String; 6], String> {
+  let parts: Vec<&str> = ranges.split(';').map(str::trim).collect();
+  if parts.len() != 6 {
+    return Err(format!("expected 6 position ranges, got {}", parts.len()));
+  }
+  Ok(std::array::from_fn(|i| parts[i].to_string()))
+}
+
+/// Stakes and bet sizes, in chips. Sizes are the total bet for the hand (blind included).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SixMaxSizes {
+  pub small_blind: f64,
+  pub big_blind: f64,
+  pub open: f64,
+  pub three_bet: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SixMaxEvaluation {
+  /// Deals that matched hero's range (and the opener's).
+  pub deals: u32,
+  /// Deals where every other player folded.
+  pub folds: u32,
+  /// Hero's average pot share when someone continues (ties split).
+  pub equity: f64,
+  /// Average number of opponents in the pot when someone continues.
+  pub average_opponents: f64,
+  /// Expected chips won compared with folding now.
+  pub ev: f64,
+  /// Chips in the pot before hero acts.
+  pub pot_before: f64,
+  /// Chips hero adds with the action.
+  pub investment: f64,
+  /// How often each position is in the pot after hero's action (hero's own entry is 0).
+  pub continue_frequency: [f64; 6],
+}
+
+/// A player other than hero in the scenario.
+struct Seat {
+  position: Position,
+  /// Drawn from this range instead of from the deck (the opener when hero faces an open).
+  known: Option<Vec<[Card; 2]>>,
+  continues: HashSet<(u32, u32)>,
+  /// Chips in the pot before hero acts, and after calling hero's bet.
+  current: f64,
+  matched: f64,
+}
+
+/// Evaluate hero with `hero_range` (a hand like `"AsKs"` or a range) taking `action` from
+/// `hero`. `opener` is required for a call or 3-bet and must act before hero.
+#[allow(clippy::too_many_arguments)]
+pub fn evaluate_six_max_preflop(
+  hero: Position,
+  hero_range: &str,
+  action: PreflopAction,
+  opener: Option<Position>,
+  ranges: &PositionRanges,
+  sizes: SixMaxSizes,
+  trials: u32,
+  seed: u64,
+) -> Result<SixMaxEvaluation, String> {
+  let SixMaxSizes {
+    small_blind,
+    big_blind,
+    open,
+    three_bet,
+  } = sizes;
+  if !(small_blind >= 0.0 && big_blind >= small_blind && open >= big_blind && three_bet > open) {
+    return Err("sizes must satisfy 0 <= small blind <= big blind <= open < 3-bet".into());
+  }
+  let hero_combos = parse_combos(hero_range)?;
+  if hero_combos.is_empty() {
+    return Err("hero range must include at least 1 hand".into());
+  }
+  let set = |range: &str| parse_combos(range).map(|combos| build_range_set(&combos));
+  let union = |a: &str, b: &str| -> Result<HashSet<(u32, u32)>, String> {
+    let mut out = set(a)?;
+    out.extend(set(b)?);
+    Ok(out)
+  };
+
+  let opener = match (action, opener) {
+    (PreflopAction::Open, _) if hero == Position::Bb => {
+      return Err("the big blind cannot open".into())
+    }
+    (PreflopAction::Open, _) => None,
+    (_, Some(o)) if o < hero => Some(o),
+    _ => return Err("a call or 3-bet needs an opener acting before hero".into()),
+  };
+  let hero_total = if action == PreflopAction::ThreeBet { three_bet } else { open };
+
+  let mut seats = Vec::new();
+  let mut dead = 0.0;
+  for position in Position::ALL {
+    let i = position.index();
+    let posted = position.posted(small_blind, big_blind);
+    if position == hero {
+      continue;
+    }
+    if Some(position) == opener {
+      let open_combos = parse_combos(&ranges.open[i])?;
+      if open_combos.is_empty() {
+        return Err("the opener's open range is empty".into());
+      }
+      let continues = if action == PreflopAction::ThreeBet {
+        let behind = union(&ranges.call[i], &ranges.three_bet[i])?;
+        build_range_set(&open_combos).intersection(&behind).copied().collect()
+      } else {
+        build_range_set(&open_combos)
+      };
+      seats.push(Seat {
+        position,
+        known: Some(open_combos),
+        continues,
+        current: open,
+        matched: hero_total,
+      });
+    } else if position > hero {
+      let continues = if action == PreflopAction::ThreeBet {
+        set(&ranges.three_bet[i])?
+      } else {
+        union(&ranges.call[i], &ranges.three_bet[i])?
+      };
+      seats.push(Seat {
+        position,
+        known: None,
+        continues,
+        current: posted,
+        matched: hero_total,
+      });
+    } else {
+      // folded before hero
+      dead += posted;
+    }
+  }
+
+  let hero_posted = hero.posted(small_blind, big_blind);
+  let pot_before = dead + hero_posted + seats.iter().map(|s| s.current).sum::<f64>();
+  let investment = hero_total - hero_posted;
+  let continue_sets: Vec<HashSet<(u32, u32)>> =
+    seats.iter().map(|s| s.continues.clone()).collect();
+
+  let mut rng = DefaultRng::new(seed);
+  let mut deals = 0u32;
+  let mut folds = 0u32;
+  let mut share_sum = 0.0;
+  let mut opponents_sum = 0u64;
+  let mut ev_sum = 0.0;
+  let mut continued = [0u32; 6];
+  let mut deck = Vec::with_capacity(2 + 2 * seats.len() + 5);
+  for _ in 0..trials.max(1) {
+    if !deal(&hero_combos, &seats, &mut deck, &mut rng) {
+      continue;
+    }
+    deals += 1;
+    let seated = seat_opponents(&deck, &continue_sets);
+    let board = &deck[deck.len() - 5..];
+
+    let mut pot = dead + hero_total;
+    let mut in_pot = Vec::with_capacity(seats.len());
+    for (seat, hand) in seats.iter().zip(&seated) {
+      match hand {
+        Some(hand) => {
+          pot += seat.matched;
+          continued[seat.position.index()] += 1;
+          in_pot.push(*hand);
+        }
+        None => pot += seat.current,
+      }
+    }
+    if in_pot.is_empty() {
+      folds += 1;
+      ev_sum += pot_before;
+      continue;
+    }
+    let share = showdown_share(&deck[..2], &in_pot, board);
+    share_sum += share;
+    opponents_sum += in_pot.len() as u64;
+    ev_sum += share * pot - investment;
+  }
+
+  let showdowns = deals - folds;
+  let per = |sum: f64, n: u32| if n == 0 { 0.0 } else { sum / n as f64 };
+  Ok(SixMaxEvaluation {
+    deals,
+    folds,
+    equity: per(share_sum, showdowns),
+    average_opponents: per(opponents_sum as f64, showdowns),
+    ev: per(ev_sum, deals),
+    pot_before,
+    investment,
+    continue_frequency: continued.map(|c| per(c as f64, deals)),
+  })
+}
+
+/// Deduplicated combos of `range`, sorted so a seed always draws the same hands
+/// (`RangeParser` returns them in hash order). An empty string is an empty range.
+fn parse_combos(range: &str) -> Result<Vec<[Card; 2]>, String> {
+  if range.trim().is_empty() {
+    return Ok(Vec::new());
+  }
+  let mut combos = parse_range_hands(range)?;
+  combos.sort_by_key(decode_hand_pair);
+  combos.dedup_by_key(|hand| decode_hand_pair(hand));
+  Ok(combos)
+}
+
+/// Lay out one deal in `deck`: hero's hand, each seat's hand, then the board. Known hands
+/// are drawn from their ranges and the rest from the remaining deck. Returns false when the
+/// known hands share a card (the deal is discarded, which keeps the draw uniform).
+fn deal<R: SimRng>(
+  hero_combos: &[[Card; 2]],
+  seats: &[Seat],
+  deck: &mut Vec<Card>,
+  rng: &mut R,
+) -> bool {
+  let mut known: Vec<Card> = Vec::with_capacity(2 + 2 * seats.len());
+  let hero = hero_combos[rng.below(hero_combos.len() as u32) as usize];
+  known.extend(hero);
+  let mut pinned = Vec::with_capacity(seats.len());
+  for seat in seats {
+    let hand = seat
+      .known
+      .as_ref()
+      .map(|combos| combos[rng.below(combos.len() as u32) as usize]);
+    if let Some(hand) = hand {
+      if hand.iter().any(|c| known.contains(c)) {
+        return false;
+      }
+      known.extend(hand);
+    }
+    pinned.push(hand);
+  }
+
+  let mut rest = build_deck(&known);
+  let random_seats = pinned.iter().filter(|h| h.is_none()).count();
+  let dealt = deal_prefix(&mut rest, 2 * random_seats + 5, rng);
+  let mut next = dealt.iter().copied();
+  deck.clear();
+  deck.extend(hero);
+  for hand in pinned {
+    match hand {
+      Some(hand) => deck.extend(hand),
+      None => deck.extend(next.by_ref().take(2)),
+    }
+  }
+  deck.extend(next);
+  true
+}
+
+/// Hero's share of the pot against `opponents` on a full board (ties split).
+fn showdown_share(hero: &[Card], opponents: &[[Card; 2]], board: &[Card]) -> f64 {
+  let score = |hand: &[Card]| {
+    let mut cards = [hand[0]; 7];
+    cards[1] = hand[1];
+    cards[2..].copy_from_slice(board);
+    best_of(&cards).encoded
+  };
+  let hero_score = score(hero);
+  let mut tied = 1;
+  for opp in opponents {
+    let s = score(opp);
+    if s > hero_score {
+      return 0.0;
+    }
+    if s == hero_score {
+      tied += 1;
+    }
+  }
+  1.0 / tied as f64
+}
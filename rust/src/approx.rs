@@ -0,0 +1,8 @@
+//! 浮動小数の額・重みを比べるときの許容誤差。
+//!
+//! チップ額（[`crate::game`]・[`crate::settlement`]）もレンジの重み
+//! （[`crate::range_update`]）も `f64` で持つので、足し引きの丸め誤差を
+//! 同じしきい値で吸収する。
+
+/// チップ額や重みの比較で許す誤差。
+pub const EPSILON: f64 = 1e-9;
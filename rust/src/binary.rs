@@ -0,0 +1,389 @@
+//! 結果のコンパクトなバイナリ表現（`u32` 配列、JS では `Uint32Array`）。
+//!
+//! `serde-wasm-bindgen` でコンボごとに JS オブジェクトとハンド文字列を作る代わりに、
+//! 数値だけを詰めた配列で返すためのフォーマット。`rust-wasm/src/sim/binary.rs` と
+//! 同じレイアウトで、フロントの `src/lib/wasm-v1/utils/binary-format.ts` で読む。
+//!
+//! # ヘッダ（[`HEADER_LEN`] 語）
+//!
+//! | 語 | 内容                                                      |
+//! | -- | --------------------------------------------------------- |
+//! | 0  | [`MAGIC`]（リトルエンディアンのバイト列で `"PPRB"`）      |
+//! | 1  | [`VERSION`]                                               |
+//! | 2  | 種類（[`KIND_RANGE_VS_RANGE`] / [`KIND_VS_LIST_WITH_RANKS`]） |
+//! | 3  | ヘッダ長（語数）。レコードはこの位置から始まる            |
+//! | 4  | 1 レコードの語数                                          |
+//! | 5  | レコード数                                                |
+//!
+//! 読む側はヘッダ長・レコード長を使って位置を決めること。後方互換な拡張
+//! （ヘッダやレコード末尾への語の追加）ではバージョンを上げない。
+//!
+//! # 値の表現
+//!
+//! - カード: `rank * 4 + suit`（rank 0 = `2` .. 12 = `A`、suit 0 = s, 1 = h, 2 = d, 3 = c）。
+//!   v1 (`rust_wasm_demo`) の出力と同じ番号。
+//! - equity: `floor((win + tie / 2) / count * 1_000_000)`（[`EQUITY_SCALE`] 倍の固定小数点）。
+//!
+//! # レコード
+//!
+//! [`KIND_RANGE_VS_RANGE`]（[`RANGE_VS_RANGE_RECORD_LEN`] 語）:
+//! `[card1, card2, side, equity]`。`side` は 0 = hero, 1 = villain。
+//!
+//! [`KIND_VS_LIST_WITH_RANKS`]（[`VS_LIST_WITH_RANKS_RECORD_LEN`] 語）:
+//! `[card1, card2, flags, count, win, tie, lose, equity, rank_win × 9, rank_tie × 9, rank_lose × 9]`。
+//! 各値は JSON 版の `CombinedEntry` の同名フィールドと同じ意味。`flags` の
+//! [`FLAG_HERO_AGGREGATE`] が立ったレコードが hero の合算（常に末尾）。
+//!
+//! レコードの並びは出力側の都合で決まる（equity 順とは限らない）。
+
+use rs_poker::core::{Card, Suit, Value};
+
+use crate::cards::pair_string;
+use crate::dto::CombinedEntry;
+use crate::parser::parse_two_cards;
+use crate::sim::range_vs_range::{ComboStats, RangeVsRangeAccumulator};
+use crate::sim::vs_list::VsListAccumulator;
+
+pub const MAGIC: u32 = u32::from_le_bytes(*b"PPRB");
+pub const VERSION: u32 = 1;
+pub const HEADER_LEN: usize = 6;
+
+pub const KIND_RANGE_VS_RANGE: u32 = 1;
+pub const KIND_VS_LIST_WITH_RANKS: u32 = 2;
+
+pub const RANGE_VS_RANGE_RECORD_LEN: usize = 4;
+pub const VS_LIST_WITH_RANKS_RECORD_LEN: usize = 35;
+
+pub const EQUITY_SCALE: u64 = 1_000_000;
+pub const FLAG_HERO_AGGREGATE: u32 = 1;
+
+/// 読み取ったヘッダ。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    pub kind: u32,
+    pub header_len: usize,
+    pub record_len: usize,
+    pub record_count: usize,
+}
+
+/// [`KIND_RANGE_VS_RANGE`] の 1 レコード。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeEquityRecord {
+    /// `"As Ks"` 形式。
+    pub hand: String,
+    pub villain: bool,
+    pub equity: u32,
+}
+
+/// [`KIND_VS_LIST_WITH_RANKS`] の 1 レコード。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CombinedRecord {
+    /// `"As Ks"` 形式。
+    pub hand: String,
+    pub hero_aggregate: bool,
+    pub count: u32,
+    pub win: u32,
+    pub tie: u32,
+    pub lose: u32,
+    pub equity: u32,
+    pub rank_win: [u32; 9],
+    pub rank_tie: [u32; 9],
+    pub rank_lose: [u32; 9],
+}
+
+/// range vs range の集計をエンコードする。hero のコンボ、続いて villain のコンボ（集計順）。
+pub fn encode_range_vs_range(acc: &RangeVsRangeAccumulator) -> Result<Vec<u32>, String> {
+    let count = acc.hero.len() + acc.villain.len();
+    let mut out = header(KIND_RANGE_VS_RANGE, RANGE_VS_RANGE_RECORD_LEN, count);
+    let sides = [
+        (0, &acc.hero_combos, &acc.hero),
+        (1, &acc.villain_combos, &acc.villain),
+    ];
+    for (side, combos, stats) in sides {
+        for (hand, s) in combos.iter().zip(stats.iter()) {
+            let (a, b) = parse_two_cards(hand)?;
+            out.extend_from_slice(&[card_code(a), card_code(b), side, combo_equity(s)]);
+        }
+    }
+    Ok(out)
+}
+
+/// hero vs list の集計をエンコードする。相手（入力順）、最後に hero の合算。
+pub fn encode_vs_list_with_ranks(acc: &VsListAccumulator) -> Result<Vec<u32>, String> {
+    let entries = acc.opponent_entries();
+    let mut out = header(
+        KIND_VS_LIST_WITH_RANKS,
+        VS_LIST_WITH_RANKS_RECORD_LEN,
+        entries.len() + 1,
+    );
+    for entry in &entries {
+        push_combined(&mut out, entry, 0)?;
+    }
+    push_combined(&mut out, &acc.hero_entry(), FLAG_HERO_AGGREGATE)?;
+    Ok(out)
+}
+
+/// ヘッダを検証して読み取る。配列長がレコード数に足りない場合もエラー。
+pub fn decode_header(words: &[u32]) -> Result<Header, String> {
+    if words.len() < HEADER_LEN {
+        return Err("binary result is shorter than the header".into());
+    }
+    if words[0] != MAGIC {
+        return Err("binary result has wrong magic".into());
+    }
+    if words[1] != VERSION {
+        return Err(format!("unsupported binary result version {}", words[1]));
+    }
+    let header = Header {
+        kind: words[2],
+        header_len: words[3] as usize,
+        record_len: words[4] as usize,
+        record_count: words[5] as usize,
+    };
+    let needed = header
+        .record_len
+        .checked_mul(header.record_count)
+        .and_then(|n| n.checked_add(header.header_len));
+    if header.header_len < HEADER_LEN || needed.is_none_or(|n| n > words.len()) {
+        return Err("binary result is truncated".into());
+    }
+    Ok(header)
+}
+
+pub fn decode_range_vs_range(words: &[u32]) -> Result<Vec<RangeEquityRecord>, String> {
+    records(words, KIND_RANGE_VS_RANGE, RANGE_VS_RANGE_RECORD_LEN)?
+        .map(|r| {
+            Ok(RangeEquityRecord {
+                hand: hand_from_codes(r[0], r[1])?,
+                villain: r[2] == 1,
+                equity: r[3],
+            })
+        })
+        .collect()
+}
+
+pub fn decode_vs_list_with_ranks(words: &[u32]) -> Result<Vec<CombinedRecord>, String> {
+    records(
+        words,
+        KIND_VS_LIST_WITH_RANKS,
+        VS_LIST_WITH_RANKS_RECORD_LEN,
+    )?
+    .map(|r| {
+        let counts = |start: usize| -> [u32; 9] { r[start..start + 9].try_into().unwrap() };
+        Ok(CombinedRecord {
+            hand: hand_from_codes(r[0], r[1])?,
+            hero_aggregate: r[2] & FLAG_HERO_AGGREGATE != 0,
+            count: r[3],
+            win: r[4],
+            tie: r[5],
+            lose: r[6],
+            equity: r[7],
+            rank_win: counts(8),
+            rank_tie: counts(17),
+            rank_lose: counts(26),
+        })
+    })
+    .collect()
+}
+
+fn header(kind: u32, record_len: usize, record_count: usize) -> Vec<u32> {
+    let mut out = Vec::with_capacity(HEADER_LEN + record_len * record_count);
+    out.extend_from_slice(&[
+        MAGIC,
+        VERSION,
+        kind,
+        HEADER_LEN as u32,
+        record_len as u32,
+        record_count as u32,
+    ]);
+    out
+}
+
+/// 種類を確認し、各レコードの先頭 `min_len` 語を返す（それ以降の語は将来の拡張分）。
+fn records(
+    words: &[u32],
+    kind: u32,
+    min_len: usize,
+) -> Result<impl Iterator<Item = &[u32]>, String> {
+    let h = decode_header(words)?;
+    if h.kind != kind {
+        return Err(format!(
+            "expected binary result kind {}, got {}",
+            kind, h.kind
+        ));
+    }
+    if h.record_len < min_len {
+        return Err("binary result records are too short".into());
+    }
+    Ok((0..h.record_count).map(move |i| {
+        let start = h.header_len + i * h.record_len;
+        &words[start..start + min_len]
+    }))
+}
+
+fn push_combined(out: &mut Vec<u32>, entry: &CombinedEntry, flags: u32) -> Result<(), String> {
+    let (a, b) = parse_two_cards(&entry.hand)?;
+    out.extend_from_slice(&[
+        card_code(a),
+        card_code(b),
+        flags,
+        entry.count,
+        entry.win,
+        entry.tie,
+        entry.lose,
+        fixed_equity(entry.win.into(), entry.tie.into(), entry.count.into()),
+    ]);
+    let outcomes = entry.results.by_index();
+    out.extend(outcomes.iter().map(|o| o.win));
+    out.extend(outcomes.iter().map(|o| o.tie));
+    out.extend(outcomes.iter().map(|o| o.lose));
+    Ok(())
+}
+
+#[inline]
+fn combo_equity(s: &ComboStats) -> u32 {
+    fixed_equity(s.wins, s.ties, s.plays)
+}
+
+/// `floor((wins + ties / 2) / plays * EQUITY_SCALE)` を整数演算で求める。
+fn fixed_equity(wins: u64, ties: u64, plays: u64) -> u32 {
+    if plays == 0 {
+        return 0;
+    }
+    let scaled = (u128::from(wins) * 2 + u128::from(ties)) * u128::from(EQUITY_SCALE)
+        / (u128::from(plays) * 2);
+    scaled.min(u128::from(EQUITY_SCALE)) as u32
+}
+
+/// カードをフォーマットの番号にする。rs_poker の `Suit` の並び（s, c, h, d）とは異なる点に注意。
+pub fn card_code(c: Card) -> u32 {
+    let suit = match c.suit {
+        Suit::Spade => 0,
+        Suit::Heart => 1,
+        Suit::Diamond => 2,
+        Suit::Club => 3,
+    };
+    (c.value as u32) * 4 + suit
+}
+
+/// [`card_code`] の逆変換。範囲外なら `None`。
+pub fn card_from_code(code: u32) -> Option<Card> {
+    if code >= 52 {
+        return None;
+    }
+    let suit = match code % 4 {
+        0 => Suit::Spade,
+        1 => Suit::Heart,
+        2 => Suit::Diamond,
+        _ => Suit::Club,
+    };
+    Some(Card {
+        value: Value::from_u8((code / 4) as u8),
+        suit,
+    })
+}
+
+fn hand_from_codes(a: u32, b: u32) -> Result<String, String> {
+    match (card_from_code(a), card_from_code(b)) {
+        (Some(a), Some(b)) => Ok(pair_string(a, b)),
+        _ => Err(format!("invalid card codes {} {}", a, b)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::full_deck;
+    use crate::sim::{range_vs_range, vs_list};
+
+    #[test]
+    fn card_codes_round_trip_and_match_v1_numbering() {
+        for card in full_deck() {
+            assert_eq!(card_from_code(card_code(card)), Some(card));
+        }
+        // v1: As = 12 * 4 + 0, 2c = 0 * 4 + 3
+        let (ace, deuce) = parse_two_cards("As2c").unwrap();
+        assert_eq!(card_code(ace), 48);
+        assert_eq!(card_code(deuce), 3);
+    }
+
+    #[test]
+    fn range_vs_range_round_trips_through_decoder() {
+        let acc = range_vs_range::accumulate("QQ+,AKs", "JJ-99", "2h7c9d", 30, 7).unwrap();
+        let words = encode_range_vs_range(&acc).unwrap();
+
+        let header = decode_header(&words).unwrap();
+        assert_eq!(header.kind, KIND_RANGE_VS_RANGE);
+        assert_eq!(header.record_count, acc.hero.len() + acc.villain.len());
+        assert_eq!(
+            words.len(),
+            HEADER_LEN + header.record_count * RANGE_VS_RANGE_RECORD_LEN
+        );
+
+        let records = decode_range_vs_range(&words).unwrap();
+        let (hero, villain): (Vec<_>, Vec<_>) = records.iter().partition(|r| !r.villain);
+        assert_eq!(
+            hero.iter().map(|r| r.hand.clone()).collect::<Vec<_>>(),
+            acc.hero_combos
+        );
+        assert_eq!(
+            villain.iter().map(|r| r.hand.clone()).collect::<Vec<_>>(),
+            acc.villain_combos
+        );
+        for (record, stats) in hero.iter().zip(&acc.hero) {
+            let expected = (stats.equity() * EQUITY_SCALE as f64).floor() as i64;
+            assert!((i64::from(record.equity) - expected).abs() <= 1);
+        }
+    }
+
+    #[test]
+    fn vs_list_round_trips_through_decoder() {
+        let acc = vs_list::accumulate("AhKh", "2h7c9d", "QsQd; JcTc; 7s7d", 500, 3).unwrap();
+        let words = encode_vs_list_with_ranks(&acc).unwrap();
+        let records = decode_vs_list_with_ranks(&words).unwrap();
+
+        let mut expected = acc.opponent_entries();
+        expected.push(acc.hero_entry());
+        assert_eq!(records.len(), expected.len());
+        for (i, (record, entry)) in records.iter().zip(&expected).enumerate() {
+            assert_eq!(record.hero_aggregate, i == expected.len() - 1);
+            assert_eq!(record.hand, entry.hand);
+            assert_eq!(
+                (record.count, record.win, record.tie, record.lose),
+                (entry.count, entry.win, entry.tie, entry.lose)
+            );
+            for (r, outcome) in entry.results.by_index().iter().enumerate() {
+                assert_eq!(record.rank_win[r], outcome.win);
+                assert_eq!(record.rank_tie[r], outcome.tie);
+                assert_eq!(record.rank_lose[r], outcome.lose);
+            }
+        }
+    }
+
+    #[test]
+    fn decoder_skips_extra_words_and_rejects_bad_input() {
+        let acc = vs_list::accumulate("AhKh", "", "QsQd", 10, 1).unwrap();
+        let words = encode_vs_list_with_ranks(&acc).unwrap();
+
+        // 後方互換な拡張（ヘッダ末尾・レコード末尾の追加語）は読み飛ばせる。
+        let mut extended = words[..HEADER_LEN].to_vec();
+        extended[3] = HEADER_LEN as u32 + 1;
+        extended[4] = VS_LIST_WITH_RANKS_RECORD_LEN as u32 + 1;
+        extended.push(0xDEAD);
+        for record in words[HEADER_LEN..].chunks(VS_LIST_WITH_RANKS_RECORD_LEN) {
+            extended.extend_from_slice(record);
+            extended.push(0xBEEF);
+        }
+        assert_eq!(
+            decode_vs_list_with_ranks(&extended).unwrap(),
+            decode_vs_list_with_ranks(&words).unwrap()
+        );
+
+        assert!(decode_header(&words[..HEADER_LEN - 1]).is_err());
+        assert!(decode_header(&words[..words.len() - 1]).is_err());
+        assert!(decode_range_vs_range(&words).is_err());
+        let mut wrong_version = words.clone();
+        wrong_version[1] = VERSION + 1;
+        assert!(decode_header(&wrong_version).is_err());
+    }
+}
@@ -0,0 +1,506 @@
+//! シミュレーション結果のキャッシュ。
+//!
+//! 同じ hero / ボード / レンジでの再計算を避けるため、入力を正規化したキーで
+//! [`RangeVsRangeAccumulator`] / [`VsListAccumulator`] を保持する。
+//!
+//! - コンボ・ボード・相手リストは並び順に依存しないよう整列する
+//! - スートの同型（`AhKh` on `2h7c9d` と `AsKs` on `2s7c9d`）を同じキーにするため、
+//!   24 通りのスート置換のうちキーが辞書順最小になるものへ写してから計算・保存する
+//!
+//! キャッシュ済みの試行数が要求より少なければ、不足分だけ別シードで追加計算して
+//! `merge` で合算する。シードはキーに含めないので、2 回目以降の呼び出しは
+//! 指定シードに関係なくキャッシュ済みの集計を使う。
+//!
+//! 状態は [`ResultCache::to_bytes`] / [`ResultCache::load_bytes`] でバイト列にでき、
+//! JS 側で IndexedDB などに保存できる。
+
+use std::cmp::Reverse;
+
+use rs_poker::core::Card;
+use serde::{Deserialize, Serialize};
+
+use crate::cards::{
+    all_perms, card_index, cards_string, combo_key, invert, map_card, pair_string, SuitPerm,
+};
+use crate::dto::{CombinedPayload, RangeVsRangePayload};
+use crate::parser::{parse_cards, parse_hands_list, parse_range, parse_two_cards};
+use crate::rng::derive_seed;
+use crate::sim::range_vs_range::{self, RangeVsRangeAccumulator};
+use crate::sim::vs_list::{self, VsListAccumulator};
+
+/// 既定の最大エントリ数。
+pub const DEFAULT_CAPACITY: usize = 64;
+
+/// [`ResultCache::to_bytes`] の先頭 1 バイト。エンコードを変えたら上げる。
+const FORMAT_VERSION: u8 = 1;
+
+/// キーの先頭 1 バイト。シミュレーションの種類を区別する。
+const KIND_RANGE_VS_RANGE: u8 = 1;
+const KIND_VS_LIST: u8 = 2;
+
+/// キー内でカード列を区切るバイト（カード番号は 0..52）。
+const SEPARATOR: u8 = 0xFF;
+
+#[derive(Clone, Serialize, Deserialize)]
+enum CachedResult {
+    RangeVsRange(RangeVsRangeAccumulator),
+    VsList(VsListAccumulator),
+}
+
+impl CachedResult {
+    fn merge(&mut self, other: &CachedResult) -> Result<(), String> {
+        match (self, other) {
+            (Self::RangeVsRange(a), Self::RangeVsRange(b)) => a.merge(b),
+            (Self::VsList(a), Self::VsList(b)) => a.merge(b),
+            _ => Err("cache entry kind mismatch".into()),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Entry {
+    /// 正規化済み入力のバイト列。
+    key: Vec<u8>,
+    /// 合算済みの試行数（各シミュレーションの `trials` と同じ単位）。
+    trials: u32,
+    /// 合算した実行回数。追加計算のサブシード導出に使う。
+    runs: u32,
+    /// 正規化したスートのままの集計。
+    result: CachedResult,
+}
+
+/// 正規化済み入力をキーにした LRU キャッシュ。
+pub struct ResultCache {
+    capacity: usize,
+    /// 古い順。参照したエントリは末尾へ移す。
+    entries: Vec<Entry>,
+}
+
+impl Default for ResultCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl ResultCache {
+    /// `capacity` 件まで保持するキャッシュを作る。0 は 1 として扱う。
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// キャッシュを通した [`range_vs_range::run`]。
+    pub fn range_vs_range(
+        &mut self,
+        hero_range: &str,
+        villain_range: &str,
+        board: &str,
+        trials: u32,
+        seed: u64,
+    ) -> Result<RangeVsRangePayload, String> {
+        let hero = parse_range(hero_range)?;
+        let villain = parse_range(villain_range)?;
+        let board_cards = parse_cards(board)?;
+        if board_cards.len() > 5 {
+            return Err("board must be <=5 cards".into());
+        }
+
+        let (perm, key) =
+            canonical_perm(|perm| range_vs_range_key(perm, &board_cards, &hero, &villain));
+        let canon_board = canonical_cards(&perm, &board_cards);
+        let canon_hero = canonical_combos(&perm, &hero);
+        let canon_villain = canonical_combos(&perm, &villain);
+
+        let entry = self.lookup_or_run(key, trials.max(1), seed, |run_trials, run_seed| {
+            range_vs_range::accumulate_combos(
+                canon_hero.clone(),
+                canon_villain.clone(),
+                canon_board.clone(),
+                run_trials,
+                run_seed,
+            )
+            .map(CachedResult::RangeVsRange)
+        })?;
+        let CachedResult::RangeVsRange(acc) = &entry.result else {
+            return Err("cache entry kind mismatch".into());
+        };
+
+        // スートを入力側に戻す。ボードは入力の並びのまま返す。
+        let inverse = invert(&perm);
+        let restore = |hands: &[String]| -> Result<Vec<String>, String> {
+            hands.iter().map(|h| restore_hand(&inverse, h)).collect()
+        };
+        let restored = RangeVsRangeAccumulator {
+            board: cards_string(&board_cards),
+            hero_combos: restore(&acc.hero_combos)?,
+            hero: acc.hero.clone(),
+            villain_combos: restore(&acc.villain_combos)?,
+            villain: acc.villain.clone(),
+        };
+        Ok(restored.to_payload())
+    }
+
+    /// キャッシュを通した [`vs_list::run`]。
+    pub fn vs_list(
+        &mut self,
+        hero: &str,
+        board: &str,
+        compare: &str,
+        trials: u32,
+        seed: u64,
+    ) -> Result<CombinedPayload, String> {
+        let hero_pair = parse_two_cards(hero)?;
+        let board_cards = parse_cards(board)?;
+        let opponents = parse_hands_list(compare)?;
+        if opponents.is_empty() {
+            return Err("No compare hands provided".into());
+        }
+
+        let (perm, key) = canonical_perm(|perm| {
+            let (canon_opps, _) = canonical_opponents(perm, &opponents);
+            let mut key = vec![KIND_VS_LIST];
+            push_cards(&mut key, &canonical_cards(perm, &board_cards));
+            push_combos(&mut key, &[map_combo(perm, hero_pair)]);
+            push_combos(&mut key, &canon_opps);
+            key
+        });
+        let canon_board = canonical_cards(&perm, &board_cards);
+        let canon_hero = map_combo(&perm, hero_pair);
+        let (canon_opps, order) = canonical_opponents(&perm, &opponents);
+
+        let entry = self.lookup_or_run(key, trials.max(1), seed, |run_trials, run_seed| {
+            Ok(CachedResult::VsList(vs_list::accumulate_cards(
+                canon_hero,
+                &canon_board,
+                &canon_opps,
+                run_trials,
+                run_seed,
+            )))
+        })?;
+        let CachedResult::VsList(acc) = &entry.result else {
+            return Err("cache entry kind mismatch".into());
+        };
+
+        // 相手を入力順・入力のスートに戻す。
+        let mut restored = VsListAccumulator {
+            hero: pair_string(hero_pair.0, hero_pair.1),
+            board: cards_string(&board_cards),
+            opponents: vec![Default::default(); opponents.len()],
+        };
+        for (stats, &original) in acc.opponents.iter().zip(&order) {
+            let (a, b) = opponents[original];
+            restored.opponents[original] = vs_list::OpponentStats {
+                hand: pair_string(a, b),
+                ..stats.clone()
+            };
+        }
+        Ok(restored.to_payload())
+    }
+
+    /// キャッシュの状態をバイト列にする。先頭 1 バイトはフォーマットのバージョン。
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = vec![FORMAT_VERSION];
+        out.extend(
+            postcard::to_allocvec(&self.entries).expect("cache entries are always serializable"),
+        );
+        out
+    }
+
+    /// [`to_bytes`](Self::to_bytes) の出力で状態を置き換える。
+    ///
+    /// `capacity` を超える分は古いエントリから捨てる。失敗した場合は何も変更しない。
+    pub fn load_bytes(&mut self, bytes: &[u8]) -> Result<(), String> {
+        let Some((&version, body)) = bytes.split_first() else {
+            return Err("cache bytes are empty".into());
+        };
+        if version != FORMAT_VERSION {
+            return Err(format!("unsupported cache format version {}", version));
+        }
+        let mut entries: Vec<Entry> =
+            postcard::from_bytes(body).map_err(|e| format!("cache decode error: {}", e))?;
+        let excess = entries.len().saturating_sub(self.capacity);
+        entries.drain(..excess);
+        self.entries = entries;
+        Ok(())
+    }
+
+    /// `key` のエントリを返す。なければ `simulate(trials, seed)` で作り、
+    /// 試行数が足りなければ不足分を `derive_seed(seed, runs)` で追加計算して合算する。
+    fn lookup_or_run<F>(
+        &mut self,
+        key: Vec<u8>,
+        trials: u32,
+        seed: u64,
+        simulate: F,
+    ) -> Result<&Entry, String>
+    where
+        F: Fn(u32, u64) -> Result<CachedResult, String>,
+    {
+        match self.entries.iter().position(|e| e.key == key) {
+            Some(pos) => {
+                let entry = &mut self.entries[pos];
+                if entry.trials < trials {
+                    let extra = simulate(
+                        trials - entry.trials,
+                        derive_seed(seed, u64::from(entry.runs)),
+                    )?;
+                    entry.result.merge(&extra)?;
+                    entry.trials = trials;
+                    entry.runs = entry.runs.saturating_add(1);
+                }
+                let entry = self.entries.remove(pos);
+                self.entries.push(entry);
+            }
+            None => {
+                let result = simulate(trials, seed)?;
+                if self.entries.len() >= self.capacity {
+                    self.entries.remove(0);
+                }
+                self.entries.push(Entry {
+                    key,
+                    trials,
+                    runs: 1,
+                    result,
+                });
+            }
+        }
+        Ok(self.entries.last().expect("entry was just pushed"))
+    }
+}
+
+/// 24 通りのスート置換のうち、`key_of` が辞書順最小になるものとそのキーを返す。
+fn canonical_perm<K: Fn(&SuitPerm) -> Vec<u8>>(key_of: K) -> (SuitPerm, Vec<u8>) {
+    all_perms()
+        .into_iter()
+        .map(|perm| (perm, key_of(&perm)))
+        .min_by(|(_, a), (_, b)| a.cmp(b))
+        .expect("there are 24 suit permutations")
+}
+
+/// range vs range のキー。置換後のボード・hero・villain を並べる。
+fn range_vs_range_key(
+    perm: &SuitPerm,
+    board: &[Card],
+    hero: &[(Card, Card)],
+    villain: &[(Card, Card)],
+) -> Vec<u8> {
+    let mut key = vec![KIND_RANGE_VS_RANGE];
+    push_cards(&mut key, &canonical_cards(perm, board));
+    push_combos(&mut key, &canonical_combos(perm, hero));
+    push_combos(&mut key, &canonical_combos(perm, villain));
+    key
+}
+
+#[inline]
+fn map_combo(perm: &SuitPerm, (a, b): (Card, Card)) -> (Card, Card) {
+    let (a, b) = (map_card(perm, a), map_card(perm, b));
+    if card_index(a) >= card_index(b) {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// ボードを置換して強い順に並べる（ボードの並びは結果に影響しない）。
+fn canonical_cards(perm: &SuitPerm, cards: &[Card]) -> Vec<Card> {
+    let mut out: Vec<Card> = cards.iter().map(|&c| map_card(perm, c)).collect();
+    out.sort_unstable_by_key(|&c| Reverse(card_index(c)));
+    out
+}
+
+/// レンジを置換し、[`parse_range`] と同じ順に並べる。
+fn canonical_combos(perm: &SuitPerm, combos: &[(Card, Card)]) -> Vec<(Card, Card)> {
+    let mut out: Vec<(Card, Card)> = combos.iter().map(|&c| map_combo(perm, c)).collect();
+    out.sort_unstable_by_key(|&(a, b)| Reverse(combo_key(a, b)));
+    out
+}
+
+/// 相手リストを置換して並べ替え、並べ替え後の各位置が元の何番目かも返す。
+fn canonical_opponents(
+    perm: &SuitPerm,
+    opponents: &[(Card, Card)],
+) -> (Vec<(Card, Card)>, Vec<usize>) {
+    let mapped: Vec<(Card, Card)> = opponents.iter().map(|&c| map_combo(perm, c)).collect();
+    let mut order: Vec<usize> = (0..mapped.len()).collect();
+    order.sort_by_key(|&i| Reverse(combo_key(mapped[i].0, mapped[i].1)));
+    (order.iter().map(|&i| mapped[i]).collect(), order)
+}
+
+fn push_cards(key: &mut Vec<u8>, cards: &[Card]) {
+    key.extend(cards.iter().map(|&c| card_index(c)));
+    key.push(SEPARATOR);
+}
+
+fn push_combos(key: &mut Vec<u8>, combos: &[(Card, Card)]) {
+    for &(a, b) in combos {
+        key.push(card_index(a));
+        key.push(card_index(b));
+    }
+    key.push(SEPARATOR);
+}
+
+/// `"As Ks"` 形式のハンドのスートを置換し直す。
+///
+/// ポケットペアは [`pair_string`] が引数の順のまま出すので、[`parse_range`] が返すコンボと
+/// 同じくカード番号の小さい方を先にして、直接計算した結果とラベルを揃える。
+fn restore_hand(inverse: &SuitPerm, hand: &str) -> Result<String, String> {
+    let (a, b) = parse_two_cards(hand)?;
+    let (a, b) = (map_card(inverse, a), map_card(inverse, b));
+    Ok(if card_index(a) <= card_index(b) {
+        pair_string(a, b)
+    } else {
+        pair_string(b, a)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(entries: &[crate::dto::RangeEquityEntry]) -> Vec<String> {
+        let mut out: Vec<String> = entries.iter().map(|e| e.hand.clone()).collect();
+        out.sort();
+        out
+    }
+
+    #[test]
+    fn suit_isomorphic_inputs_share_one_entry() {
+        let mut cache = ResultCache::new(4);
+        let hearts = cache
+            .range_vs_range("AhKh", "QQ", "2h 7c 9d", 2, 1)
+            .unwrap();
+        let spades = cache
+            .range_vs_range("AsKs", "QQ", "2s 7c 9d", 2, 1)
+            .unwrap();
+        assert_eq!(cache.len(), 1);
+        assert_eq!(labels(&hearts.hero), ["Ah Kh"]);
+        assert_eq!(labels(&spades.hero), ["As Ks"]);
+        assert_eq!(hearts.hero[0].equity, spades.hero[0].equity);
+
+        let hearts = cache.vs_list("Ah Kh", "2h 7c 9d", "Qh Qd", 2, 1).unwrap();
+        let spades = cache.vs_list("As Ks", "2s 7c 9d", "Qs Qd", 2, 1).unwrap();
+        assert_eq!(cache.len(), 2);
+        assert_eq!(hearts.data[0].hand, "Qh Qd");
+        assert_eq!(spades.data[0].hand, "Qs Qd");
+    }
+
+    #[test]
+    fn cached_labels_match_the_direct_path() {
+        let mut cache = ResultCache::new(4);
+        for (hero, villain, board) in [
+            ("AsKs,QQ", "JJ,AdQc", "2s 7d 9c"),
+            ("22+,AKo", "KQs,TT", "Ah 8d 3c"),
+        ] {
+            let cached = cache.range_vs_range(hero, villain, board, 1, 3).unwrap();
+            let direct = range_vs_range::run(hero, villain, board, 1, 3).unwrap();
+            assert_eq!(labels(&cached.hero), labels(&direct.hero), "{hero}");
+            assert_eq!(
+                labels(&cached.villain),
+                labels(&direct.villain),
+                "{villain}"
+            );
+        }
+    }
+
+    #[test]
+    fn evicts_the_least_recently_used_entry_at_capacity() {
+        let mut cache = ResultCache::new(2);
+        cache.range_vs_range("AA", "KK", "", 1, 1).unwrap();
+        cache.range_vs_range("QQ", "KK", "", 1, 1).unwrap();
+        // AA を参照し直すので、次に追い出されるのは QQ
+        cache.range_vs_range("AA", "KK", "", 1, 1).unwrap();
+        cache.range_vs_range("JJ", "KK", "", 1, 1).unwrap();
+        assert_eq!(cache.len(), 2);
+
+        let keys: Vec<Vec<u8>> = cache.entries.iter().map(|e| e.key.clone()).collect();
+        let key_of = |hero: &str| {
+            let hero = parse_range(hero).unwrap();
+            let villain = parse_range("KK").unwrap();
+            canonical_perm(|perm| range_vs_range_key(perm, &[], &hero, &villain)).1
+        };
+        assert_eq!(keys, [key_of("AA"), key_of("JJ")]);
+    }
+
+    #[test]
+    fn tops_up_with_a_derived_seed_when_more_trials_are_asked() {
+        let (hero, villain, board) = ("AhKh,QQ", "JJ,AQo", "2h 7c 9d");
+        let mut cache = ResultCache::new(4);
+        cache.range_vs_range(hero, villain, board, 2, 10).unwrap();
+        cache.range_vs_range(hero, villain, board, 5, 20).unwrap();
+        // 足りていれば追加計算しない
+        cache.range_vs_range(hero, villain, board, 3, 30).unwrap();
+
+        let entry = &cache.entries[0];
+        assert_eq!((entry.trials, entry.runs), (5, 2));
+
+        let (hero, villain) = (parse_range(hero).unwrap(), parse_range(villain).unwrap());
+        let board = parse_cards(board).unwrap();
+        let (perm, _) = canonical_perm(|perm| range_vs_range_key(perm, &board, &hero, &villain));
+        let run = |trials, seed| {
+            range_vs_range::accumulate_combos(
+                canonical_combos(&perm, &hero),
+                canonical_combos(&perm, &villain),
+                canonical_cards(&perm, &board),
+                trials,
+                seed,
+            )
+            .unwrap()
+        };
+        let mut expected = run(2, 10);
+        expected.merge(&run(3, derive_seed(20, 1))).unwrap();
+        let CachedResult::RangeVsRange(acc) = &entry.result else {
+            panic!("expected a range vs range entry");
+        };
+        assert_eq!(acc, &expected);
+    }
+
+    #[test]
+    fn zero_trials_are_treated_as_one() {
+        let mut cache = ResultCache::new(4);
+        cache.vs_list("As Ks", "2s 7c 9d", "Qs Qd", 0, 1).unwrap();
+        cache.range_vs_range("AA", "KK", "", 0, 1).unwrap();
+        assert!(cache.entries.iter().all(|e| e.trials == 1));
+        let CachedResult::VsList(acc) = &cache.entries[0].result else {
+            panic!("expected a vs list entry");
+        };
+        assert_eq!(acc.opponents[0].plays, 1);
+    }
+
+    #[test]
+    fn bytes_round_trip_and_reject_other_versions() {
+        let mut cache = ResultCache::new(4);
+        cache.range_vs_range("AA", "KK", "2h 7c 9d", 2, 1).unwrap();
+        cache
+            .vs_list("As Ks", "2s 7c 9d", "Qs Qd; Jh Jd", 2, 1)
+            .unwrap();
+        let bytes = cache.to_bytes();
+        assert_eq!(bytes[0], FORMAT_VERSION);
+
+        let mut loaded = ResultCache::new(4);
+        loaded.load_bytes(&bytes).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded.to_bytes(), bytes);
+
+        let mut other = bytes.clone();
+        other[0] = FORMAT_VERSION + 1;
+        assert!(loaded.load_bytes(&other).is_err());
+        assert!(loaded.load_bytes(&[]).is_err());
+        assert_eq!(loaded.to_bytes(), bytes);
+
+        // capacity を超える分は古い方から捨てる
+        let mut small = ResultCache::new(1);
+        small.load_bytes(&bytes).unwrap();
+        assert_eq!(small.len(), 1);
+        assert_eq!(small.entries[0].key, cache.entries[1].key);
+    }
+}
@@ -57,6 +57,11 @@ pub fn card_to_str(c: &Card) -> String {
     s
 }
 
+/// カード列を `"2h 7c 9d"` のような空白区切りにする（並びは入力順のまま）。
+pub fn cards_string(cards: &[Card]) -> String {
+    cards.iter().map(card_to_str).collect::<Vec<_>>().join(" ")
+}
+
 /// 2 枚のカードを `"As Ks"` 形式に整形する（強い方を先頭に）。
 ///
 /// v1 互換のため、JS 側で `hand.split(" ")` してマッチングできるよう
@@ -84,7 +89,44 @@ pub fn combo_key(a: Card, b: Card) -> (u8, u8) {
     }
 }
 
+/// カードを 0..52 の番号にする（`value * 4 + suit`）。
 #[inline]
-fn card_index(c: Card) -> u8 {
+pub fn card_index(c: Card) -> u8 {
     (c.value as u8) * 4 + (c.suit as u8)
 }
+
+/// スート置換。`perm[suit as usize]` が写した先のスート番号。
+///
+/// スートの入れ替えで結果が変わらない入力（同型）を同一視するのに使う。
+pub type SuitPerm = [u8; 4];
+
+/// 4 スートの置換 24 通り。
+pub fn all_perms() -> Vec<SuitPerm> {
+    let mut out = Vec::with_capacity(24);
+    for a in 0..4u8 {
+        for b in (0..4u8).filter(|&b| b != a) {
+            for c in (0..4u8).filter(|&c| c != a && c != b) {
+                let d = 6 - a - b - c;
+                out.push([a, b, c, d]);
+            }
+        }
+    }
+    out
+}
+
+/// 逆置換。正規化したスートを元のスートへ戻すのに使う。
+pub fn invert(perm: &SuitPerm) -> SuitPerm {
+    let mut inv = [0u8; 4];
+    for (from, &to) in perm.iter().enumerate() {
+        inv[to as usize] = from as u8;
+    }
+    inv
+}
+
+#[inline]
+pub fn map_card(perm: &SuitPerm, c: Card) -> Card {
+    Card {
+        value: c.value,
+        suit: Suit::from_u8(perm[c.suit as usize]),
+    }
+}
@@ -0,0 +1,159 @@
+//! 後から検証できるデッキの配り（provably fair）。
+//!
+//! 1. 配る側は server seed を決め、そのハッシュ（[`commitment`]）を先に公開する
+//! 2. プレイヤー側が client seed を決める。ハンドごとに `nonce` を変える
+//! 3. デッキの順番は 3 つの値だけで決まる（[`shuffled_deck`]）。配ったカードはこの順番の先頭から
+//! 4. 後で server seed を明かせば、誰でもハッシュと配ったカードを照合できる（[`verify`]）
+//!
+//! server seed を先に固定し、client seed を後から混ぜるので、どちらか一方だけでは
+//! デッキを選べない。デッキの順番そのもののハッシュ（`deck_hash`）もハンドごとの約束に使える。
+//!
+//! 順番はこのクレートのバージョンや rand の実装に依存しないよう、次の手順で固定している。
+//!
+//! - シード = SHA-256(`len(server) ‖ server ‖ len(client) ‖ client ‖ nonce`)。長さと nonce は u64 LE
+//! - 乱数はそのシードの ChaCha20。[`crate::cards::full_deck`] の並びから、i = 51..1 について
+//!   `j` を `0..=i` から（`next_u32` の棄却法で）一様に選んで i と入れ替える
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use rs_poker::core::Card;
+use sha2::{Digest, Sha256};
+
+use crate::cards::{card_to_str, full_deck};
+
+/// server seed のハッシュ（SHA-256 の 16 進）。配る前に公開する。
+pub fn commitment(server_seed: &str) -> String {
+    to_hex(&Sha256::digest(server_seed.as_bytes()))
+}
+
+/// 32 バイトの乱数から server seed（16 進 64 文字）を作る。
+pub fn generate_server_seed() -> Result<String, String> {
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes).map_err(|e| format!("failed to get random bytes: {}", e))?;
+    Ok(to_hex(&bytes))
+}
+
+/// 3 つの値から決まる 52 枚の順番。先頭から配る。
+pub fn shuffled_deck(server_seed: &str, client_seed: &str, nonce: u64) -> Vec<Card> {
+    let mut hasher = Sha256::new();
+    for part in [server_seed, client_seed] {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part.as_bytes());
+    }
+    hasher.update(nonce.to_le_bytes());
+    let mut rng = ChaCha20Rng::from_seed(hasher.finalize().into());
+
+    let mut deck = full_deck();
+    for i in (1..deck.len()).rev() {
+        let j = uniform_below(&mut rng, i as u32 + 1) as usize;
+        deck.swap(i, j);
+    }
+    deck
+}
+
+/// デッキの順番のハッシュ（`"As"` 形式を並べた文字列の SHA-256 の 16 進）。
+pub fn deck_hash(deck: &[Card]) -> String {
+    let order: String = deck.iter().map(card_to_str).collect();
+    to_hex(&Sha256::digest(order.as_bytes()))
+}
+
+/// [`verify`] の結果。
+#[derive(Clone, Debug, PartialEq)]
+pub struct Verification {
+    /// `server_seed` のハッシュが公開していた値と一致したか。
+    pub seed_matches: bool,
+    /// 配ったカードが先頭から順番どおりでなくなった最初の位置。全部一致なら `None`。
+    pub mismatch_at: Option<usize>,
+    /// seeds から作り直したデッキ。
+    pub deck: Vec<Card>,
+}
+
+impl Verification {
+    pub fn is_valid(&self) -> bool {
+        self.seed_matches && self.mismatch_at.is_none()
+    }
+}
+
+/// 明かされた server seed が約束どおりで、`dealt`（配った順）がデッキの先頭と一致するか。
+pub fn verify(
+    commitment_hex: &str,
+    server_seed: &str,
+    client_seed: &str,
+    nonce: u64,
+    dealt: &[Card],
+) -> Verification {
+    let deck = shuffled_deck(server_seed, client_seed, nonce);
+    let mismatch_at = (0..dealt.len()).find(|&i| deck.get(i) != Some(&dealt[i]));
+    Verification {
+        seed_matches: commitment(server_seed).eq_ignore_ascii_case(commitment_hex.trim()),
+        mismatch_at,
+        deck,
+    }
+}
+
+/// `0..bound` の一様な整数。`bound` の倍数に収まらない値は捨てて引き直す。
+fn uniform_below(rng: &mut impl Rng, bound: u32) -> u32 {
+    let zone = u32::MAX - (u32::MAX - bound + 1) % bound;
+    loop {
+        let x = rng.next_u32();
+        if x <= zone {
+            return x % bound;
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deck_is_a_fixed_permutation_of_the_seeds() {
+        assert_eq!(
+            commitment("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        let a = shuffled_deck("server", "client", 1);
+        assert_eq!(a, shuffled_deck("server", "client", 1));
+        assert_ne!(a, shuffled_deck("server", "client", 2));
+        assert_ne!(a, shuffled_deck("server", "client2", 1));
+        // 長さを前に付けるので、境界をずらしても同じシードにならない
+        assert_ne!(a, shuffled_deck("serverc", "lient", 1));
+
+        let mut sorted = a.clone();
+        sorted.sort();
+        let mut full = full_deck();
+        full.sort();
+        assert_eq!(sorted, full);
+        assert_eq!(deck_hash(&a).len(), 64);
+
+        let seed = generate_server_seed().unwrap();
+        assert_eq!(seed.len(), 64);
+    }
+
+    #[test]
+    fn verifies_dealt_cards_against_the_commitment() {
+        let server = "7f3c";
+        let promised = commitment(server);
+        let deck = shuffled_deck(server, "player", 42);
+
+        let ok = verify(&promised, server, "player", 42, &deck[..9]);
+        assert!(ok.is_valid());
+        assert_eq!(ok.deck, deck);
+
+        // 4 枚目を差し替えた
+        let mut dealt = deck[..9].to_vec();
+        dealt[3] = deck[20];
+        let bad = verify(&promised, server, "player", 42, &dealt);
+        assert_eq!(bad.mismatch_at, Some(3));
+        assert!(!bad.is_valid());
+
+        // 約束と違う server seed
+        let other = verify(&promised, "7f3d", "player", 42, &[]);
+        assert!(!other.seed_matches);
+        assert!(!verify(&promised, server, "player", 43, &deck[..2]).is_valid());
+    }
+}
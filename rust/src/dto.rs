@@ -6,7 +6,9 @@
 
 use serde::Serialize;
 
+use crate::game::Round;
 use crate::rank::RankBuckets;
+use crate::settlement::PotAward;
 
 // ─────────────────────────────────────────────
 // 役判定の結果
@@ -20,9 +22,519 @@ pub struct HandRankingEntry {
     #[serde(rename = "rankName")]
     pub rank_name: String,
     pub encoded: u32,
-    /// v1 互換のため残しているが、rs_poker の `Rank` は内部値に
-    /// キッカーをエンコード済みなので、ここでは空配列を返す。
+    /// 役を作る 5 枚（`"Ks"` 形式）。役の札が先、キッカーが後。
+    #[serde(rename = "bestFive")]
+    pub best_five: Vec<String>,
+    /// 勝敗に効くキッカーのランク（0 = 2 .. 12 = A）。強い順。
     pub kickers: Vec<u32>,
+    /// `"Two Pair, Kings and Fives, Queen kicker"` のような説明文。
+    pub description: String,
+}
+
+// ─────────────────────────────────────────────
+// ショーダウン比較の結果
+// ─────────────────────────────────────────────
+
+#[derive(Serialize)]
+pub struct ShowdownComparison {
+    /// 0 = first の勝ち、1 = second の勝ち、2 = スプリット。
+    pub winner: u32,
+    pub first: HandRankingEntry,
+    pub second: HandRankingEntry,
+    /// 決め手。`"higher category"` / `"second kicker Q vs J"` / `"board plays - split"` など。
+    pub reason: String,
+}
+
+// ─────────────────────────────────────────────
+// ナッツ順位の結果
+// ─────────────────────────────────────────────
+
+/// 同じ `Rank` になるコンボの組（同値クラス）。
+#[derive(Serialize)]
+pub struct NutClass {
+    /// 1 = ナッツ、2 = 2nd ナッツ…。
+    pub position: usize,
+    #[serde(rename = "rankIndex")]
+    pub rank_index: usize,
+    #[serde(rename = "rankName")]
+    pub rank_name: String,
+    pub description: String,
+    pub combos: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct HeroNutRank {
+    pub hand: String,
+    /// hero が属するクラスの [`NutClass::position`]。
+    pub position: usize,
+    /// hero のカードとかぶらないコンボのうち、hero が勝つ / 引き分け / 負ける数。
+    pub beats: usize,
+    pub ties: usize,
+    pub loses: usize,
+    /// `beats / (beats + ties + loses)`。「87% のコンボに勝つ」の 0.87。
+    pub percentile: f64,
+}
+
+#[derive(Serialize)]
+pub struct NutRankingPayload {
+    /// 強い順。
+    pub classes: Vec<NutClass>,
+    /// ボード以外の全 2 枚組の数（5 / 4 / 3 枚ボードで 1081 / 1128 / 1176）。
+    #[serde(rename = "totalCombos")]
+    pub total_combos: usize,
+    /// hero を渡したときだけ入る。
+    pub hero: Option<HeroNutRank>,
+}
+
+// ─────────────────────────────────────────────
+// hand strength / ポテンシャルの結果
+// ─────────────────────────────────────────────
+
+#[derive(Serialize)]
+pub struct HandStrengthPayload {
+    pub hand: String,
+    pub hs: f64,
+    pub ppot: f64,
+    pub npot: f64,
+    pub ehs: f64,
+    /// ランアウトごとのリバー HS の二乗の平均（E[HS²]）。
+    #[serde(rename = "ehsSquared")]
+    pub ehs_squared: f64,
+    /// dead カードを除いた相手コンボ数。
+    #[serde(rename = "villainCombos")]
+    pub villain_combos: usize,
+    /// 列挙したランアウト数（リバーなら 1）。
+    pub runouts: usize,
+}
+
+// ─────────────────────────────────────────────
+// ストリートごとの equity 推移
+// ─────────────────────────────────────────────
+
+#[derive(Serialize)]
+pub struct StreetEquity {
+    /// `"preflop"` / `"flop"` / `"turn"` / `"river"`。
+    pub street: String,
+    /// そのストリートで見えているボード。
+    pub board: Vec<String>,
+    pub equity: f64,
+    /// 前のストリートからの変化（プリフロップは 0）。
+    pub delta: f64,
+    /// false ならプリフロップの Monte Carlo。
+    pub exact: bool,
+}
+
+#[derive(Serialize)]
+pub struct CardEquity {
+    pub card: String,
+    pub equity: f64,
+    /// 1 つ前のストリートの equity からの変化。
+    pub delta: f64,
+}
+
+#[derive(Serialize)]
+pub struct EquityPathPayload {
+    pub hand: String,
+    /// 使ったボード 5 枚（指定分 + 引いた分）。
+    pub runout: Vec<String>,
+    pub streets: Vec<StreetEquity>,
+    /// フロップに続く各ターンカードの equity。高い順（先頭がベスト、末尾がワースト）。
+    #[serde(rename = "turnCards")]
+    pub turn_cards: Vec<CardEquity>,
+    /// ランアウトのターンに続く各リバーカードの equity。高い順。
+    #[serde(rename = "riverCards")]
+    pub river_cards: Vec<CardEquity>,
+}
+
+// ─────────────────────────────────────────────
+// 次の 1 枚ごとの equity
+// ─────────────────────────────────────────────
+
+/// 今の equity から [`crate::sim::next_card::SWING_THRESHOLD`] 以上動くかで分けたカード。
+#[derive(Serialize, Default)]
+pub struct NextCardBuckets {
+    pub good: Vec<String>,
+    pub neutral: Vec<String>,
+    pub bad: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct NextCardPayload {
+    pub hand: String,
+    pub board: Vec<String>,
+    /// 配られるストリート（`"turn"` / `"river"`）。
+    pub street: String,
+    /// 今のボードでの equity。
+    pub equity: f64,
+    #[serde(rename = "villainCombos")]
+    pub villain_combos: usize,
+    /// 52 要素。添字はバイナリ形式と同じカード番号（`rank * 4 + suit`、s/h/d/c）。
+    /// 来ないカードは null。
+    pub grid: Vec<Option<f64>>,
+    /// 来うるカードごとの equity（デッキ順）。
+    pub cards: Vec<CardEquity>,
+    pub buckets: NextCardBuckets,
+}
+
+// ─────────────────────────────────────────────
+// ハンドクラス（169 種）とフロップの同型
+// ─────────────────────────────────────────────
+
+/// 13×13 グリッドの 1 セル。並びと添字は [`crate::hand_class`] を参照。
+#[derive(Serialize)]
+pub struct HandClassCell {
+    /// `"AKs"` / `"AKo"` / `"QQ"`。
+    pub class: String,
+    /// `row * 13 + col`。
+    pub index: usize,
+    pub row: usize,
+    pub col: usize,
+    /// 使用済みカードがないときのコンボ数（6 / 4 / 12）。
+    pub combos: usize,
+    /// 使用済みカードを除いたコンボ数。
+    #[serde(rename = "liveCombos")]
+    pub live_combos: usize,
+}
+
+#[derive(Serialize)]
+pub struct CanonicalFlopPayload {
+    /// 同型なフロップの代表。カード番号の降順。
+    pub board: Vec<String>,
+    /// 代表と同型なフロップの数（1 / 4 / 6 / 12 / 24）。
+    pub isomorphs: usize,
+}
+
+// ─────────────────────────────────────────────
+// 全フロップでの range vs range
+// ─────────────────────────────────────────────
+
+#[derive(Serialize)]
+pub struct FlopEquityEntry {
+    /// 同型なフロップの代表。カード番号の降順。
+    pub board: Vec<String>,
+    /// 代表と同型なフロップの数。全フロップで合計 22100。
+    pub weight: usize,
+    /// `"two-tone unpaired connected"` のようなテクスチャ。
+    pub texture: String,
+    /// hero レンジの equity。ボードとの衝突で対戦がなければ null。
+    #[serde(rename = "heroEquity")]
+    pub hero_equity: Option<f64>,
+}
+
+/// テクスチャやハイカードでまとめたフロップ。
+#[derive(Serialize)]
+pub struct FlopGroup {
+    pub label: String,
+    /// 代表フロップの数。
+    pub flops: usize,
+    pub weight: usize,
+    /// 各フロップの equity を `weight` で重み付けした平均。
+    #[serde(rename = "heroEquity")]
+    pub hero_equity: f64,
+}
+
+#[derive(Serialize)]
+pub struct FlopReportPayload {
+    /// 全フロップでの hero レンジの equity（`weight` の重み付き平均）。
+    #[serde(rename = "heroEquity")]
+    pub hero_equity: f64,
+    /// 対戦があったフロップの `weight` の合計。
+    pub weight: usize,
+    /// 代表フロップごとの結果（デッキ順）。
+    pub flops: Vec<FlopEquityEntry>,
+    /// テクスチャ別。hero の equity が高い順。
+    pub textures: Vec<FlopGroup>,
+    /// ハイカード別（`"A-high"` 等）。hero の equity が高い順。
+    #[serde(rename = "highCards")]
+    pub high_cards: Vec<FlopGroup>,
+}
+
+// ─────────────────────────────────────────────
+// ゲームエンジンのスナップショット
+// ─────────────────────────────────────────────
+
+/// `src/features/game/holdem.ts` の `RoundData` と同じ shape。
+#[derive(Serialize)]
+pub struct RoundDataSnapshot {
+    #[serde(rename = "startingPlayerActive")]
+    pub starting_player_active: Vec<bool>,
+    #[serde(rename = "needsAction")]
+    pub needs_action: Vec<bool>,
+    #[serde(rename = "minRaise")]
+    pub min_raise: f64,
+    pub bet: f64,
+    #[serde(rename = "playerBet")]
+    pub player_bet: Vec<f64>,
+    #[serde(rename = "totalBetCount")]
+    pub total_bet_count: u32,
+    #[serde(rename = "totalRaiseCount")]
+    pub total_raise_count: u32,
+    #[serde(rename = "toActIdx")]
+    pub to_act_idx: usize,
+}
+
+/// 手番のプレイヤーが取れるアクション。
+#[derive(Serialize)]
+pub struct LegalActionsSnapshot {
+    pub player: usize,
+    #[serde(rename = "canCheck")]
+    pub can_check: bool,
+    #[serde(rename = "callAmount")]
+    pub call_amount: f64,
+    /// ベット / レイズできないときは null。
+    #[serde(rename = "minRaiseTo")]
+    pub min_raise_to: Option<f64>,
+    #[serde(rename = "maxRaiseTo")]
+    pub max_raise_to: Option<f64>,
+}
+
+#[derive(Serialize)]
+pub struct PotSnapshot {
+    pub amount: f64,
+    pub eligible: Vec<usize>,
+    pub winners: Vec<usize>,
+    /// `winners` と同じ並びの取り分。
+    pub shares: Vec<f64>,
+}
+
+impl From<&PotAward> for PotSnapshot {
+    fn from(p: &PotAward) -> Self {
+        Self {
+            amount: p.amount,
+            eligible: p.eligible.clone(),
+            winners: p.winners.clone(),
+            shares: p.shares.clone(),
+        }
+    }
+}
+
+/// `HoldemGameState` と同じ shape に、手番の合法手と精算結果を足したもの。
+/// カードは `"As"` 形式の文字列。
+#[derive(Serialize)]
+pub struct GameSnapshot {
+    #[serde(rename = "numPlayers")]
+    pub num_players: usize,
+    #[serde(rename = "playerActive")]
+    pub player_active: Vec<bool>,
+    #[serde(rename = "playerAllIn")]
+    pub player_all_in: Vec<bool>,
+    #[serde(rename = "totalPot")]
+    pub total_pot: f64,
+    pub stacks: Vec<f64>,
+    #[serde(rename = "startingStacks")]
+    pub starting_stacks: Vec<f64>,
+    #[serde(rename = "playerBet")]
+    pub player_bet: Vec<f64>,
+    #[serde(rename = "playerWinnings")]
+    pub player_winnings: Vec<f64>,
+    #[serde(rename = "bigBlind")]
+    pub big_blind: f64,
+    #[serde(rename = "smallBlind")]
+    pub small_blind: f64,
+    pub ante: f64,
+    pub hands: Vec<[String; 2]>,
+    #[serde(rename = "dealerIdx")]
+    pub dealer_idx: usize,
+    pub round: Round,
+    #[serde(rename = "roundBefore")]
+    pub round_before: Round,
+    #[serde(rename = "roundData")]
+    pub round_data: RoundDataSnapshot,
+    pub board: Vec<String>,
+    #[serde(rename = "bbPosted")]
+    pub bb_posted: bool,
+    #[serde(rename = "sbPosted")]
+    pub sb_posted: bool,
+    /// ベッティングラウンド以外では null。
+    pub legal: Option<LegalActionsSnapshot>,
+    /// 精算したポット（`Complete` になるまで空）。先頭がメインポット。
+    pub pots: Vec<PotSnapshot>,
+    /// ショーダウンした各プレイヤーの役の説明。しなかったプレイヤーは null。
+    pub showdown: Vec<Option<String>>,
+}
+
+// ─────────────────────────────────────────────
+// サイドポットの精算
+// ─────────────────────────────────────────────
+
+#[derive(Serialize)]
+pub struct SettlementPayload {
+    /// 先頭がメインポット。
+    pub pots: Vec<PotSnapshot>,
+    /// 各プレイヤーが受け取る額。
+    pub winnings: Vec<f64>,
+    /// 受け取る額 − 出した額。
+    pub net: Vec<f64>,
+    /// ショーダウンした各プレイヤーの役の説明。しなかったプレイヤーは null。
+    pub showdown: Vec<Option<String>>,
+}
+
+// ─────────────────────────────────────────────
+// マルチウェイのオールイン EV
+// ─────────────────────────────────────────────
+
+#[derive(Serialize)]
+pub struct AllInEvPlayer {
+    /// 入力したハンドかレンジ。フォールドした席は空文字。
+    pub hand: String,
+    pub folded: bool,
+    pub contribution: f64,
+    /// 受け取るチップの期待値。
+    #[serde(rename = "expectedReturn")]
+    pub expected_return: f64,
+    /// `expectedReturn − contribution`。
+    #[serde(rename = "netEv")]
+    pub net_ev: f64,
+}
+
+#[derive(Serialize)]
+pub struct AllInEvPot {
+    pub amount: f64,
+    pub eligible: Vec<usize>,
+    /// `eligible` と同じ並びの、このポットの取り分の期待値（割合）。
+    pub equity: Vec<f64>,
+}
+
+#[derive(Serialize)]
+pub struct AllInEvPayload {
+    pub players: Vec<AllInEvPlayer>,
+    /// 先頭がメインポット。
+    pub pots: Vec<AllInEvPot>,
+    /// 数えたランアウト（配り）の数。
+    pub samples: u64,
+    /// 全列挙した厳密値か（false なら Monte Carlo）。
+    pub exact: bool,
+}
+
+// ─────────────────────────────────────────────
+// 検証できるデッキの配り
+// ─────────────────────────────────────────────
+
+#[derive(Serialize)]
+pub struct ShuffledDeckPayload {
+    /// 配る順の 52 枚。
+    pub cards: Vec<String>,
+    /// 順番の SHA-256（16 進）。ハンドごとの約束として先に公開できる。
+    #[serde(rename = "deckHash")]
+    pub deck_hash: String,
+}
+
+#[derive(Serialize)]
+pub struct DealVerificationPayload {
+    /// seed が約束どおりで、配ったカードが全部デッキの順番どおり。
+    pub valid: bool,
+    #[serde(rename = "seedMatches")]
+    pub seed_matches: bool,
+    /// 順番どおりでなくなった最初の位置。全部一致なら null。
+    #[serde(rename = "mismatchAt")]
+    pub mismatch_at: Option<usize>,
+    /// seeds から作り直したデッキ。
+    pub deck: Vec<String>,
+    #[serde(rename = "deckHash")]
+    pub deck_hash: String,
+}
+
+// ─────────────────────────────────────────────
+// アクション列によるレンジの絞り込み
+// ─────────────────────────────────────────────
+
+#[derive(Serialize)]
+pub struct WeightedHandEntry {
+    pub hand: String,
+    #[serde(rename = "handClass")]
+    pub hand_class: String,
+    /// そのストリートのボードに対する強さ。プリフロップは null。
+    pub strength: Option<String>,
+    pub weight: f64,
+}
+
+#[derive(Serialize)]
+pub struct ClassWeight {
+    #[serde(rename = "handClass")]
+    pub hand_class: String,
+    pub weight: f64,
+}
+
+#[derive(Serialize)]
+pub struct StrengthWeight {
+    pub strength: String,
+    pub weight: f64,
+}
+
+#[derive(Serialize)]
+pub struct WeightedRangeSnapshot {
+    /// 重みの合計。確率にするときはこれで割る。
+    #[serde(rename = "totalWeight")]
+    pub total_weight: f64,
+    /// 重みが 0 のコンボは含まない。
+    pub combos: Vec<WeightedHandEntry>,
+    /// ハンドクラスごとの重み（グリッドの並び）。
+    pub classes: Vec<ClassWeight>,
+    /// 強さごとの重み（強い順）。プリフロップは空。
+    pub strengths: Vec<StrengthWeight>,
+}
+
+#[derive(Serialize)]
+pub struct RangeUpdateStepPayload {
+    pub street: String,
+    pub action: String,
+    /// 直前のレンジがこのアクションを選ぶ確率。
+    #[serde(rename = "actionProbability")]
+    pub action_probability: f64,
+    pub range: WeightedRangeSnapshot,
+}
+
+#[derive(Serialize)]
+pub struct RangeUpdatePayload {
+    /// ボードとかぶるコンボを除いた開始レンジ。
+    pub initial: WeightedRangeSnapshot,
+    pub steps: Vec<RangeUpdateStepPayload>,
+}
+
+// ─────────────────────────────────────────────
+// BB ディフェンスと MDF
+// ─────────────────────────────────────────────
+
+#[derive(Serialize)]
+pub struct BbDefenseClass {
+    #[serde(rename = "handClass")]
+    pub hand_class: String,
+    /// 候補レンジに入っているこのクラスのコンボ数。
+    pub combos: usize,
+    /// オープンレンジに対する equity（コンボの対戦数で重み付け）。
+    pub equity: f64,
+    /// `equity × realization`。
+    #[serde(rename = "realizedEquity")]
+    pub realized_equity: f64,
+    /// フォールドと比べたコールの期待値（BB 単位）。
+    #[serde(rename = "callEv")]
+    pub call_ev: f64,
+    /// `"call"` / `"fold"`。
+    pub action: String,
+}
+
+#[derive(Serialize)]
+pub struct BbDefensePayload {
+    #[serde(rename = "callAmount")]
+    pub call_amount: f64,
+    /// コールした後のポット。
+    #[serde(rename = "finalPot")]
+    pub final_pot: f64,
+    /// コールに必要な equity（実現率なし）。
+    #[serde(rename = "potOdds")]
+    pub pot_odds: f64,
+    /// 実現率を考えた生の equity の必要ライン（`potOdds / realization`）。
+    #[serde(rename = "requiredEquity")]
+    pub required_equity: f64,
+    pub mdf: f64,
+    /// 全 1326 コンボのうちコールと判定した割合。
+    #[serde(rename = "defendFrequency")]
+    pub defend_frequency: f64,
+    /// 全 1326 コンボのうち候補レンジの割合。
+    #[serde(rename = "candidateFrequency")]
+    pub candidate_frequency: f64,
+    /// 候補レンジに入っているクラス（グリッドの並び）。
+    pub classes: Vec<BbDefenseClass>,
 }
 
 // ─────────────────────────────────────────────
@@ -83,6 +595,23 @@ impl From<&RankBuckets> for RankOutcomeResults {
     }
 }
 
+impl RankOutcomeResults {
+    /// カテゴリ番号（[`crate::rank::rank_index`]）順に並べた参照。
+    pub fn by_index(&self) -> [&RankOutcome; 9] {
+        [
+            &self.high_card,
+            &self.one_pair,
+            &self.two_pair,
+            &self.three_of_a_kind,
+            &self.straight,
+            &self.flush,
+            &self.full_house,
+            &self.four_of_a_kind,
+            &self.straight_flush,
+        ]
+    }
+}
+
 #[derive(Serialize)]
 pub struct CombinedEntry {
     pub hand: String,
@@ -128,3 +657,25 @@ pub struct RangeVsRangePayload {
     pub hero: Vec<RangeEquityEntry>,
     pub villain: Vec<RangeEquityEntry>,
 }
+
+/// range vs range のハンドクラス 1 つ分。レンジにないセルは `combos == 0` で equity 系は null。
+#[derive(Serialize)]
+pub struct RangeGridCell {
+    /// `"AKs"` / `"AKo"` / `"QQ"`。
+    pub class: String,
+    /// ボードとかぶるコンボを除いた、レンジ内のコンボ数。
+    pub combos: usize,
+    /// クラス内の wins/ties/plays を合算した equity（コンボの対戦数で重み付けした平均）。
+    pub equity: Option<f64>,
+    /// コンボ単位の equity の最小・最大。
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+/// [`RangeVsRangePayload`] をハンドクラスごとにまとめたもの。
+/// どちらも 169 要素で、添字はグリッドの並び（[`crate::hand_class`]）。
+#[derive(Serialize)]
+pub struct RangeVsRangeGridPayload {
+    pub hero: Vec<RangeGridCell>,
+    pub villain: Vec<RangeGridCell>,
+}
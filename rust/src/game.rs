@@ -0,0 +1,800 @@
+//! ノーリミット・ホールデムの 1 ハンドを進めるゲームエンジン。
+//!
+//! 状態の持ち方（[`Round`] / [`RoundData`] / [`GameState`] のフィールド）は rs_poker の
+//! `arena::game_state` に合わせている。arena は tokio の `rt-multi-thread` に依存していて
+//! wasm32 ではビルドできない（エラーは README のゲームエンジンの節）ため、同じ形をこのクレートで持ち、
+//! ルールは次のように扱う。
+//!
+//! - `player_active` はフォールドしていないプレイヤー（arena と違い、オールインでも残る）
+//! - コール額に届かないベット、最小レイズ未満のレイズ、スタックを超える額はエラー。
+//!   ただしオールインならコール額・最小レイズに届かなくてもよい
+//! - 最小レイズに満たないオールイン（アンダーレイズ）はアクションを再オープンしない。
+//!   すでに行動したプレイヤーは、前回の行動時から最小レイズ以上 bet が上がっていなければ
+//!   コールかフォールドしかできない（短いオールインが重なって 1 レイズ分になれば再オープン）
+//! - BB がショートでも、コール額は BB 満額
+//! - ベッティングラウンドが終わると自動で次のストリートを配る。行動できるプレイヤーが
+//!   1 人以下ならリバーまで配ってショーダウンし、サイドポットに分けて精算する
+//!
+//! デッキはシードからシャッフルするので、同じシード・同じアクション列なら同じ結果になる。
+//! チップは f64（JS の number）で、比較には [`EPSILON`] の誤差を許す。
+
+use rand::seq::SliceRandom;
+use rs_poker::core::Card;
+use serde::Serialize;
+
+use crate::approx::EPSILON;
+use crate::cards::{card_to_str, full_deck};
+use crate::dto::{GameSnapshot, LegalActionsSnapshot, PotSnapshot, RoundDataSnapshot};
+use crate::rng::seeded_rng;
+use crate::settlement::{self, OddChipRule, PotAward, Seat};
+
+pub const MAX_PLAYERS: usize = 9;
+
+/// ハンドの進行段階。名前は rs_poker の `Round` と同じ。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize)]
+pub enum Round {
+    #[default]
+    Starting,
+    Ante,
+    DealPreflop,
+    Preflop,
+    DealFlop,
+    Flop,
+    DealTurn,
+    Turn,
+    DealRiver,
+    River,
+    Showdown,
+    Complete,
+}
+
+impl Round {
+    pub fn advance(self) -> Round {
+        match self {
+            Round::Starting => Round::Ante,
+            Round::Ante => Round::DealPreflop,
+            Round::DealPreflop => Round::Preflop,
+            Round::Preflop => Round::DealFlop,
+            Round::DealFlop => Round::Flop,
+            Round::Flop => Round::DealTurn,
+            Round::DealTurn => Round::Turn,
+            Round::Turn => Round::DealRiver,
+            Round::DealRiver => Round::River,
+            Round::River => Round::Showdown,
+            Round::Showdown | Round::Complete => Round::Complete,
+        }
+    }
+
+    /// ベッティングが行われるラウンドか。
+    pub fn is_betting(self) -> bool {
+        matches!(
+            self,
+            Round::Preflop | Round::Flop | Round::Turn | Round::River
+        )
+    }
+}
+
+/// 1 ベッティングラウンド内の状態。
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundData {
+    /// ラウンド開始時にハンドに残っていたプレイヤー。
+    pub starting_player_active: Vec<bool>,
+    /// このラウンドでまだ行動が必要なプレイヤー。
+    pub needs_action: Vec<bool>,
+    /// 最小レイズ幅（直近のフルレイズの幅。最初は BB）。
+    pub min_raise: f64,
+    /// コールすべき額（このラウンドの最大ベット）。
+    pub bet: f64,
+    /// このラウンドで各プレイヤーが出した額。
+    pub player_bet: Vec<f64>,
+    pub total_bet_count: u32,
+    pub total_raise_count: u32,
+    pub to_act_idx: usize,
+    /// 各プレイヤーがこのラウンドで最後に行動したときの `bet`。未行動は `None`。
+    /// アンダーレイズで再オープンされたかの判定に使う。
+    pub acted_at: Vec<Option<f64>>,
+}
+
+impl RoundData {
+    fn new(starting_player_active: Vec<bool>, needs_action: Vec<bool>, min_raise: f64) -> Self {
+        let n = needs_action.len();
+        RoundData {
+            starting_player_active,
+            needs_action,
+            min_raise,
+            bet: 0.0,
+            player_bet: vec![0.0; n],
+            total_bet_count: 0,
+            total_raise_count: 0,
+            to_act_idx: 0,
+            acted_at: vec![None; n],
+        }
+    }
+
+    /// `idx` がレイズできる状態か（未行動か、前回の行動から 1 レイズ分以上上がっている）。
+    pub fn is_reopened_for(&self, idx: usize) -> bool {
+        self.acted_at[idx].is_none_or(|at| self.bet - at >= self.min_raise - EPSILON)
+    }
+}
+
+/// プレイヤーのアクション。額を取るのは [`Action::RaiseTo`] だけで、追加するチップ量ではなく
+/// ラウンド内の合計ベット額を指定する。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Action {
+    Fold,
+    Check,
+    Call,
+    /// このラウンドの合計ベット額が `amount` になるようにベット / レイズする。
+    RaiseTo(f64),
+    AllIn,
+}
+
+/// 手番のプレイヤーが取れるアクション。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LegalActions {
+    pub player: usize,
+    pub can_check: bool,
+    /// コールに必要な額（スタックが足りなければ残り全部）。チェックできるときは 0。
+    pub call_amount: f64,
+    /// ベット / レイズできるときの最小・最大の合計額。最大はオールイン。
+    pub min_raise_to: Option<f64>,
+    pub max_raise_to: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameConfig {
+    pub stacks: Vec<f64>,
+    pub small_blind: f64,
+    pub big_blind: f64,
+    pub ante: f64,
+    pub dealer_idx: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct GameState {
+    pub num_players: usize,
+    /// フォールドしていないプレイヤー。
+    pub player_active: Vec<bool>,
+    pub player_all_in: Vec<bool>,
+    pub total_pot: f64,
+    pub stacks: Vec<f64>,
+    pub starting_stacks: Vec<f64>,
+    /// ハンド全体で各プレイヤーがポットに出した額（アンティ込み）。
+    pub player_bet: Vec<f64>,
+    pub player_winnings: Vec<f64>,
+    pub big_blind: f64,
+    pub small_blind: f64,
+    pub ante: f64,
+    pub hands: Vec<[Card; 2]>,
+    pub dealer_idx: usize,
+    pub round: Round,
+    pub round_before: Round,
+    pub round_data: RoundData,
+    pub board: Vec<Card>,
+    pub bb_posted: bool,
+    pub sb_posted: bool,
+    /// 精算結果。`Complete` になるまでは空。
+    pub pots: Vec<PotAward>,
+    /// ショーダウンした各プレイヤーの役の説明。
+    pub showdown: Vec<Option<String>>,
+    /// 残りのデッキ。末尾から配る。
+    deck: Vec<Card>,
+}
+
+impl GameState {
+    /// シャッフルしたデッキから配り、アンティとブラインドを払ってプリフロップの最初の手番まで進める。
+    pub fn new(config: &GameConfig, seed: u64) -> Result<Self, String> {
+        let n = config.stacks.len();
+        if !(2..=MAX_PLAYERS).contains(&n) {
+            return Err(format!("expected 2 to {} players, got {}", MAX_PLAYERS, n));
+        }
+        if config.stacks.iter().any(|s| !s.is_finite() || *s <= 0.0) {
+            return Err("stacks must be positive".to_string());
+        }
+        let blinds_ok = config.big_blind.is_finite()
+            && config.big_blind > 0.0
+            && (0.0..=config.big_blind).contains(&config.small_blind)
+            && config.ante.is_finite()
+            && config.ante >= 0.0;
+        if !blinds_ok {
+            return Err(
+                "expected 0 <= small blind <= big blind, big blind > 0 and ante >= 0".to_string(),
+            );
+        }
+        if config.dealer_idx >= n {
+            return Err(format!(
+                "dealer index {} is out of range for {} players",
+                config.dealer_idx, n
+            ));
+        }
+
+        let mut deck = full_deck();
+        deck.shuffle(&mut seeded_rng(seed));
+        let mut state = GameState {
+            num_players: n,
+            player_active: vec![true; n],
+            player_all_in: vec![false; n],
+            total_pot: 0.0,
+            stacks: config.stacks.clone(),
+            starting_stacks: config.stacks.clone(),
+            player_bet: vec![0.0; n],
+            player_winnings: vec![0.0; n],
+            big_blind: config.big_blind,
+            small_blind: config.small_blind,
+            ante: config.ante,
+            hands: Vec::with_capacity(n),
+            dealer_idx: config.dealer_idx,
+            round: Round::Starting,
+            round_before: Round::Starting,
+            round_data: RoundData::new(vec![true; n], vec![false; n], config.big_blind),
+            board: Vec::with_capacity(5),
+            bb_posted: false,
+            sb_posted: false,
+            pots: Vec::new(),
+            showdown: vec![None; n],
+            deck,
+        };
+        state.start_hand();
+        Ok(state)
+    }
+
+    fn start_hand(&mut self) {
+        self.advance_round(); // Ante
+        if self.ante > 0.0 {
+            for i in 0..self.num_players {
+                self.post(i, self.ante, false);
+            }
+        }
+        self.advance_round(); // DealPreflop
+        for _ in 0..self.num_players {
+            let hand = [self.draw(), self.draw()];
+            self.hands.push(hand);
+        }
+        self.advance_round(); // Preflop
+        self.start_betting_round();
+
+        // ヘッズアップはディーラーが SB
+        let sb = if self.num_players == 2 {
+            self.dealer_idx
+        } else {
+            self.next_seat(self.dealer_idx)
+        };
+        let bb = self.next_seat(sb);
+        self.post(sb, self.small_blind, true);
+        self.sb_posted = true;
+        self.post(bb, self.big_blind, true);
+        self.bb_posted = true;
+        // 実際に払えた額にかかわらず、コール額は BB 満額
+        self.round_data.bet = self.big_blind;
+        self.round_data.needs_action = self.can_act_mask();
+        self.next_turn(bb);
+    }
+
+    /// 手番のプレイヤーのアクションを検証して適用し、必要ならストリートを進める。
+    pub fn act(&mut self, action: Action) -> Result<(), String> {
+        let legal = self
+            .legal_actions()
+            .ok_or_else(|| format!("no player to act in round {:?}", self.round))?;
+        let idx = legal.player;
+        match action {
+            Action::Fold => {
+                self.player_active[idx] = false;
+                self.round_data.needs_action[idx] = false;
+            }
+            Action::Check => {
+                if !legal.can_check {
+                    return Err(format!(
+                        "cannot check while facing a bet of {}",
+                        self.round_data.bet
+                    ));
+                }
+                self.mark_acted(idx);
+            }
+            Action::Call => {
+                if legal.can_check {
+                    return Err("nothing to call; check instead".to_string());
+                }
+                self.put_in(idx, legal.call_amount);
+            }
+            Action::RaiseTo(amount) => {
+                self.validate_raise(&legal, amount)?;
+                let extra = (amount - self.round_data.player_bet[idx]).min(self.stacks[idx]);
+                self.put_in(idx, extra);
+            }
+            Action::AllIn => {
+                let amount = self.round_data.player_bet[idx] + self.stacks[idx];
+                if amount > self.round_data.bet + EPSILON {
+                    self.validate_raise(&legal, amount)?;
+                }
+                self.put_in(idx, self.stacks[idx]);
+            }
+        }
+        self.next_turn(idx);
+        Ok(())
+    }
+
+    /// 手番のプレイヤーが取れるアクション。ベッティングラウンド以外では `None`。
+    pub fn legal_actions(&self) -> Option<LegalActions> {
+        if !self.round.is_betting() {
+            return None;
+        }
+        let rd = &self.round_data;
+        let idx = rd.to_act_idx;
+        let to_call = (rd.bet - rd.player_bet[idx]).max(0.0);
+        let can_check = to_call <= EPSILON;
+        let max_to = rd.player_bet[idx] + self.stacks[idx];
+        let others_can_act = (0..self.num_players)
+            .any(|i| i != idx && self.player_active[i] && !self.player_all_in[i]);
+        let can_raise = max_to > rd.bet + EPSILON && rd.is_reopened_for(idx) && others_can_act;
+        Some(LegalActions {
+            player: idx,
+            can_check,
+            call_amount: if can_check {
+                0.0
+            } else {
+                to_call.min(self.stacks[idx])
+            },
+            min_raise_to: can_raise.then(|| (rd.bet + rd.min_raise).min(max_to)),
+            max_raise_to: can_raise.then_some(max_to),
+        })
+    }
+
+    fn validate_raise(&self, legal: &LegalActions, amount: f64) -> Result<(), String> {
+        let (Some(min), Some(max)) = (legal.min_raise_to, legal.max_raise_to) else {
+            return Err(if !self.round_data.is_reopened_for(legal.player) {
+                "betting was not reopened by the all-in under-raise; call or fold".to_string()
+            } else {
+                "cannot raise: no opponent can respond".to_string()
+            });
+        };
+        if !amount.is_finite() {
+            return Err(format!("invalid bet amount {}", amount));
+        }
+        if amount > max + EPSILON {
+            return Err(format!(
+                "raise to {} exceeds the stack (all-in is {})",
+                amount, max
+            ));
+        }
+        // オールインなら最小レイズ未満でもよい
+        if amount < min - EPSILON && max - amount > EPSILON {
+            return Err(format!(
+                "raise to {} is below the minimum raise to {}",
+                amount, min
+            ));
+        }
+        Ok(())
+    }
+
+    /// `idx` が `extra` を出す（コール / ベット / レイズ）。
+    fn put_in(&mut self, idx: usize, extra: f64) {
+        self.post(idx, extra, true);
+        let rd = &mut self.round_data;
+        let prev = rd.bet;
+        let now = rd.player_bet[idx];
+        if now > prev + EPSILON {
+            let raise = now - prev;
+            // フルレイズだけが最小レイズ幅を更新する
+            if raise >= rd.min_raise - EPSILON {
+                rd.min_raise = raise;
+            }
+            rd.bet = now;
+            rd.total_raise_count += 1;
+            for i in 0..self.num_players {
+                rd.needs_action[i] = i != idx && self.player_active[i] && !self.player_all_in[i];
+            }
+        }
+        self.mark_acted(idx);
+    }
+
+    fn mark_acted(&mut self, idx: usize) {
+        let rd = &mut self.round_data;
+        rd.needs_action[idx] = false;
+        rd.acted_at[idx] = Some(rd.bet);
+    }
+
+    /// スタックから `amount`（足りなければ残り全部）をポットへ移す。
+    /// `in_round` が false（アンティ）のときはラウンドのベットに数えない。
+    fn post(&mut self, idx: usize, amount: f64, in_round: bool) {
+        let paid = amount.min(self.stacks[idx]);
+        self.stacks[idx] -= paid;
+        self.player_bet[idx] += paid;
+        self.total_pot += paid;
+        if in_round {
+            self.round_data.player_bet[idx] += paid;
+            if paid > 0.0 {
+                self.round_data.total_bet_count += 1;
+            }
+        }
+        if self.stacks[idx] <= EPSILON {
+            self.stacks[idx] = 0.0;
+            self.player_all_in[idx] = true;
+        }
+    }
+
+    /// `from` の行動後、次の手番を決める。ラウンドが終わっていれば次のストリートへ。
+    fn next_turn(&mut self, from: usize) {
+        if self.active_count() == 1 {
+            // 1 人を残して全員がフォールドした。ポットは全部その人へ
+            self.settle();
+            return;
+        }
+        let can_act: Vec<usize> = (0..self.num_players)
+            .filter(|&i| self.player_active[i] && !self.player_all_in[i])
+            .collect();
+        let rd = &self.round_data;
+        // 行動できるのが 1 人だけで、その人にコール額もなければ誰も応じられない
+        let lone = can_act.len() == 1 && rd.player_bet[can_act[0]] >= rd.bet - EPSILON;
+        if lone || !rd.needs_action.contains(&true) {
+            self.end_betting_round();
+            return;
+        }
+        let next = self.next_where(from, |s, i| s.round_data.needs_action[i]);
+        self.round_data.to_act_idx = next;
+    }
+
+    /// 次のストリートを配る。行動できるプレイヤーが 2 人未満ならショーダウンまで配り切る。
+    fn end_betting_round(&mut self) {
+        loop {
+            if self.round == Round::River {
+                self.showdown();
+                return;
+            }
+            self.advance_round(); // DealFlop / DealTurn / DealRiver
+            let count = if self.round == Round::DealFlop { 3 } else { 1 };
+            for _ in 0..count {
+                let card = self.draw();
+                self.board.push(card);
+            }
+            self.advance_round(); // Flop / Turn / River
+            self.start_betting_round();
+            let can_act = self.can_act_mask();
+            if can_act.iter().filter(|&&b| b).count() >= 2 {
+                self.round_data.needs_action = can_act;
+                self.round_data.to_act_idx = self.next_where(self.dealer_idx, |s, i| {
+                    s.player_active[i] && !s.player_all_in[i]
+                });
+                return;
+            }
+        }
+    }
+
+    fn start_betting_round(&mut self) {
+        self.round_data = RoundData::new(
+            self.player_active.clone(),
+            vec![false; self.num_players],
+            self.big_blind,
+        );
+    }
+
+    /// 役を比べ、出した額の段ごとにサイドポットを分けて精算する。
+    fn showdown(&mut self) {
+        self.advance_round(); // Showdown
+        self.settle();
+    }
+
+    /// [`settlement::settle`] で精算する。端数は等分。
+    fn settle(&mut self) {
+        let seats: Vec<Seat> = (0..self.num_players)
+            .map(|i| Seat {
+                contribution: self.player_bet[i],
+                folded: !self.player_active[i],
+                hand: Some(self.hands[i]),
+            })
+            .collect();
+        let result = settlement::settle(&seats, &self.board, OddChipRule::SplitEvenly)
+            .expect("the engine keeps contributions and cards consistent");
+        for (i, &amount) in result.winnings.iter().enumerate() {
+            self.stacks[i] += amount;
+            self.player_winnings[i] += amount;
+        }
+        self.pots = result.pots;
+        self.showdown = result.showdown;
+        self.complete();
+    }
+
+    fn complete(&mut self) {
+        self.round_before = self.round;
+        self.round = Round::Complete;
+        self.round_data.needs_action = vec![false; self.num_players];
+    }
+
+    fn advance_round(&mut self) {
+        self.round_before = self.round;
+        self.round = self.round.advance();
+    }
+
+    fn draw(&mut self) -> Card {
+        self.deck
+            .pop()
+            .expect("52 cards cover 9 players and a board")
+    }
+
+    fn active_count(&self) -> usize {
+        self.player_active.iter().filter(|&&a| a).count()
+    }
+
+    fn can_act_mask(&self) -> Vec<bool> {
+        (0..self.num_players)
+            .map(|i| self.player_active[i] && !self.player_all_in[i])
+            .collect()
+    }
+
+    /// `from` の次の席から時計回りに、ブラインドを払う（スタックのある）席。
+    fn next_seat(&self, from: usize) -> usize {
+        self.next_where(from, |s, i| s.stacks[i] > 0.0)
+    }
+
+    fn next_where(&self, from: usize, pred: impl Fn(&Self, usize) -> bool) -> usize {
+        (1..=self.num_players)
+            .map(|k| (from + k) % self.num_players)
+            .find(|&i| pred(self, i))
+            .unwrap_or(from)
+    }
+
+    /// JS に返すスナップショット。
+    pub fn snapshot(&self) -> GameSnapshot {
+        let rd = &self.round_data;
+        GameSnapshot {
+            num_players: self.num_players,
+            player_active: self.player_active.clone(),
+            player_all_in: self.player_all_in.clone(),
+            total_pot: self.total_pot,
+            stacks: self.stacks.clone(),
+            starting_stacks: self.starting_stacks.clone(),
+            player_bet: self.player_bet.clone(),
+            player_winnings: self.player_winnings.clone(),
+            big_blind: self.big_blind,
+            small_blind: self.small_blind,
+            ante: self.ante,
+            hands: self
+                .hands
+                .iter()
+                .map(|h| [card_to_str(&h[0]), card_to_str(&h[1])])
+                .collect(),
+            dealer_idx: self.dealer_idx,
+            round: self.round,
+            round_before: self.round_before,
+            round_data: RoundDataSnapshot {
+                starting_player_active: rd.starting_player_active.clone(),
+                needs_action: rd.needs_action.clone(),
+                min_raise: rd.min_raise,
+                bet: rd.bet,
+                player_bet: rd.player_bet.clone(),
+                total_bet_count: rd.total_bet_count,
+                total_raise_count: rd.total_raise_count,
+                to_act_idx: rd.to_act_idx,
+            },
+            board: self.board.iter().map(card_to_str).collect(),
+            bb_posted: self.bb_posted,
+            sb_posted: self.sb_posted,
+            legal: self.legal_actions().map(|l| LegalActionsSnapshot {
+                player: l.player,
+                can_check: l.can_check,
+                call_amount: l.call_amount,
+                min_raise_to: l.min_raise_to,
+                max_raise_to: l.max_raise_to,
+            }),
+            pots: self.pots.iter().map(PotSnapshot::from).collect(),
+            showdown: self.showdown.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(stacks: &[f64], dealer_idx: usize) -> GameState {
+        game_with_ante(stacks, dealer_idx, 0.0)
+    }
+
+    fn game_with_ante(stacks: &[f64], dealer_idx: usize, ante: f64) -> GameState {
+        let config = GameConfig {
+            stacks: stacks.to_vec(),
+            small_blind: 0.5,
+            big_blind: 1.0,
+            ante,
+            dealer_idx,
+        };
+        GameState::new(&config, 7).unwrap()
+    }
+
+    fn chips(g: &GameState) -> f64 {
+        g.stacks.iter().sum::<f64>()
+    }
+
+    #[test]
+    fn heads_up_dealer_posts_small_blind_and_fold_ends_the_hand() {
+        let mut g = game(&[100.0, 100.0], 0);
+        assert_eq!(g.round, Round::Preflop);
+        assert_eq!(g.round_data.player_bet, [0.5, 1.0]);
+        assert_eq!(g.round_data.to_act_idx, 0);
+        g.act(Action::Call).unwrap();
+        // BB のオプション
+        assert_eq!(g.round_data.to_act_idx, 1);
+        assert!(g.act(Action::Call).is_err());
+        g.act(Action::Check).unwrap();
+        assert_eq!(g.round, Round::Flop);
+        assert_eq!(g.board.len(), 3);
+        assert_eq!(g.round_data.to_act_idx, 1);
+
+        // 同じシードなら同じカード
+        let again = game(&[100.0, 100.0], 0);
+        assert_eq!(again.hands, g.hands);
+
+        // フォールドで決着したらショーダウンなしで BB が取る
+        let mut g = game(&[100.0, 100.0], 1);
+        g.act(Action::Fold).unwrap();
+        assert_eq!(g.round, Round::Complete);
+        assert_eq!(g.round_before, Round::Preflop);
+        assert_eq!(g.stacks, [100.5, 99.5]);
+        assert_eq!(g.pots[0].winners, [0]);
+        assert!(g.showdown.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn validates_bet_sizes() {
+        let mut g = game(&[100.0, 100.0, 100.0], 0);
+        // 3 人ならディーラーから
+        assert_eq!(g.round_data.to_act_idx, 0);
+        assert!(g.act(Action::Check).is_err());
+        assert!(g.act(Action::RaiseTo(1.5)).is_err());
+        assert!(g.act(Action::RaiseTo(101.0)).is_err());
+        g.act(Action::RaiseTo(3.0)).unwrap();
+        // 2 → 3 のレイズ幅 2 が最小レイズ
+        let legal = g.legal_actions().unwrap();
+        assert_eq!((legal.player, legal.call_amount), (1, 2.5));
+        assert_eq!(legal.min_raise_to, Some(5.0));
+        assert!(g.act(Action::RaiseTo(4.5)).is_err());
+        g.act(Action::RaiseTo(5.0)).unwrap();
+        assert_eq!(g.round_data.min_raise, 2.0);
+        g.act(Action::Fold).unwrap();
+        g.act(Action::Call).unwrap();
+        assert_eq!(g.round, Round::Flop);
+        assert_eq!(g.total_pot, 11.0);
+        // フロップは SB から
+        assert_eq!(g.round_data.to_act_idx, 1);
+    }
+
+    #[test]
+    fn all_in_under_raise_does_not_reopen_betting() {
+        // ディーラー 0、SB 1（残り 15）、BB 2
+        let mut g = game(&[100.0, 15.0, 100.0], 0);
+        g.act(Action::RaiseTo(10.0)).unwrap();
+        // 10 → 15 はレイズ幅 5 < 9 のアンダーレイズ
+        g.act(Action::AllIn).unwrap();
+        assert_eq!((g.round_data.bet, g.round_data.min_raise), (15.0, 9.0));
+        // BB はまだ行動していないのでレイズできる
+        let legal = g.legal_actions().unwrap();
+        assert_eq!((legal.player, legal.min_raise_to), (2, Some(24.0)));
+        g.act(Action::Call).unwrap();
+        // 最初のレイザーはコールかフォールドだけ
+        let legal = g.legal_actions().unwrap();
+        assert_eq!((legal.player, legal.min_raise_to), (0, None));
+        assert!(g.act(Action::RaiseTo(40.0)).is_err());
+        assert!(g.act(Action::AllIn).is_err());
+        g.act(Action::Call).unwrap();
+        assert_eq!(g.round, Round::Flop);
+
+        // 短いオールインが重なって 1 レイズ分になれば再オープン
+        let mut g = game(&[100.0, 15.0, 22.0, 100.0], 0);
+        g.act(Action::RaiseTo(10.0)).unwrap(); // 3（UTG）
+        g.act(Action::Call).unwrap(); // 0
+        g.act(Action::AllIn).unwrap(); // 1: 15
+        g.act(Action::AllIn).unwrap(); // 2: 22
+        let legal = g.legal_actions().unwrap();
+        assert_eq!(legal.player, 3);
+        assert_eq!(legal.min_raise_to, Some(31.0));
+    }
+
+    #[test]
+    fn splits_side_pots_and_runs_out_the_board() {
+        let mut g = game(&[10.0, 20.0, 30.0], 0);
+        g.act(Action::AllIn).unwrap();
+        g.act(Action::AllIn).unwrap();
+        // 相手が全員オールインなので、BB はレイズできない
+        assert_eq!(g.legal_actions().unwrap().max_raise_to, None);
+        g.act(Action::Call).unwrap();
+        assert_eq!(g.round, Round::Complete);
+        assert_eq!(g.board.len(), 5);
+        let pots: Vec<(f64, usize)> = g
+            .pots
+            .iter()
+            .map(|p| (p.amount, p.eligible.len()))
+            .collect();
+        assert_eq!(pots, [(30.0, 3), (20.0, 2)]);
+        assert_eq!(chips(&g), 60.0);
+        assert!(g.showdown.iter().all(Option::is_some));
+        assert!(g.act(Action::Check).is_err());
+    }
+
+    #[test]
+    fn rejects_raise_to_below_the_current_bet() {
+        let mut g = game(&[100.0, 100.0, 100.0], 0);
+        g.act(Action::RaiseTo(3.0)).unwrap();
+        let before = g.snapshot();
+        assert!(g.act(Action::RaiseTo(2.0)).is_err());
+        assert!(g.act(Action::RaiseTo(3.0)).is_err());
+        assert_eq!(g.round_data.player_bet, before.round_data.player_bet);
+        assert_eq!(g.round_data.to_act_idx, 1);
+    }
+
+    #[test]
+    fn postflop_first_bet_is_at_least_the_big_blind() {
+        let mut g = game(&[100.0, 100.0, 100.0], 0);
+        g.act(Action::Call).unwrap();
+        g.act(Action::Call).unwrap();
+        g.act(Action::Check).unwrap();
+        assert_eq!(g.round, Round::Flop);
+        let legal = g.legal_actions().unwrap();
+        assert_eq!((legal.player, legal.can_check), (1, true));
+        assert_eq!(legal.min_raise_to, Some(1.0));
+        assert!(g.act(Action::RaiseTo(0.5)).is_err());
+        g.act(Action::RaiseTo(1.0)).unwrap();
+        // ベット 1 に対する最小レイズは 2
+        let legal = g.legal_actions().unwrap();
+        assert_eq!((legal.call_amount, legal.min_raise_to), (1.0, Some(2.0)));
+    }
+
+    #[test]
+    fn antes_are_dead_money_and_can_put_a_player_all_in() {
+        // ディーラー 0、SB 1、BB 2。3 はアンティ 0.25 を払い切れずにオールイン
+        let mut g = game_with_ante(&[100.0, 100.0, 100.0, 0.2], 0, 0.25);
+        assert_eq!(g.player_bet, [0.25, 0.75, 1.25, 0.2]);
+        assert!(g.player_all_in[3]);
+        assert!((g.total_pot - 2.45).abs() < 1e-12);
+        // アンティはラウンドのベットに数えないので、コール額は BB のまま
+        assert_eq!(g.round_data.player_bet, [0.0, 0.5, 1.0, 0.0]);
+        let legal = g.legal_actions().unwrap();
+        assert_eq!((legal.player, legal.call_amount), (0, 1.0));
+
+        g.act(Action::Fold).unwrap();
+        g.act(Action::Fold).unwrap();
+        // 残りは BB とオールインの 3 だけなので、そのままショーダウン
+        assert_eq!(g.round, Round::Complete);
+        assert_eq!(g.board.len(), 5);
+        let pots: Vec<(f64, Vec<usize>)> = g
+            .pots
+            .iter()
+            .map(|p| (p.amount, p.eligible.clone()))
+            .collect();
+        assert!((pots[0].0 - 0.8).abs() < 1e-12);
+        assert_eq!(pots[0].1, [2, 3]);
+        assert_eq!(pots[1].1, [2]);
+        assert!((chips(&g) - 300.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn short_big_blind_still_sets_a_full_call() {
+        // BB の 2 はスタック 0.6 でオールイン
+        let mut g = game(&[100.0, 100.0, 0.6], 0);
+        assert!(g.player_all_in[2]);
+        assert_eq!(g.round_data.player_bet, [0.0, 0.5, 0.6]);
+        assert_eq!(g.round_data.bet, 1.0);
+        let legal = g.legal_actions().unwrap();
+        assert_eq!((legal.player, legal.call_amount), (0, 1.0));
+        assert_eq!(legal.min_raise_to, Some(2.0));
+        g.act(Action::Call).unwrap();
+        let legal = g.legal_actions().unwrap();
+        assert_eq!((legal.player, legal.call_amount), (1, 0.5));
+    }
+
+    #[test]
+    fn checking_every_street_reaches_showdown() {
+        let mut g = game(&[100.0, 100.0], 0);
+        g.act(Action::Call).unwrap();
+        g.act(Action::Check).unwrap();
+        for (round, board) in [(Round::Flop, 3), (Round::Turn, 4), (Round::River, 5)] {
+            assert_eq!((g.round, g.board.len()), (round, board));
+            g.act(Action::Check).unwrap();
+            g.act(Action::Check).unwrap();
+        }
+        assert_eq!(g.round, Round::Complete);
+        assert_eq!(g.round_before, Round::Showdown);
+        assert_eq!(g.total_pot, 2.0);
+        assert!(g.showdown.iter().all(Option::is_some));
+        assert_eq!(chips(&g), 200.0);
+        assert!(g.legal_actions().is_none());
+    }
+}
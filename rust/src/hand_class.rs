@@ -0,0 +1,287 @@
+//! プリフロップのハンドクラス（169 種）と、フロップのスート同型による正規化。
+//!
+//! レンジ表で使う `"AKs"` / `"AKo"` / `"QQ"` と具体的なコンボ（`AsKs` 等）の相互変換、
+//! 使用済みカードを除いたコンボ数、13×13 グリッド上の位置を扱う。
+//! グリッドの並びは TS 側（`src/utils/v2/combo.ts`）と同じで、
+//!
+//! - 行 = 12 − 高い方の value、列 = 12 − 低い方の value（A が 0）
+//! - 対角線がペア、右上（row < col）がスーテッド、左下がオフスート
+//! - インデックスは `row * 13 + col`
+
+use std::fmt;
+
+use rs_poker::core::{Card, Suit, Value};
+
+use crate::cards::{all_perms, card_index, combo_key, map_card};
+
+/// グリッドの 1 辺。
+pub const GRID_DIM: usize = 13;
+
+/// ハンドクラスの総数（13 ペア + 78 スーテッド + 78 オフスート）。
+pub const CLASS_COUNT: usize = GRID_DIM * GRID_DIM;
+
+const SUITS: [Suit; 4] = [Suit::Spade, Suit::Club, Suit::Heart, Suit::Diamond];
+
+/// 169 種のハンドクラスの 1 つ。`hi >= lo` で、ペアは `suited == false`。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HandClass {
+    pub hi: Value,
+    pub lo: Value,
+    pub suited: bool,
+}
+
+impl HandClass {
+    /// `"AKs"` / `"AKo"` / `"QQ"` をパースする。ペア以外はサフィックス必須。
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let chars: Vec<char> = s.trim().chars().collect();
+        let value = |c: char| {
+            Value::from_char(c.to_ascii_uppercase())
+                .ok_or_else(|| format!("invalid rank '{}' in hand class '{}'", c, s))
+        };
+        if !(2..=3).contains(&chars.len()) {
+            return Err(format!("invalid hand class '{}'", s));
+        }
+        let (a, b) = (value(chars[0])?, value(chars[1])?);
+        let (hi, lo) = if a >= b { (a, b) } else { (b, a) };
+        let suited = match (chars.get(2).map(|c| c.to_ascii_lowercase()), hi == lo) {
+            (None, true) => false,
+            (Some('s'), false) => true,
+            (Some('o'), false) => false,
+            _ => return Err(format!("invalid hand class '{}'", s)),
+        };
+        Ok(HandClass { hi, lo, suited })
+    }
+
+    /// 具体的なコンボが属するクラス。
+    pub fn from_combo(a: Card, b: Card) -> Self {
+        let (hi, lo) = if a.value >= b.value {
+            (a.value, b.value)
+        } else {
+            (b.value, a.value)
+        };
+        HandClass {
+            hi,
+            lo,
+            suited: hi != lo && a.suit == b.suit,
+        }
+    }
+
+    /// グリッドのインデックス（0..169）から。範囲外は `None`。
+    pub fn from_grid_index(index: usize) -> Option<Self> {
+        if index >= CLASS_COUNT {
+            return None;
+        }
+        let (row, col) = (index / GRID_DIM, index % GRID_DIM);
+        let value = |i: usize| Value::from_u8((GRID_DIM - 1 - i) as u8);
+        Some(HandClass {
+            hi: value(row.min(col)),
+            lo: value(row.max(col)),
+            suited: row < col,
+        })
+    }
+
+    /// 全 169 クラスをグリッドの並び（AA, AKs, AQs, …, 22）で返す。
+    pub fn all() -> Vec<Self> {
+        (0..CLASS_COUNT).filter_map(Self::from_grid_index).collect()
+    }
+
+    pub fn is_pair(&self) -> bool {
+        self.hi == self.lo
+    }
+
+    /// `(row, col)`。
+    pub fn grid_position(&self) -> (usize, usize) {
+        let hi = GRID_DIM - 1 - self.hi as usize;
+        let lo = GRID_DIM - 1 - self.lo as usize;
+        if self.suited {
+            (hi, lo)
+        } else {
+            (lo, hi)
+        }
+    }
+
+    pub fn grid_index(&self) -> usize {
+        let (row, col) = self.grid_position();
+        row * GRID_DIM + col
+    }
+
+    /// 使用済みカードがないときのコンボ数（ペア 6、スーテッド 4、オフスート 12）。
+    pub fn combo_count(&self) -> usize {
+        match (self.is_pair(), self.suited) {
+            (true, _) => 6,
+            (false, true) => 4,
+            (false, false) => 12,
+        }
+    }
+
+    /// クラスに属する全コンボ。各コンボは高い方が先頭で、並びは
+    /// [`crate::parser::parse_range`] と同じ [`combo_key`] の降順。
+    pub fn combos(&self) -> Vec<(Card, Card)> {
+        let mut out = Vec::with_capacity(self.combo_count());
+        for (i, &s1) in SUITS.iter().enumerate() {
+            for (j, &s2) in SUITS.iter().enumerate() {
+                let keep = match (self.is_pair(), self.suited) {
+                    (true, _) => i < j,
+                    (false, true) => i == j,
+                    (false, false) => i != j,
+                };
+                if keep {
+                    out.push((
+                        Card {
+                            value: self.hi,
+                            suit: s1,
+                        },
+                        Card {
+                            value: self.lo,
+                            suit: s2,
+                        },
+                    ));
+                }
+            }
+        }
+        // ペアは (Ad, As) のようにスート番号の大きい方を先頭へ
+        for (a, b) in out.iter_mut() {
+            if card_index(*a) < card_index(*b) {
+                std::mem::swap(a, b);
+            }
+        }
+        out.sort_by_key(|&(a, b)| std::cmp::Reverse(combo_key(a, b)));
+        out
+    }
+
+    /// `dead` のカードを含まないコンボ。
+    pub fn live_combos(&self, dead: &[Card]) -> Vec<(Card, Card)> {
+        self.combos()
+            .into_iter()
+            .filter(|(a, b)| !dead.contains(a) && !dead.contains(b))
+            .collect()
+    }
+
+    /// `dead` のカードを除いたあとのコンボ数。
+    pub fn live_count(&self, dead: &[Card]) -> usize {
+        self.live_combos(dead).len()
+    }
+}
+
+impl fmt::Display for HandClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.hi.to_char(), self.lo.to_char())?;
+        match (self.is_pair(), self.suited) {
+            (true, _) => Ok(()),
+            (false, true) => write!(f, "s"),
+            (false, false) => write!(f, "o"),
+        }
+    }
+}
+
+/// スートの入れ替えで移り合うフロップを 1 つの代表に揃える。
+///
+/// 24 通りのスート置換のうち、カード番号（[`card_index`]）の降順に並べた列が
+/// 辞書順で最小になるものを選ぶ。モノトーンはスペード、レインボーは
+/// スペード・クラブ・ハートの順に割り当たる。戻り値はカード番号の降順。
+pub fn canonical_flop(flop: [Card; 3]) -> [Card; 3] {
+    all_perms()
+        .iter()
+        .map(|perm| sorted_desc(flop.map(|c| map_card(perm, c))))
+        .min_by_key(|cards| cards.map(card_index))
+        .expect("there are 24 suit permutations")
+}
+
+/// `flop` と同型なフロップ（順不同の 3 枚の組）の数。1・4・6・12・24 のいずれか。
+pub fn isomorphic_flop_count(flop: [Card; 3]) -> usize {
+    let mut seen: Vec<[u8; 3]> = all_perms()
+        .iter()
+        .map(|perm| sorted_desc(flop.map(|c| map_card(perm, c))).map(card_index))
+        .collect();
+    seen.sort_unstable();
+    seen.dedup();
+    seen.len()
+}
+
+/// 全 22100 フロップを同型で束ねた代表（1755 通り）と、それぞれの同型数。
+pub fn canonical_flops() -> Vec<([Card; 3], usize)> {
+    let deck = crate::cards::full_deck();
+    let mut out = Vec::new();
+    for i in 0..deck.len() {
+        for j in i + 1..deck.len() {
+            for k in j + 1..deck.len() {
+                let flop = sorted_desc([deck[i], deck[j], deck[k]]);
+                if canonical_flop(flop) == flop {
+                    out.push((flop, isomorphic_flop_count(flop)));
+                }
+            }
+        }
+    }
+    out
+}
+
+fn sorted_desc(mut cards: [Card; 3]) -> [Card; 3] {
+    cards.sort_by_key(|&c| std::cmp::Reverse(card_index(c)));
+    cards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_cards, parse_two_cards};
+
+    #[test]
+    fn classes_round_trip_through_grid_and_combos() {
+        let all = HandClass::all();
+        assert_eq!(all.len(), CLASS_COUNT);
+        assert_eq!(all[0].to_string(), "AA");
+        assert_eq!(all[1].to_string(), "AKs");
+        assert_eq!(all[13].to_string(), "AKo");
+        assert_eq!(all[168].to_string(), "22");
+        for (i, class) in all.iter().enumerate() {
+            assert_eq!(class.grid_index(), i);
+            assert_eq!(HandClass::parse(&class.to_string()).unwrap(), *class);
+            for (a, b) in class.combos() {
+                assert_eq!(HandClass::from_combo(a, b), *class);
+            }
+        }
+        let total: usize = all.iter().map(|c| c.combos().len()).sum();
+        assert_eq!(total, 1326);
+
+        let (a, b) = parse_two_cards("7h9h").unwrap();
+        assert_eq!(HandClass::from_combo(a, b).to_string(), "97s");
+        assert_eq!(HandClass::parse("kAs").unwrap().to_string(), "AKs");
+        assert!(HandClass::parse("AK").is_err());
+        assert!(HandClass::parse("QQs").is_err());
+
+        // 使用済みカードでコンボが減る
+        let dead = parse_cards("AsKd").unwrap();
+        let count = |s: &str| HandClass::parse(s).unwrap().live_count(&dead);
+        assert_eq!(
+            (count("AA"), count("AKs"), count("AKo"), count("QQ")),
+            (3, 2, 7, 6)
+        );
+    }
+
+    #[test]
+    fn flops_collapse_to_1755_classes() {
+        let flops = canonical_flops();
+        assert_eq!(flops.len(), 1755);
+        assert_eq!(flops.iter().map(|(_, n)| n).sum::<usize>(), 22100);
+
+        let flop = |s: &str| {
+            let cards = parse_cards(s).unwrap();
+            [cards[0], cards[1], cards[2]]
+        };
+        assert_eq!(
+            canonical_flop(flop("Kh7h2h")),
+            canonical_flop(flop("Kd7d2d"))
+        );
+        assert_eq!(
+            canonical_flop(flop("Ah7d2c")),
+            canonical_flop(flop("Ac7s2h"))
+        );
+        assert_ne!(
+            canonical_flop(flop("Ah7h2c")),
+            canonical_flop(flop("Ah7c2h"))
+        );
+        assert_eq!(isomorphic_flop_count(flop("Kh7h2h")), 4);
+        assert_eq!(isomorphic_flop_count(flop("AhAdAc")), 4);
+        assert_eq!(isomorphic_flop_count(flop("Ah7d2c")), 24);
+    }
+}
@@ -8,17 +8,39 @@
 //!
 //! - [`parser`]  : 入力文字列のパース
 //! - [`cards`]   : Card 表示・デッキ生成
+//! - [`approx`]  : 額・重みの比較で許す誤差
 //! - [`rank`]    : `Rank` のカテゴリ/エンコード値抽出と集計バケット
-//! - [`rng`]     : シード付き RNG
+//! - [`hand_class`] : 169 種のハンドクラスとフロップのスート同型
+//! - [`game`]    : NLHE 1 ハンドのゲームエンジン（アクション検証・ストリート進行）
+//! - [`settlement`] : サイドポットの構築とショーダウンの精算
+//! - [`deal`]    : seed のコミットメントで後から検証できるデッキの配り
+//! - [`range_update`] : アクション列による相手レンジの絞り込み（ベイズ更新）
+//! - [`rng`]     : シード付き RNG・サブシード導出
+//! - [`parallel`] : ネイティブ限定のマルチスレッド実行ヘルパー
 //! - [`dto`]     : JS 境界の Serialize 構造体
+//! - [`binary`]  : `Uint32Array` で返すバイナリ形式のエンコード/デコード
+//! - [`cache`]   : 正規化した入力をキーにした結果キャッシュ
 //! - [`sim`]     : 各シミュレーション本体
+//!
+//! `rust-cli` が JS 境界を通さずに呼ぶ `cards` / `dto` / `parser` / `rank` / `sim` と、
+//! ネイティブからバイナリ形式を読む `binary` だけを `pub` にし、ほかはクレート内部に閉じる。
 
-mod cards;
-mod dto;
-mod parser;
-mod rank;
+mod approx;
+pub mod binary;
+mod cache;
+pub mod cards;
+mod deal;
+pub mod dto;
+mod game;
+mod hand_class;
+#[cfg(not(target_arch = "wasm32"))]
+mod parallel;
+pub mod parser;
+mod range_update;
+pub mod rank;
 mod rng;
-mod sim;
+mod settlement;
+pub mod sim;
 
 use serde::Serialize;
 use wasm_bindgen::prelude::*;
@@ -45,6 +67,86 @@ pub fn evaluate_hands_ranking(hands: &str, board: &str) -> Result<JsValue, JsVal
     to_js(sim::evaluate::run(hands, board))
 }
 
+/// 5 枚のボードで 2 ハンドを比べ、勝敗と決め手（キッカー差など）を返す。
+#[wasm_bindgen]
+pub fn compare_showdown(first: &str, second: &str, board: &str) -> Result<JsValue, JsValue> {
+    to_js(sim::showdown::run(first, second, board))
+}
+
+/// ボード以外の全コンボを役の強さでクラス分けし、ナッツ順と hero の順位を返す。
+/// `hero` は空文字でもよい。
+#[wasm_bindgen]
+pub fn nut_ranking(hero: &str, board: &str) -> Result<JsValue, JsValue> {
+    to_js(sim::nuts::run(hero, board))
+}
+
+/// フロップ / ターンでの hero の HS・PPot・NPot・EHS・E[HS²]。
+/// `villain_range` が空なら一様レンジ。
+#[wasm_bindgen]
+pub fn hand_strength(hero: &str, board: &str, villain_range: &str) -> Result<JsValue, JsValue> {
+    to_js(sim::hand_strength::run(hero, board, villain_range))
+}
+
+/// ランアウトに沿ったストリートごとの equity と、ターン / リバーのカード別 equity。
+/// `board` が 5 枚未満なら残りを `seed` から引く。
+#[wasm_bindgen]
+pub fn equity_path(
+    hero: &str,
+    board: &str,
+    compare: &str,
+    trials: u32,
+    seed: u64,
+) -> Result<JsValue, JsValue> {
+    to_js(sim::equity_path::run(hero, board, compare, trials, seed))
+}
+
+/// フロップ / ターンから、次に来うる各カードでの hero equity（52 枠のヒートマップ）。
+#[wasm_bindgen]
+pub fn next_card_equity(hero: &str, villain_range: &str, board: &str) -> Result<JsValue, JsValue> {
+    to_js(sim::next_card::run(hero, villain_range, board))
+}
+
+/// 具体的なコンボ（`"AsKs"`）をハンドクラス（`"AKs"`）にする。
+#[wasm_bindgen]
+pub fn hand_class_of(hand: &str) -> Result<String, JsValue> {
+    sim::hand_class::class_of(hand).map_err(|e| JsValue::from_str(&e))
+}
+
+/// ハンドクラス（`"AKs"` 等）の全コンボ。`dead` を含むコンボは除く。
+#[wasm_bindgen]
+pub fn hand_class_combos(class: &str, dead: &str) -> Result<JsValue, JsValue> {
+    to_js(sim::hand_class::combos(class, dead))
+}
+
+/// 13×13 レンジ表の 169 セル（クラス名・位置・使用済みカードを除いたコンボ数）。
+#[wasm_bindgen]
+pub fn hand_class_grid(dead: &str) -> Result<JsValue, JsValue> {
+    to_js(sim::hand_class::grid(dead))
+}
+
+/// フロップをスート同型の代表に揃え、同型なフロップの数と一緒に返す。
+#[wasm_bindgen]
+pub fn canonical_flop(board: &str) -> Result<JsValue, JsValue> {
+    to_js(sim::hand_class::flop(board))
+}
+
+/// スート同型で 1755 通りの全フロップで range vs range を走らせ、フロップ別・テクスチャ別の
+/// hero equity を返す。レンジはスート対称なもの（レンジ式）に限る。
+#[wasm_bindgen]
+pub fn flop_report(
+    hero_range: &str,
+    villain_range: &str,
+    trials: u32,
+    seed: u64,
+) -> Result<JsValue, JsValue> {
+    to_js(sim::flop_report::run(
+        hero_range,
+        villain_range,
+        trials,
+        seed,
+    ))
+}
+
 /// hero 1 ハンド vs 相手ハンドリストのヘッズアップ MC シミュレーション。
 #[wasm_bindgen]
 pub fn simulate_vs_list_with_ranks(
@@ -57,6 +159,20 @@ pub fn simulate_vs_list_with_ranks(
     to_js(sim::vs_list::run(hero, board, compare, trials, seed))
 }
 
+/// [`simulate_vs_list_with_ranks`] の結果を [`binary`] 形式の `Uint32Array` で返す。
+#[wasm_bindgen]
+pub fn simulate_vs_list_with_ranks_binary(
+    hero: &str,
+    board: &str,
+    compare: &str,
+    trials: u32,
+    seed: u64,
+) -> Result<Vec<u32>, JsValue> {
+    sim::vs_list::accumulate(hero, board, compare, trials, seed)
+        .and_then(|acc| binary::encode_vs_list_with_ranks(&acc))
+        .map_err(|e| JsValue::from_str(&e))
+}
+
 /// hero レンジ vs villain レンジの MC シミュレーション。
 #[wasm_bindgen]
 pub fn simulate_range_vs_range_equity(
@@ -75,6 +191,38 @@ pub fn simulate_range_vs_range_equity(
     ))
 }
 
+/// [`simulate_range_vs_range_equity`] の結果を、ハンドクラスごとの 169 セル（グリッドの並び）で返す。
+#[wasm_bindgen]
+pub fn simulate_range_vs_range_equity_grid(
+    hero_range: &str,
+    villain_range: &str,
+    board: &str,
+    trials: u32,
+    seed: u64,
+) -> Result<JsValue, JsValue> {
+    to_js(sim::range_vs_range::run_grid(
+        hero_range,
+        villain_range,
+        board,
+        trials,
+        seed,
+    ))
+}
+
+/// [`simulate_range_vs_range_equity`] の結果を [`binary`] 形式の `Uint32Array` で返す。
+#[wasm_bindgen]
+pub fn simulate_range_vs_range_equity_binary(
+    hero_range: &str,
+    villain_range: &str,
+    board: &str,
+    trials: u32,
+    seed: u64,
+) -> Result<Vec<u32>, JsValue> {
+    sim::range_vs_range::accumulate(hero_range, villain_range, board, trials, seed)
+        .and_then(|acc| binary::encode_range_vs_range(&acc))
+        .map_err(|e| JsValue::from_str(&e))
+}
+
 /// hero vs 相手リストの MC シミュレーション（役分布なし、equity のみ）。
 #[wasm_bindgen]
 pub fn simulate_vs_list_equity(
@@ -100,3 +248,261 @@ pub fn simulate_vs_list_equity(
 pub fn parse_range_to_hands(range: &str, excluded: &str) -> Result<JsValue, JsValue> {
     to_js(sim::parse_range::run(range, excluded))
 }
+
+/// 各プレイヤーが出した額からメインポット・サイドポットを組み、ショーダウンで精算する。
+///
+/// `folded` は席ごとの 0 / 1、`hands` は席順のセミコロン区切り（フォールドした席は空でよい）。
+/// `chip` が 0 なら端数も等分し、正なら `chip` 単位で分けて余りをボタンの左から配る。
+#[wasm_bindgen]
+pub fn settle_pots(
+    contributions: Vec<f64>,
+    folded: Vec<u8>,
+    hands: &str,
+    board: &str,
+    button: usize,
+    chip: f64,
+) -> Result<JsValue, JsValue> {
+    let folded: Vec<bool> = folded.iter().map(|&f| f != 0).collect();
+    to_js(sim::settlement::run(
+        &contributions,
+        &folded,
+        hands,
+        board,
+        button,
+        chip,
+    ))
+}
+
+/// マルチウェイのオールインで、サイドポットを考えた各プレイヤーの期待リターン。
+///
+/// `players` は席順のセミコロン区切りのハンドかレンジ（フォールドした席は空）。
+/// ボードが 4 枚以上なら全列挙、それ以外は `trials` 回の Monte Carlo。
+#[wasm_bindgen]
+pub fn all_in_ev(
+    players: &str,
+    contributions: Vec<f64>,
+    board: &str,
+    trials: u32,
+    seed: u64,
+) -> Result<JsValue, JsValue> {
+    to_js(sim::all_in_ev::run(
+        players,
+        &contributions,
+        board,
+        trials,
+        seed,
+    ))
+}
+
+/// オープンに対する BB のハンドクラスごとのコール / フォールドと、ディフェンス率・MDF。
+///
+/// 額は BB 単位。`pot` はオープン前のブラインドとアンテ、`open_size` はレイザーの合計ベット額。
+/// `realization` は BB の equity 実現率（1 なら実現率を考えない）。
+#[wasm_bindgen]
+pub fn bb_defense(
+    open_range: &str,
+    open_size: f64,
+    pot: f64,
+    defend_range: &str,
+    realization: f64,
+    trials: u32,
+    seed: u64,
+) -> Result<JsValue, JsValue> {
+    to_js(sim::bb_defense::run(
+        open_range,
+        open_size,
+        pot,
+        defend_range,
+        realization,
+        trials,
+        seed,
+    ))
+}
+
+/// 相手のアクション列から、各アクション後の重み付きレンジ（ベイズ更新）を返す。
+///
+/// `range` は `"QQ+,AKs,AQs:0.5"`（`:重み` は省略可）、`actions` は `"preflop raise; flop bet"`。
+/// `models` はアクションごとのセミコロン区切りで `"top_pair:0.8,AA:1"` のような頻度。
+/// 空なら組み込みのヒューリスティックを使う。
+#[wasm_bindgen]
+pub fn update_range(
+    range: &str,
+    board: &str,
+    actions: &str,
+    models: &str,
+) -> Result<JsValue, JsValue> {
+    to_js(sim::range_update::run(range, board, actions, models))
+}
+
+/// server seed のハッシュ。配る前に公開するコミットメント。
+#[wasm_bindgen]
+pub fn deal_commitment(server_seed: &str) -> String {
+    deal::commitment(server_seed)
+}
+
+/// 32 バイトの乱数から作った server seed（16 進）。
+#[wasm_bindgen]
+pub fn generate_server_seed() -> Result<String, JsValue> {
+    deal::generate_server_seed().map_err(|e| JsValue::from_str(&e))
+}
+
+/// server seed・client seed・nonce から決まる 52 枚の配り順とそのハッシュ。
+#[wasm_bindgen]
+pub fn shuffled_deck(server_seed: &str, client_seed: &str, nonce: u64) -> Result<JsValue, JsValue> {
+    to_js(Ok(sim::deal::deck(server_seed, client_seed, nonce)))
+}
+
+/// 明かされた server seed がコミットメントと一致し、`dealt`（配った順）がデッキの先頭どおりか。
+#[wasm_bindgen]
+pub fn verify_deal(
+    commitment: &str,
+    server_seed: &str,
+    client_seed: &str,
+    nonce: u64,
+    dealt: &str,
+) -> Result<JsValue, JsValue> {
+    to_js(sim::deal::verify(
+        commitment,
+        server_seed,
+        client_seed,
+        nonce,
+        dealt,
+    ))
+}
+
+/// 入力をスート同型まで正規化したキーで結果を使い回すキャッシュ（[`cache::ResultCache`]）。
+///
+/// JS 側でインスタンスを持ち続け、`export_bytes` / `import_bytes` で永続化する。
+#[wasm_bindgen]
+pub struct SimulationCache {
+    inner: cache::ResultCache,
+}
+
+#[wasm_bindgen]
+impl SimulationCache {
+    /// `capacity` 件まで保持する。0 のときは既定値（[`cache::DEFAULT_CAPACITY`]）。
+    #[wasm_bindgen(constructor)]
+    pub fn new(capacity: usize) -> SimulationCache {
+        let capacity = if capacity == 0 {
+            cache::DEFAULT_CAPACITY
+        } else {
+            capacity
+        };
+        SimulationCache {
+            inner: cache::ResultCache::new(capacity),
+        }
+    }
+
+    /// キャッシュを通した [`simulate_vs_list_with_ranks`]。
+    pub fn simulate_vs_list_with_ranks(
+        &mut self,
+        hero: &str,
+        board: &str,
+        compare: &str,
+        trials: u32,
+        seed: u64,
+    ) -> Result<JsValue, JsValue> {
+        to_js(self.inner.vs_list(hero, board, compare, trials, seed))
+    }
+
+    /// キャッシュを通した [`simulate_range_vs_range_equity`]。
+    pub fn simulate_range_vs_range_equity(
+        &mut self,
+        hero_range: &str,
+        villain_range: &str,
+        board: &str,
+        trials: u32,
+        seed: u64,
+    ) -> Result<JsValue, JsValue> {
+        to_js(
+            self.inner
+                .range_vs_range(hero_range, villain_range, board, trials, seed),
+        )
+    }
+
+    /// 保持しているエントリ数。
+    pub fn size(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    /// キャッシュの状態をバイト列（JS では `Uint8Array`）にする。
+    pub fn export_bytes(&self) -> Vec<u8> {
+        self.inner.to_bytes()
+    }
+
+    /// `export_bytes` の出力で状態を置き換える。
+    pub fn import_bytes(&mut self, bytes: &[u8]) -> Result<(), JsValue> {
+        self.inner
+            .load_bytes(bytes)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+}
+
+/// NLHE の 1 ハンドを進めるゲームエンジン（[`game::GameState`]）。
+///
+/// 各アクションは検証してから適用し、適用後のスナップショット（JS オブジェクト）を返す。
+/// 不正なアクションは Error になり、状態は変わらない。
+#[wasm_bindgen]
+pub struct HoldemGame {
+    inner: game::GameState,
+}
+
+#[wasm_bindgen]
+impl HoldemGame {
+    /// `seed` でシャッフルしたデッキから配り、ブラインドを払ってプリフロップの手番まで進める。
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        stacks: Vec<f64>,
+        small_blind: f64,
+        big_blind: f64,
+        ante: f64,
+        dealer_idx: usize,
+        seed: u64,
+    ) -> Result<HoldemGame, JsValue> {
+        let config = game::GameConfig {
+            stacks,
+            small_blind,
+            big_blind,
+            ante,
+            dealer_idx,
+        };
+        game::GameState::new(&config, seed)
+            .map(|inner| HoldemGame { inner })
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    pub fn snapshot(&self) -> Result<JsValue, JsValue> {
+        to_js(Ok(self.inner.snapshot()))
+    }
+
+    pub fn fold(&mut self) -> Result<JsValue, JsValue> {
+        self.act(game::Action::Fold)
+    }
+
+    pub fn check(&mut self) -> Result<JsValue, JsValue> {
+        self.act(game::Action::Check)
+    }
+
+    pub fn call(&mut self) -> Result<JsValue, JsValue> {
+        self.act(game::Action::Call)
+    }
+
+    /// このラウンドの合計ベット額が `amount` になるようにベット / レイズする。
+    pub fn raise_to(&mut self, amount: f64) -> Result<JsValue, JsValue> {
+        self.act(game::Action::RaiseTo(amount))
+    }
+
+    pub fn all_in(&mut self) -> Result<JsValue, JsValue> {
+        self.act(game::Action::AllIn)
+    }
+}
+
+impl HoldemGame {
+    fn act(&mut self, action: game::Action) -> Result<JsValue, JsValue> {
+        to_js(self.inner.act(action).map(|()| self.inner.snapshot()))
+    }
+}
@@ -0,0 +1,83 @@
+//! ネイティブビルド用のマルチスレッド実行ヘルパー。
+//!
+//! シミュレーションを「番号付きの作業単位」に分け、各単位は
+//! [`crate::rng::derive_seed`] で導出したサブシードだけを使って計算する。
+//! 集計は整数カウンタの加算（可換・結合的）なので、スレッド数や
+//! 処理順が変わっても結果は変わらない。
+//!
+//! WASM ビルドはスレッドを使えないため、このモジュールはネイティブ限定。
+//! JS から呼ばれるシリアル版はそのまま残している。
+//!
+//! `rust-wasm/src/sim/parallel.rs` に同じ実装があるのは意図的な複製。2 つのクレートは
+//! 別々の WASM バンドルとしてビルドされ依存関係を共有していないので、数十行のために
+//! 共通クレートは作っていない。乱数生成器もクレートごとに違うため、片方だけ直しても
+//! もう片方の結果には影響しない。
+
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// 利用可能な論理コア数。取得できない環境では 1。
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// `0..units` の作業単位をスレッドに配り、スレッドごとの集計を最後に 1 つへ畳み込む。
+///
+/// - `init`  : 空の集計値を作る
+/// - `work`  : 作業単位 1 つ分を集計値へ足し込む
+/// - `merge` : スレッドの集計値を最終結果へ足し込む
+///
+/// 単位の割り当ては早い者勝ちなので、`work` / `merge` が順序に依存しない
+/// （カウンタの加算など）場合に限り結果がスレッド数に依存しない。
+/// `threads` が 0 のときは [`default_threads`] を使う。
+pub fn fold_units<T, I, W, M>(units: usize, threads: usize, init: I, work: W, merge: M) -> T
+where
+    T: Send,
+    I: Fn() -> T + Sync,
+    W: Fn(&mut T, usize) + Sync,
+    M: Fn(&mut T, T),
+{
+    let threads = if threads == 0 {
+        default_threads()
+    } else {
+        threads
+    }
+    .min(units.max(1));
+
+    let mut total = init();
+    if threads <= 1 {
+        for unit in 0..units {
+            work(&mut total, unit);
+        }
+        return total;
+    }
+
+    let next = AtomicUsize::new(0);
+    let partials: Vec<T> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut acc = init();
+                    loop {
+                        let unit = next.fetch_add(1, Ordering::Relaxed);
+                        if unit >= units {
+                            break;
+                        }
+                        work(&mut acc, unit);
+                    }
+                    acc
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("simulation worker panicked"))
+            .collect()
+    });
+
+    for partial in partials {
+        merge(&mut total, partial);
+    }
+    total
+}
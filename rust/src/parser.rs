@@ -12,6 +12,8 @@
 use rs_poker::core::{Card, Hand};
 use rs_poker::holdem::RangeParser;
 
+use crate::cards::combo_key;
+
 /// 連結カード文字列 (`"AsKsQd"`) を `Vec<Card>` に変換する。
 ///
 /// rs_poker の `Hand::new_from_str` は空白を許容しないので、空白文字を
@@ -25,6 +27,24 @@ pub fn parse_cards(s: &str) -> Result<Vec<Card>, String> {
     Ok(hand.iter().collect())
 }
 
+/// [`parse_cards`] と同じ入力を、並び順を保ったままパースする。
+///
+/// `Hand` はカードを集合として持つので、ランアウトのように順番に意味がある
+/// ボードはこちらを使う。
+pub fn parse_cards_ordered(s: &str) -> Result<Vec<Card>, String> {
+    let cleaned: Vec<char> = s.chars().filter(|c| !c.is_whitespace()).collect();
+    if !cleaned.len().is_multiple_of(2) {
+        return Err(format!("parse error '{}': odd number of characters", s));
+    }
+    cleaned
+        .chunks(2)
+        .map(|pair| {
+            let token: String = pair.iter().collect();
+            Card::try_from(token.as_str()).map_err(|e| format!("parse error '{}': {:?}", s, e))
+        })
+        .collect()
+}
+
 /// 「ちょうど 2 枚のカード」をパースする。スターティングハンド用。
 pub fn parse_two_cards(s: &str) -> Result<(Card, Card), String> {
     let cards = parse_cards(s)?;
@@ -51,6 +71,10 @@ pub fn parse_hands_list(s: &str) -> Result<Vec<(Card, Card)>, String> {
 ///
 /// rs_poker の `RangeParser::parse_many` を薄くラップしているだけだが、
 /// 戻り値の `FlatHand` から `(Card, Card)` タプルへ変換する役目もある。
+///
+/// `parse_many` の戻り順はハッシュ順で実行ごとに変わるため、強いカード順
+/// （[`combo_key`] の降順）に並べ直して返す。コンボの並びがシードごとの
+/// 乱数の割り当てを決めるので、これがないと同じシードでも結果が再現しない。
 pub fn parse_range(input: &str) -> Result<Vec<(Card, Card)>, String> {
     let trimmed = input.trim();
     if trimmed.is_empty() {
@@ -58,7 +82,7 @@ pub fn parse_range(input: &str) -> Result<Vec<(Card, Card)>, String> {
     }
     let hands = RangeParser::parse_many(trimmed)
         .map_err(|e| format!("range parse error '{}': {:?}", input, e))?;
-    Ok(hands
+    let mut combos: Vec<(Card, Card)> = hands
         .into_iter()
         .filter_map(|h| {
             let cards: Vec<Card> = h.iter().copied().collect();
@@ -68,5 +92,7 @@ pub fn parse_range(input: &str) -> Result<Vec<(Card, Card)>, String> {
                 None
             }
         })
-        .collect())
+        .collect();
+    combos.sort_unstable_by_key(|&(a, b)| std::cmp::Reverse(combo_key(a, b)));
+    Ok(combos)
 }
@@ -0,0 +1,556 @@
+//! 相手のアクション列からレンジを絞り込む（ベイズ更新）。
+//!
+//! コンボごとの重みを事前分布とみなし、アクションを観測するたびに
+//! 「そのコンボならそのアクションを選ぶ頻度」を掛ける:
+//! `posterior(c) ∝ prior(c) × P(action | c)`。
+//!
+//! - 頻度はハンドの分類ごとに決める。プリフロップは 169 のハンドクラス、
+//!   フロップ以降はそのストリートのボードに対する強さ（[`Strength`]）
+//! - [`FrequencyModel`] で指定しなかった分類は組み込みのヒューリスティック
+//!   （[`heuristic_frequency`]）を使う
+//! - 重みは正規化せずに持つ（重み 1 のコンボだけなら合計 = コンボ数）。確率が欲しければ合計で割る
+//! - 最終的なボードとかぶるコンボは最初に取り除く
+
+use std::collections::HashMap;
+
+use rs_poker::core::{Card, Rankable, Suit, Value};
+
+use crate::approx::EPSILON;
+use crate::hand_class::{HandClass, CLASS_COUNT};
+use crate::rank::rank_index;
+
+/// アクションを観測したストリート。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+impl Street {
+    /// `"preflop"` / `"flop"` / `"turn"` / `"river"`（大文字小文字は不問）。
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "preflop" => Ok(Street::Preflop),
+            "flop" => Ok(Street::Flop),
+            "turn" => Ok(Street::Turn),
+            "river" => Ok(Street::River),
+            _ => Err(format!("invalid street '{}'", s)),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Street::Preflop => "preflop",
+            Street::Flop => "flop",
+            Street::Turn => "turn",
+            Street::River => "river",
+        }
+    }
+
+    /// このストリートで見えているボードの枚数。
+    pub fn board_len(self) -> usize {
+        match self {
+            Street::Preflop => 0,
+            Street::Flop => 3,
+            Street::Turn => 4,
+            Street::River => 5,
+        }
+    }
+}
+
+/// 相手が取ったアクション。フォールドしたら絞り込む意味がないので含めない。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RangeAction {
+    Check,
+    /// 誰もベットしていないところでのベット（プリフロップならオープンレイズ）。
+    Bet,
+    Call,
+    Raise,
+}
+
+impl RangeAction {
+    /// `"check"` / `"bet"` / `"call"` / `"raise"`。頭文字 1 字（`x` はチェック）でもよい。
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "check" | "x" => Ok(RangeAction::Check),
+            "bet" | "b" => Ok(RangeAction::Bet),
+            "call" | "c" => Ok(RangeAction::Call),
+            "raise" | "r" => Ok(RangeAction::Raise),
+            _ => Err(format!("invalid action '{}'", s)),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RangeAction::Check => "check",
+            RangeAction::Bet => "bet",
+            RangeAction::Call => "call",
+            RangeAction::Raise => "raise",
+        }
+    }
+}
+
+/// フロップ以降のボードに対するハンドの強さ。ホールカードが絡まない役はないものとして扱う。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Strength {
+    /// ツーペア（ホールカード 2 枚とも使う）・セット / トリップス以上。
+    Strong,
+    /// トップペアかオーバーペア。
+    TopPair,
+    /// セカンドペアか、トップカードより下でセカンドより上のポケットペア。
+    MiddlePair,
+    /// それより弱いペア。
+    WeakPair,
+    /// ペアなしのフラッシュドロー・オープンエンドのストレートドロー（リバーにはない）。
+    Draw,
+    Air,
+}
+
+impl Strength {
+    /// 強い順。
+    pub const ALL: [Strength; 6] = [
+        Strength::Strong,
+        Strength::TopPair,
+        Strength::MiddlePair,
+        Strength::WeakPair,
+        Strength::Draw,
+        Strength::Air,
+    ];
+
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let key = s.trim().to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|st| st.name() == key)
+            .ok_or_else(|| format!("invalid strength '{}'", s))
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Strength::Strong => "strong",
+            Strength::TopPair => "top_pair",
+            Strength::MiddlePair => "middle_pair",
+            Strength::WeakPair => "weak_pair",
+            Strength::Draw => "draw",
+            Strength::Air => "air",
+        }
+    }
+}
+
+/// 重み付きのコンボ。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WeightedCombo {
+    pub hand: (Card, Card),
+    pub weight: f64,
+}
+
+/// 1 つのアクションについて、分類ごとにそのアクションを選ぶ頻度（0〜1）。
+///
+/// ハンドクラスの指定はどのストリートでも、強さの指定はフロップ以降で使う。
+/// 両方に当てはまるときはハンドクラスを優先し、どちらもなければヒューリスティック。
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FrequencyModel {
+    pub classes: HashMap<HandClass, f64>,
+    pub strengths: HashMap<Strength, f64>,
+}
+
+impl FrequencyModel {
+    /// `hand` が `board`（そのストリートで見えている分）で `action` を選ぶ頻度。
+    pub fn frequency(
+        &self,
+        hand: (Card, Card),
+        board: &[Card],
+        street: Street,
+        action: RangeAction,
+    ) -> f64 {
+        let class = HandClass::from_combo(hand.0, hand.1);
+        let supplied = self.classes.get(&class).copied().or_else(|| {
+            (street != Street::Preflop)
+                .then(|| self.strengths.get(&classify(hand, board)).copied())
+                .flatten()
+        });
+        supplied
+            .unwrap_or_else(|| heuristic_frequency(hand, board, street, action))
+            .clamp(0.0, 1.0)
+    }
+}
+
+/// 観測したアクション 1 つ。
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObservedAction {
+    pub street: Street,
+    pub action: RangeAction,
+    pub model: FrequencyModel,
+}
+
+/// アクション 1 つを反映したあとのレンジ。
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeStep {
+    pub street: Street,
+    pub action: RangeAction,
+    /// 直前のレンジがこのアクションを選ぶ確率（重みの合計の比）。
+    pub action_probability: f64,
+    /// 重みが 0 になったコンボは含まない。
+    pub combos: Vec<WeightedCombo>,
+}
+
+/// `prior` を `actions` の順に更新し、各アクション後のレンジを返す。
+///
+/// `board` は最終的に見えているボード（0, 3, 4, 5 枚）で、各アクションはそのストリートの
+/// 枚数だけ先頭から使う。ストリートは戻れず、ボードより先のストリートは指定できない。
+pub fn update_range(
+    prior: &[WeightedCombo],
+    board: &[Card],
+    actions: &[ObservedAction],
+) -> Result<Vec<RangeStep>, String> {
+    if ![0, 3, 4, 5].contains(&board.len()) {
+        return Err(format!(
+            "expected 0, 3, 4 or 5 board cards, got {}",
+            board.len()
+        ));
+    }
+    for (i, c) in board.iter().enumerate() {
+        if board[..i].contains(c) {
+            return Err("duplicate card on board".to_string());
+        }
+    }
+    for (i, a) in actions.iter().enumerate() {
+        if a.street.board_len() > board.len() {
+            return Err(format!(
+                "action {} is on the {} but the board has {} cards",
+                i + 1,
+                a.street.name(),
+                board.len()
+            ));
+        }
+        if i > 0 && a.street < actions[i - 1].street {
+            return Err(format!(
+                "action {} goes back to the {}",
+                i + 1,
+                a.street.name()
+            ));
+        }
+    }
+
+    let mut range: Vec<WeightedCombo> = prior
+        .iter()
+        .filter(|c| c.weight > EPSILON && !board.contains(&c.hand.0) && !board.contains(&c.hand.1))
+        .copied()
+        .collect();
+    let mut steps = Vec::with_capacity(actions.len());
+    for a in actions {
+        let visible = &board[..a.street.board_len()];
+        let before: f64 = range.iter().map(|c| c.weight).sum();
+        range = range
+            .into_iter()
+            .map(|c| WeightedCombo {
+                hand: c.hand,
+                weight: c.weight * a.model.frequency(c.hand, visible, a.street, a.action),
+            })
+            .filter(|c| c.weight > EPSILON)
+            .collect();
+        let after: f64 = range.iter().map(|c| c.weight).sum();
+        steps.push(RangeStep {
+            street: a.street,
+            action: a.action,
+            action_probability: if before > EPSILON {
+                after / before
+            } else {
+                0.0
+            },
+            combos: range.clone(),
+        });
+    }
+    Ok(steps)
+}
+
+/// `hand` の `board`（3〜5 枚、かぶりなし）に対する強さ。
+pub fn classify(hand: (Card, Card), board: &[Card]) -> Strength {
+    let (a, b) = hand;
+    let mut cards = board.to_vec();
+    cards.extend([a, b]);
+    let full = cards.as_slice().rank();
+    if rank_index(&full) >= 4 {
+        // ストレート以上。5 枚のボードそのものが役ならホールカードは絡んでいない
+        if board.len() < 5 || board.rank() != full {
+            return Strength::Strong;
+        }
+        return Strength::Air;
+    }
+
+    let count = |v: Value| cards.iter().filter(|c| c.value == v).count();
+    let mut paired: Vec<Value> = [a.value, b.value]
+        .into_iter()
+        .filter(|&v| count(v) >= 2)
+        .collect();
+    paired.dedup();
+    if paired.len() == 2 || paired.iter().any(|&v| count(v) >= 3) {
+        return Strength::Strong;
+    }
+    if let Some(&v) = paired.first() {
+        return pair_strength(v, a.value == b.value, board);
+    }
+    if has_draw(hand, &cards, board.len()) {
+        Strength::Draw
+    } else {
+        Strength::Air
+    }
+}
+
+/// ホールカードを使ったペア（`pocket` ならポケットペア）の強さ。
+fn pair_strength(v: Value, pocket: bool, board: &[Card]) -> Strength {
+    let mut tops: Vec<Value> = board.iter().map(|c| c.value).collect();
+    tops.sort_unstable_by(|x, y| y.cmp(x));
+    tops.dedup();
+    let second = tops.get(1).copied();
+    if pocket {
+        if v > tops[0] {
+            Strength::TopPair
+        } else if second.is_none_or(|s| v > s) {
+            Strength::MiddlePair
+        } else {
+            Strength::WeakPair
+        }
+    } else if v == tops[0] {
+        Strength::TopPair
+    } else if Some(v) == second {
+        Strength::MiddlePair
+    } else {
+        Strength::WeakPair
+    }
+}
+
+/// ホールカードを含むフラッシュドローかオープンエンドのストレートドロー。
+fn has_draw(hand: (Card, Card), cards: &[Card], board_len: usize) -> bool {
+    if board_len >= 5 {
+        return false;
+    }
+    let flush_draw = [Suit::Spade, Suit::Club, Suit::Heart, Suit::Diamond]
+        .into_iter()
+        .any(|s| {
+            (hand.0.suit == s || hand.1.suit == s)
+                && cards.iter().filter(|c| c.suit == s).count() == 4
+        });
+    if flush_draw {
+        return true;
+    }
+
+    // bit 0 = A（ホイール用）、bit 1 = 2 … bit 13 = A
+    let bit = |v: Value| 1u16 << (v as u8 + 1);
+    let mut mask = 0u16;
+    for c in cards {
+        mask |= bit(c.value);
+        if c.value == Value::Ace {
+            mask |= 1;
+        }
+    }
+    let hole = bit(hand.0.value) | bit(hand.1.value);
+    // 4 連続の両端が 13 ランクの内側に残る窓だけがオープンエンド
+    (1..=9).any(|low| {
+        let window = 0b1111u16 << low;
+        mask & window == window && hole & window != 0
+    })
+}
+
+/// 組み込みのヒューリスティック頻度。相手モデルを持たないときの目安で、特定の戦略に基づくものではない。
+///
+/// - プリフロップ: ハンドクラスを Chen スコア順に並べた上位割合 `q`（[`preflop_percentile`]）で決める。
+///   ベット / レイズは上位 8% で 1、25% で 0。コールは上位 8% で 0.3（スロープレイ）、
+///   35% まで 0.9、60% で 0。チェックは `1 − レイズ`
+/// - フロップ以降: [`classify`] の強さごとの固定表
+pub fn heuristic_frequency(
+    hand: (Card, Card),
+    board: &[Card],
+    street: Street,
+    action: RangeAction,
+) -> f64 {
+    if street == Street::Preflop {
+        let q = preflop_percentile(HandClass::from_combo(hand.0, hand.1));
+        let raise = ramp(q, 0.08, 0.25);
+        return match action {
+            RangeAction::Bet | RangeAction::Raise => raise,
+            RangeAction::Check => 1.0 - raise,
+            RangeAction::Call if q <= 0.08 => 0.3,
+            RangeAction::Call => 0.9 * ramp(q, 0.35, 0.6),
+        };
+    }
+    // (check, bet, call, raise)。check + bet = 1、call + raise + fold = 1
+    let (check, bet, call, raise) = match classify(hand, board) {
+        Strength::Strong => (0.3, 0.7, 0.55, 0.45),
+        Strength::TopPair => (0.45, 0.55, 0.8, 0.1),
+        Strength::MiddlePair => (0.7, 0.3, 0.6, 0.03),
+        Strength::WeakPair => (0.8, 0.2, 0.35, 0.02),
+        Strength::Draw => (0.55, 0.45, 0.6, 0.2),
+        Strength::Air => (0.75, 0.25, 0.1, 0.05),
+    };
+    match action {
+        RangeAction::Check => check,
+        RangeAction::Bet => bet,
+        RangeAction::Call => call,
+        RangeAction::Raise => raise,
+    }
+}
+
+/// `q <= full` で 1、`q >= zero` で 0、その間は線形。
+fn ramp(q: f64, full: f64, zero: f64) -> f64 {
+    ((zero - q) / (zero - full)).clamp(0.0, 1.0)
+}
+
+/// 全 1326 コンボのうち、Chen スコアが `class` より高いクラスの割合（同点は半分）。0 が最強。
+pub fn preflop_percentile(class: HandClass) -> f64 {
+    let score = chen_score(class);
+    let mut better = 0.0;
+    for other in HandClass::all() {
+        let s = chen_score(other);
+        let n = other.combo_count() as f64;
+        if s > score {
+            better += n;
+        } else if s == score {
+            better += n / 2.0;
+        }
+    }
+    debug_assert_eq!(HandClass::all().len(), CLASS_COUNT);
+    better / 1326.0
+}
+
+/// Bill Chen のスターティングハンド評価（端数の切り上げはしない）。
+pub fn chen_score(class: HandClass) -> f64 {
+    let points = |v: Value| match v {
+        Value::Ace => 10.0,
+        Value::King => 8.0,
+        Value::Queen => 7.0,
+        Value::Jack => 6.0,
+        v => (v as u8 + 2) as f64 / 2.0,
+    };
+    if class.is_pair() {
+        return (points(class.hi) * 2.0).max(5.0);
+    }
+    let mut score = points(class.hi);
+    if class.suited {
+        score += 2.0;
+    }
+    let gap = class.hi as u8 - class.lo as u8 - 1;
+    score -= match gap {
+        0 => 0.0,
+        1 => 1.0,
+        2 => 2.0,
+        3 => 4.0,
+        _ => 5.0,
+    };
+    if gap <= 1 && class.hi < Value::Queen {
+        score += 1.0;
+    }
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_cards_ordered, parse_range, parse_two_cards};
+
+    fn uniform(range: &str) -> Vec<WeightedCombo> {
+        parse_range(range)
+            .unwrap()
+            .into_iter()
+            .map(|hand| WeightedCombo { hand, weight: 1.0 })
+            .collect()
+    }
+
+    fn weight_of(step: &RangeStep, hand: &str) -> f64 {
+        let (a, b) = parse_two_cards(hand).unwrap();
+        step.combos
+            .iter()
+            .find(|c| c.hand == (a, b) || c.hand == (b, a))
+            .map_or(0.0, |c| c.weight)
+    }
+
+    #[test]
+    fn heuristic_update_follows_preflop_class_and_board_strength() {
+        let board = parse_cards_ordered("Kh7c2d").unwrap();
+        assert_eq!(
+            classify(parse_two_cards("7s7d").unwrap(), &board),
+            Strength::Strong
+        );
+        assert_eq!(
+            classify(parse_two_cards("AsKs").unwrap(), &board),
+            Strength::TopPair
+        );
+        assert_eq!(
+            classify(parse_two_cards("QsQd").unwrap(), &board),
+            Strength::MiddlePair
+        );
+        assert_eq!(
+            classify(parse_two_cards("8h6h").unwrap(), &board),
+            Strength::Air
+        );
+        let hearts = parse_cards_ordered("Kh7h2d").unwrap();
+        assert_eq!(
+            classify(parse_two_cards("Ah4h").unwrap(), &hearts),
+            Strength::Draw
+        );
+
+        let actions = [
+            ObservedAction {
+                street: Street::Preflop,
+                action: RangeAction::Raise,
+                model: FrequencyModel::default(),
+            },
+            ObservedAction {
+                street: Street::Flop,
+                action: RangeAction::Bet,
+                model: FrequencyModel::default(),
+            },
+        ];
+        let steps = update_range(&uniform("77,AKs,72o"), &board, &actions).unwrap();
+        // 72o はレイズしない。ボードとかぶるコンボを除くと 77 が 3、AKs が 3、72o が 7 コンボ
+        assert_eq!(weight_of(&steps[0], "7h2c"), 0.0);
+        assert_eq!(weight_of(&steps[0], "AsKs"), 1.0);
+        let kept: f64 = steps[0].combos.iter().map(|c| c.weight).sum();
+        assert!((steps[0].action_probability - kept / 13.0).abs() < 1e-12);
+        // セットの方がトップペアよりフロップでベットしやすい
+        let set = weight_of(&steps[1], "7s7d") / weight_of(&steps[0], "7s7d");
+        let top = weight_of(&steps[1], "AsKs") / weight_of(&steps[0], "AsKs");
+        assert!(set > top, "set {} vs top pair {}", set, top);
+    }
+
+    #[test]
+    fn supplied_model_overrides_heuristic_and_rejects_bad_streets() {
+        let board = parse_cards_ordered("Kh7c2dQs").unwrap();
+        let mut model = FrequencyModel::default();
+        model.classes.insert(HandClass::parse("AKs").unwrap(), 0.5);
+        model.strengths.insert(Strength::TopPair, 0.0);
+        let actions = [ObservedAction {
+            street: Street::Turn,
+            action: RangeAction::Call,
+            model,
+        }];
+        let prior = vec![
+            WeightedCombo {
+                hand: parse_two_cards("AsKs").unwrap(),
+                weight: 0.8,
+            },
+            WeightedCombo {
+                hand: parse_two_cards("KdJd").unwrap(),
+                weight: 1.0,
+            },
+            WeightedCombo {
+                hand: parse_two_cards("Ts9s").unwrap(),
+                weight: 1.0,
+            },
+        ];
+        let steps = update_range(&prior, &board, &actions).unwrap();
+        assert_eq!(weight_of(&steps[0], "AsKs"), 0.4);
+        assert_eq!(weight_of(&steps[0], "KdJd"), 0.0);
+        // T9s はガットショットだけなので Air のコール頻度
+        assert!((weight_of(&steps[0], "Ts9s") - 0.1).abs() < 1e-12);
+
+        let river = [ObservedAction {
+            street: Street::River,
+            action: RangeAction::Bet,
+            model: FrequencyModel::default(),
+        }];
+        assert!(update_range(&prior, &board, &river).is_err());
+    }
+}
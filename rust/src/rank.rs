@@ -1,6 +1,7 @@
 //! `rs_poker::Rank` をアプリ側で扱いやすい形に変換する。
 
-use rs_poker::core::{CoreRank, Rank};
+use rs_poker::core::{Card, CoreRank, Rank, Rankable, Value};
+use serde::{Deserialize, Serialize};
 
 /// 役カテゴリの表示名。配列のインデックスが [`rank_index`] と一致するよう保つこと。
 pub const RANK_LABELS: [&str; 9] = [
@@ -44,14 +45,202 @@ pub fn rank_encoded(r: &Rank) -> u32 {
     (((rank_index(r) as u32) + 1) << 12) | u32::from(r.value_bits())
 }
 
+/// 役を構成するカードの内訳。[`hand_detail`] で作る。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandDetail {
+    /// 役を作る 5 枚。役の札（枚数の多い組 → ランクの高い順）が先、キッカーが後。
+    /// A-5 のストレートは A を末尾に置く。カードが 5 枚未満なら全部。
+    pub best_five: Vec<Card>,
+    /// 勝敗に効くキッカーのランク（0 = 2 .. 12 = A）。強い順。
+    pub kickers: Vec<u32>,
+    /// `"Two Pair, Kings and Fives, Queen kicker"` のような説明文。
+    pub description: String,
+}
+
+const VALUE_NAMES: [&str; 13] = [
+    "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine", "Ten", "Jack", "Queen",
+    "King", "Ace",
+];
+
+/// [`HandDetail::best_five`] の先頭から何枚が役の札か（残りがキッカー）。
+/// 引数はカテゴリ番号（[`rank_index`]）。
+pub fn made_card_count(index: usize) -> usize {
+    match index {
+        0 => 1,
+        1 => 2,
+        3 => 3,
+        2 | 7 => 4,
+        _ => 5,
+    }
+}
+
+/// `cards`（最大 7 枚）から `rank` を作る 5 枚を取り出し、表示用に並べて説明を付ける。
+///
+/// `rank` は `cards.rank()` の結果を渡す。同じ役になる 5 枚が複数あるときは
+/// 入力順で最初に見つかった組を使う。
+pub fn hand_detail(cards: &[Card], rank: &Rank) -> HandDetail {
+    let mut best_five = if cards.len() <= 5 {
+        cards.to_vec()
+    } else {
+        (0u32..1 << cards.len())
+            .filter(|mask| mask.count_ones() == 5)
+            .map(|mask| {
+                (0..cards.len())
+                    .filter(|i| mask & (1 << i) != 0)
+                    .map(|i| cards[i])
+                    .collect::<Vec<_>>()
+            })
+            .find(|five| five.as_slice().rank() == *rank)
+            .unwrap_or_else(|| cards[..5].to_vec())
+    };
+
+    let mut counts = [0u8; 13];
+    for c in &best_five {
+        counts[c.value as usize] += 1;
+    }
+    best_five.sort_by_key(|c| {
+        (
+            std::cmp::Reverse(counts[c.value as usize]),
+            std::cmp::Reverse(c.value),
+            c.suit,
+        )
+    });
+    let index = rank_index(rank);
+    let is_wheel = best_five.len() == 5
+        && best_five[0].value == Value::Ace
+        && best_five[1].value == Value::Five;
+    if matches!(index, 4 | 8) && is_wheel {
+        best_five.rotate_left(1);
+    }
+
+    let made = made_card_count(index);
+    let kickers = best_five
+        .iter()
+        .skip(made)
+        .map(|c| c.value as u32)
+        .collect();
+
+    let name = |i: usize| VALUE_NAMES[best_five[i].value as usize];
+    let plural = |i: usize| match best_five[i].value {
+        Value::Six => "Sixes".to_string(),
+        v => format!("{}s", VALUE_NAMES[v as usize]),
+    };
+    let mut description = match index {
+        0 => format!("{}, {}", RANK_LABELS[index], name(0)),
+        1 | 3 | 7 => format!("{}, {}", RANK_LABELS[index], plural(0)),
+        2 => format!("{}, {} and {}", RANK_LABELS[index], plural(0), plural(2)),
+        6 => format!(
+            "{}, {} full of {}",
+            RANK_LABELS[index],
+            plural(0),
+            plural(3)
+        ),
+        _ => format!("{}, {} high", RANK_LABELS[index], name(0)),
+    };
+    if made < best_five.len() {
+        description.push_str(&format!(", {} kicker", name(made)));
+    }
+
+    HandDetail {
+        best_five,
+        kickers,
+        description,
+    }
+}
+
 /// 役カテゴリ別の集計バケット。win/tie/lose × 9 カテゴリ。
 ///
 /// hero 側のランク分布を「勝ったとき」「タイ」「負けたとき」で分けて
 /// 数え上げるためのコンテナ。 [`crate::dto::RankOutcomeResults`] に
 /// 変換して JS へ返す。
-#[derive(Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RankBuckets {
     pub win: [u32; 9],
     pub tie: [u32; 9],
     pub lose: [u32; 9],
 }
+
+impl RankBuckets {
+    /// 別の集計を加算する。単純なカウンタの和なので、どの順で合わせても同じ結果になる。
+    pub fn merge(&mut self, other: &RankBuckets) {
+        for i in 0..9 {
+            self.win[i] = self.win[i].saturating_add(other.win[i]);
+            self.tie[i] = self.tie[i].saturating_add(other.tie[i]);
+            self.lose[i] = self.lose[i].saturating_add(other.lose[i]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_cards;
+
+    #[test]
+    fn rank_buckets_merge_adds_every_counter() {
+        let mut a = RankBuckets::default();
+        a.win[0] = 3;
+        a.tie[4] = 1;
+        a.lose[8] = u32::MAX - 1;
+        let mut b = RankBuckets::default();
+        b.win[0] = 2;
+        b.win[1] = 7;
+        b.lose[8] = 5;
+        a.merge(&b);
+        assert_eq!(a.win[..2], [5, 7]);
+        assert_eq!(a.tie[4], 1);
+        // 溢れる分は飽和する
+        assert_eq!(a.lose[8], u32::MAX);
+
+        let bytes = postcard::to_allocvec(&a).unwrap();
+        assert_eq!(postcard::from_bytes::<RankBuckets>(&bytes).unwrap(), a);
+    }
+
+    #[test]
+    fn hand_detail_orders_cards_and_describes_hand() {
+        let cases = [
+            (
+                "Kh 5d Qs Ks 5c 2h 3d",
+                "Ks Kh 5c 5d Qs",
+                vec![10],
+                "Two Pair, Kings and Fives, Queen kicker",
+            ),
+            (
+                "Ah 2d 3c 4s 5h 9d Kc",
+                "5h 4s 3c 2d Ah",
+                vec![],
+                "Straight, Five high",
+            ),
+            (
+                "5c Kd Ks 5h 5s 2c Kh",
+                "Ks Kh Kd 5s 5c",
+                vec![],
+                "Full House, Kings full of Fives",
+            ),
+            (
+                "6h 6d As 9c",
+                "6h 6d As 9c",
+                vec![12, 7],
+                "One Pair, Sixes, Ace kicker",
+            ),
+            (
+                "As Kd 9c 7h 4s 3d 2c",
+                "As Kd 9c 7h 4s",
+                vec![11, 7, 5, 2],
+                "High Card, Ace, King kicker",
+            ),
+        ];
+        for (input, best, kickers, description) in cases {
+            let cards = parse_cards(input).unwrap();
+            let detail = hand_detail(&cards, &cards.as_slice().rank());
+            let shown: Vec<String> = detail
+                .best_five
+                .iter()
+                .map(crate::cards::card_to_str)
+                .collect();
+            assert_eq!(shown.join(" "), best, "{input}");
+            assert_eq!(detail.kickers, kickers, "{input}");
+            assert_eq!(detail.description, description);
+        }
+    }
+}
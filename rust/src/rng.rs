@@ -11,3 +11,17 @@ use rand_chacha::ChaCha8Rng;
 pub fn seeded_rng(seed: u64) -> ChaCha8Rng {
     ChaCha8Rng::seed_from_u64(seed)
 }
+
+/// ユーザー指定のシードから、作業単位 `stream` ごとの独立したサブシードを導出する。
+///
+/// SplitMix64 の 1 ステップ分の混合関数。作業単位の番号だけで決まるので、
+/// どのスレッドがどの単位を処理しても同じ乱数列になる。
+///
+/// `rust-wasm/src/sim/rng.rs` の同名関数は意図的な複製（`crate::parallel` のモジュール説明を参照）。
+#[inline]
+pub fn derive_seed(seed: u64, stream: u64) -> u64 {
+    let mut z = seed.wrapping_add(stream.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,332 @@
+//! ショーダウンの精算。メインポット・サイドポットを組み、ポットごとに勝者へ配る。
+//!
+//! - ポットは各プレイヤーがハンド全体で出した額（アンティ・ブラインド込み）の段ごとに分け、
+//!   争えるプレイヤーが同じになる段はまとめる。先頭がメインポット
+//! - フォールドしたプレイヤーの出した額もポットに入るが、争う権利はない。フォールドした
+//!   プレイヤーしか届いていない段は次の段へ（最上段なら直前のポットへ）持ち越す
+//! - 誰もコールしていない額は、出した本人だけが争うポットになるので本人に戻る
+//! - 割り切れない額は [`OddChipRule`] で分ける
+//!
+//! フォールドしていないのが 1 人なら役を比べないので、ハンドもボードも要らない。
+
+use rs_poker::core::{Card, Rank, Rankable};
+
+use crate::approx::EPSILON;
+use crate::cards::card_to_str;
+use crate::rank::hand_detail;
+
+/// 1 プレイヤー分の入力。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Seat {
+    /// ハンド全体でポットに出した額。
+    pub contribution: f64,
+    pub folded: bool,
+    /// ホールカード。フォールドしたプレイヤーは `None` でよい。
+    pub hand: Option<[Card; 2]>,
+}
+
+/// 割り切れないポットの分け方。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OddChipRule {
+    /// 最小単位を考えず、勝者の人数で等分する。
+    SplitEvenly,
+    /// `chip` 単位で等分し、余ったチップはボタンの左の席から時計回りに近い勝者へ 1 枚ずつ配る。
+    LeftOfButton { button: usize, chip: f64 },
+}
+
+/// 精算した 1 つのポット。
+#[derive(Clone, Debug, PartialEq)]
+pub struct PotAward {
+    pub amount: f64,
+    /// このポットを争えたプレイヤー。
+    pub eligible: Vec<usize>,
+    pub winners: Vec<usize>,
+    /// `winners` と同じ並びの取り分。
+    pub shares: Vec<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settlement {
+    pub pots: Vec<PotAward>,
+    /// 各プレイヤーが受け取る額。
+    pub winnings: Vec<f64>,
+    /// 受け取る額 − 出した額。
+    pub net: Vec<f64>,
+    /// ショーダウンした各プレイヤーの役の説明。役を比べなかったときは全員 `None`。
+    pub showdown: Vec<Option<String>>,
+}
+
+/// 出した額からポットを組む。`(額, 争えるプレイヤー)` の並びで、先頭がメインポット。
+pub fn build_pots(seats: &[Seat]) -> Vec<(f64, Vec<usize>)> {
+    let mut levels: Vec<f64> = seats
+        .iter()
+        .map(|s| s.contribution)
+        .filter(|&c| c > EPSILON)
+        .collect();
+    levels.sort_by(f64::total_cmp);
+    levels.dedup_by(|a, b| (*a - *b).abs() <= EPSILON);
+
+    let mut pots: Vec<(f64, Vec<usize>)> = Vec::new();
+    let mut prev = 0.0;
+    let mut carry = 0.0;
+    for level in levels {
+        let amount = carry
+            + seats
+                .iter()
+                .map(|s| s.contribution.min(level) - s.contribution.min(prev))
+                .sum::<f64>();
+        prev = level;
+        let eligible: Vec<usize> = (0..seats.len())
+            .filter(|&i| !seats[i].folded && seats[i].contribution >= level - EPSILON)
+            .collect();
+        if eligible.is_empty() {
+            carry = amount;
+            continue;
+        }
+        carry = 0.0;
+        match pots.last_mut() {
+            Some(last) if last.1 == eligible => last.0 += amount,
+            _ => pots.push((amount, eligible)),
+        }
+    }
+    if let Some(last) = pots.last_mut() {
+        last.0 += carry;
+    }
+    pots
+}
+
+/// ポットを組み、各ポットを争えるプレイヤーのうち最も強いハンドに配る。
+///
+/// 2 人以上がショーダウンするときは、ボード 5 枚と、フォールドしていない全員のハンドが要る。
+pub fn settle(seats: &[Seat], board: &[Card], rule: OddChipRule) -> Result<Settlement, String> {
+    validate(seats, rule)?;
+    let n = seats.len();
+    let contested = seats.iter().filter(|s| !s.folded).count() > 1;
+    let mut ranks: Vec<Option<Rank>> = vec![None; n];
+    let mut showdown = vec![None; n];
+    if contested {
+        validate_cards(seats, board)?;
+        for (i, seat) in seats.iter().enumerate() {
+            if let (false, Some(hand)) = (seat.folded, seat.hand) {
+                let mut cards = board.to_vec();
+                cards.extend(hand);
+                let rank = cards.as_slice().rank();
+                showdown[i] = Some(hand_detail(&cards, &rank).description);
+                ranks[i] = Some(rank);
+            }
+        }
+    }
+
+    let mut winnings = vec![0.0; n];
+    let pots = build_pots(seats)
+        .into_iter()
+        .map(|(amount, eligible)| {
+            let best = eligible.iter().filter_map(|&i| ranks[i]).max();
+            let winners: Vec<usize> = eligible
+                .iter()
+                .copied()
+                .filter(|&i| ranks[i] == best)
+                .collect();
+            let shares = split(amount, &winners, n, rule);
+            for (&w, &share) in winners.iter().zip(&shares) {
+                winnings[w] += share;
+            }
+            PotAward {
+                amount,
+                eligible,
+                winners,
+                shares,
+            }
+        })
+        .collect();
+    let net = winnings
+        .iter()
+        .zip(seats)
+        .map(|(w, s)| w - s.contribution)
+        .collect();
+    Ok(Settlement {
+        pots,
+        winnings,
+        net,
+        showdown,
+    })
+}
+
+fn split(amount: f64, winners: &[usize], n: usize, rule: OddChipRule) -> Vec<f64> {
+    let k = winners.len();
+    match rule {
+        OddChipRule::SplitEvenly => vec![amount / k as f64; k],
+        OddChipRule::LeftOfButton { button, chip } => {
+            let units = (amount / chip).round() as u64;
+            let mut shares = vec![(units / k as u64) as f64 * chip; k];
+            // ボタンの左の席を 0 とした時計回りの距離が近い順
+            let mut order: Vec<usize> = (0..k).collect();
+            order.sort_by_key(|&j| (winners[j] + n - button - 1) % n);
+            for &j in &order[..(units % k as u64) as usize] {
+                shares[j] += chip;
+            }
+            shares
+        }
+    }
+}
+
+fn validate(seats: &[Seat], rule: OddChipRule) -> Result<(), String> {
+    if seats.len() < 2 {
+        return Err(format!("expected at least 2 players, got {}", seats.len()));
+    }
+    if let Some(i) = seats
+        .iter()
+        .position(|s| !s.contribution.is_finite() || s.contribution < 0.0)
+    {
+        return Err(format!(
+            "player {} has an invalid contribution {}",
+            i, seats[i].contribution
+        ));
+    }
+    if seats.iter().all(|s| s.folded) {
+        return Err("every player folded".to_string());
+    }
+    if let OddChipRule::LeftOfButton { button, chip } = rule {
+        if button >= seats.len() {
+            return Err(format!(
+                "button {} is out of range for {} players",
+                button,
+                seats.len()
+            ));
+        }
+        if !chip.is_finite() || chip <= 0.0 {
+            return Err(format!("chip must be positive, got {}", chip));
+        }
+        if let Some(i) = seats.iter().position(|s| {
+            let units = s.contribution / chip;
+            (units.round() - units).abs() * chip > EPSILON
+        }) {
+            return Err(format!(
+                "player {} contributed {}, which is not a multiple of the chip {}",
+                i, seats[i].contribution, chip
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn validate_cards(seats: &[Seat], board: &[Card]) -> Result<(), String> {
+    if board.len() != 5 {
+        return Err(format!(
+            "a showdown needs 5 board cards, got {}",
+            board.len()
+        ));
+    }
+    if let Some(i) = seats.iter().position(|s| !s.folded && s.hand.is_none()) {
+        return Err(format!("player {} goes to showdown without a hand", i));
+    }
+    let mut seen = board.to_vec();
+    for hand in seats.iter().filter_map(|s| s.hand) {
+        seen.extend(hand);
+    }
+    for (i, c) in seen.iter().enumerate() {
+        if seen[..i].contains(c) {
+            return Err(format!("duplicate card {}", card_to_str(c)));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_cards_ordered, parse_two_cards};
+
+    fn seat(contribution: f64, folded: bool, hand: &str) -> Seat {
+        let (a, b) = parse_two_cards(hand).unwrap();
+        Seat {
+            contribution,
+            folded,
+            hand: Some([a, b]),
+        }
+    }
+
+    #[test]
+    fn builds_side_pots_for_uneven_all_ins() {
+        let board = parse_cards_ordered("2c7d9hJsKc").unwrap();
+        // 0: 短いオールインで最強、1: 中くらいのオールイン、2: 1 より強い、3: 途中でフォールド
+        let seats = [
+            seat(20.0, false, "KhKd"),
+            seat(50.0, false, "JhJd"),
+            seat(80.0, false, "AsKs"),
+            seat(30.0, true, "QhQd"),
+        ];
+        let r = settle(&seats, &board, OddChipRule::SplitEvenly).unwrap();
+        let pots: Vec<(f64, Vec<usize>, Vec<usize>)> = r
+            .pots
+            .iter()
+            .map(|p| (p.amount, p.eligible.clone(), p.winners.clone()))
+            .collect();
+        assert_eq!(
+            pots,
+            [
+                (80.0, vec![0, 1, 2], vec![0]),
+                (70.0, vec![1, 2], vec![1]),
+                // 誰もコールしていない 30 は 2 に戻る
+                (30.0, vec![2], vec![2]),
+            ]
+        );
+        assert_eq!(r.winnings, [80.0, 70.0, 30.0, 0.0]);
+        assert_eq!(r.net, [60.0, 20.0, -50.0, -30.0]);
+        assert_eq!(r.showdown[3], None);
+        assert!(r.showdown[0]
+            .as_deref()
+            .unwrap()
+            .starts_with("Three of a Kind"));
+
+        // フォールドで 1 人残ればハンドもボードも要らない
+        let seats = [
+            Seat {
+                contribution: 3.0,
+                folded: false,
+                hand: None,
+            },
+            Seat {
+                contribution: 1.0,
+                folded: true,
+                hand: None,
+            },
+        ];
+        let r = settle(&seats, &[], OddChipRule::SplitEvenly).unwrap();
+        assert_eq!(r.winnings, [4.0, 0.0]);
+        assert!(settle(&seats[..1], &[], OddChipRule::SplitEvenly).is_err());
+    }
+
+    #[test]
+    fn gives_odd_chips_left_of_the_button() {
+        // 全員ボードのストレートでスプリット
+        let board = parse_cards_ordered("Th9s8d7c6h").unwrap();
+        let seats = [
+            seat(5.0, false, "2c3c"),
+            seat(5.0, false, "2d3d"),
+            seat(5.0, false, "2h3h"),
+        ];
+        let rule = |button| OddChipRule::LeftOfButton { button, chip: 1.0 };
+        // 3 人スプリットの 15 は割り切れる
+        let r = settle(&seats, &board, rule(0)).unwrap();
+        assert_eq!(r.winnings, [5.0, 5.0, 5.0]);
+
+        let seats = [seats[0], seats[1], seat(5.0, true, "AsAd")];
+        // 15 を 2 人で 7 + 7 + 余り 1。ボタンが 0 なら左は 1
+        let r = settle(&seats, &board, rule(0)).unwrap();
+        assert_eq!(r.winnings, [7.0, 8.0, 0.0]);
+        assert_eq!(r.pots[0].shares, [7.0, 8.0]);
+        // ボタンが 1 なら、左の 2 はフォールドしているので次の 0
+        let r = settle(&seats, &board, rule(1)).unwrap();
+        assert_eq!(r.winnings, [8.0, 7.0, 0.0]);
+        let r = settle(&seats, &board, OddChipRule::SplitEvenly).unwrap();
+        assert_eq!(r.winnings, [7.5, 7.5, 0.0]);
+
+        let half = OddChipRule::LeftOfButton {
+            button: 0,
+            chip: 2.0,
+        };
+        assert!(settle(&seats, &board, half).is_err());
+        assert!(settle(&seats, &board[..4], rule(0)).is_err());
+    }
+}
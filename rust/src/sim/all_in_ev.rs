@@ -0,0 +1,358 @@
+//! マルチウェイのオールインでの各プレイヤーの期待リターン（サイドポット込み）。
+//!
+//! 各プレイヤーのハンドかレンジ（[`super::range_vs_range`] と同じレンジ式）と、ハンド全体で
+//! 出した額から [`crate::settlement::build_pots`] でポットを組み、ランアウトごとに
+//! ポット単位で勝者を決める。ショートスタックはメインポットしか取れないので、
+//! 「equity × ポット全体」より正確な期待値になる。
+//!
+//! - `players` は席順のセミコロン区切り。空の席はフォールド済み（出した額はデッドマネー）
+//! - ボードが 4 枚以上で、コンボの組み合わせが [`EXACT_DEAL_LIMIT`] 以下なら全列挙して厳密に数える
+//! - それ以外は `trials` 回の Monte Carlo。コンボは一様に選び、カードが衝突した配りは引き直す
+//! - ポットを分けるときは端数も等分する
+
+use rand::prelude::IndexedRandom;
+use rand::Rng;
+use rs_poker::core::{Card, Rank};
+
+use crate::cards::deck_minus;
+use crate::dto::{AllInEvPayload, AllInEvPlayer, AllInEvPot};
+use crate::parser::{parse_cards_ordered, parse_range};
+use crate::rng::seeded_rng;
+use crate::settlement::{build_pots, Seat};
+use crate::sim::evaluate_seven;
+
+/// 全列挙するコンボの組み合わせ（各プレイヤーのコンボ数の積）の上限。
+pub const EXACT_DEAL_LIMIT: usize = 100_000;
+
+/// 衝突しない配りが見つかるまでに引き直す上限。
+const MAX_REDRAWS: u32 = 10_000;
+
+pub fn run(
+    players: &str,
+    contributions: &[f64],
+    board: &str,
+    trials: u32,
+    seed: u64,
+) -> Result<AllInEvPayload, String> {
+    let setup = Setup::parse(players, contributions, board)?;
+    let mut acc = Accumulator::new(&setup.pots, contributions.len());
+    let exact = setup.board.len() >= 4
+        && setup
+            .ranges
+            .iter()
+            .flatten()
+            .try_fold(1usize, |n, r| n.checked_mul(r.len()))
+            .is_some_and(|n| n <= EXACT_DEAL_LIMIT);
+    if exact {
+        setup.enumerate(&mut acc);
+        if acc.samples == 0 {
+            return Err("no non-conflicting deal exists for the given ranges".to_string());
+        }
+    } else {
+        setup.sample(trials, seed, &mut acc)?;
+    }
+    Ok(setup.into_payload(acc, exact))
+}
+
+struct Setup {
+    entries: Vec<String>,
+    contributions: Vec<f64>,
+    /// 席ごとのボードと衝突しないコンボ。フォールドした席は `None`。
+    ranges: Vec<Option<Vec<(Card, Card)>>>,
+    board: Vec<Card>,
+    pots: Vec<(f64, Vec<usize>)>,
+}
+
+impl Setup {
+    fn parse(players: &str, contributions: &[f64], board: &str) -> Result<Self, String> {
+        let entries: Vec<String> = players.split(';').map(|p| p.trim().to_string()).collect();
+        if entries.len() != contributions.len() {
+            return Err(format!(
+                "expected {} players for {} contributions, got {}",
+                contributions.len(),
+                contributions.len(),
+                entries.len()
+            ));
+        }
+        if let Some(c) = contributions.iter().find(|c| !c.is_finite() || **c < 0.0) {
+            return Err(format!("invalid contribution {}", c));
+        }
+        let board = parse_cards_ordered(board)?;
+        if board.len() > 5 {
+            return Err(format!(
+                "expected at most 5 board cards, got {}",
+                board.len()
+            ));
+        }
+        for (i, c) in board.iter().enumerate() {
+            if board[..i].contains(c) {
+                return Err("duplicate card in board".to_string());
+            }
+        }
+
+        let mut ranges = Vec::with_capacity(entries.len());
+        for entry in &entries {
+            if entry.is_empty() {
+                ranges.push(None);
+                continue;
+            }
+            let combos: Vec<(Card, Card)> = parse_range(entry)?
+                .into_iter()
+                .filter(|(a, b)| !board.contains(a) && !board.contains(b))
+                .collect();
+            if combos.is_empty() {
+                return Err(format!("'{}' has no combos left after the board", entry));
+            }
+            ranges.push(Some(combos));
+        }
+        if ranges.iter().flatten().count() < 2 {
+            return Err("at least 2 players need a hand or range".to_string());
+        }
+
+        let seats: Vec<Seat> = contributions
+            .iter()
+            .zip(&ranges)
+            .map(|(&contribution, range)| Seat {
+                contribution,
+                folded: range.is_none(),
+                hand: None,
+            })
+            .collect();
+        Ok(Setup {
+            entries,
+            contributions: contributions.to_vec(),
+            ranges,
+            pots: build_pots(&seats),
+            board,
+        })
+    }
+
+    /// コンボの組み合わせと残りのランアウトを全列挙する。
+    fn enumerate(&self, acc: &mut Accumulator) {
+        let mut deal = vec![None; self.ranges.len()];
+        self.enumerate_from(0, &mut deal, acc);
+    }
+
+    fn enumerate_from(
+        &self,
+        seat: usize,
+        deal: &mut [Option<(Card, Card)>],
+        acc: &mut Accumulator,
+    ) {
+        if seat == self.ranges.len() {
+            let used = used_cards(&self.board, deal);
+            let mut full = self.board.clone();
+            if full.len() == 5 {
+                acc.add(&self.pots, &showdown_ranks(&full, deal));
+            } else {
+                for river in deck_minus(&used) {
+                    full.truncate(4);
+                    full.push(river);
+                    acc.add(&self.pots, &showdown_ranks(&full, deal));
+                }
+            }
+            return;
+        }
+        let Some(range) = &self.ranges[seat] else {
+            return self.enumerate_from(seat + 1, deal, acc);
+        };
+        for &(a, b) in range {
+            if deal
+                .iter()
+                .flatten()
+                .any(|&(x, y)| [x, y].iter().any(|c| *c == a || *c == b))
+            {
+                continue;
+            }
+            deal[seat] = Some((a, b));
+            self.enumerate_from(seat + 1, deal, acc);
+        }
+        deal[seat] = None;
+    }
+
+    fn sample(&self, trials: u32, seed: u64, acc: &mut Accumulator) -> Result<(), String> {
+        let mut rng = seeded_rng(seed);
+        let missing = 5 - self.board.len();
+        for _ in 0..trials.max(1) {
+            let deal = self.draw_deal(&mut rng)?;
+            let used = used_cards(&self.board, &deal);
+            let deck = deck_minus(&used);
+            let mut full = self.board.clone();
+            full.extend(deck.sample(&mut rng, missing).copied());
+            acc.add(&self.pots, &showdown_ranks(&full, &deal));
+        }
+        Ok(())
+    }
+
+    /// 各席のコンボを独立に選び、衝突していたら全部引き直す（偏りのない棄却法）。
+    fn draw_deal(&self, rng: &mut impl Rng) -> Result<Vec<Option<(Card, Card)>>, String> {
+        for _ in 0..MAX_REDRAWS {
+            let deal: Vec<Option<(Card, Card)>> = self
+                .ranges
+                .iter()
+                .map(|r| r.as_ref().and_then(|r| r.choose(rng).copied()))
+                .collect();
+            let cards: Vec<Card> = deal.iter().flatten().flat_map(|&(a, b)| [a, b]).collect();
+            if cards
+                .iter()
+                .enumerate()
+                .all(|(i, c)| !cards[..i].contains(c))
+            {
+                return Ok(deal);
+            }
+        }
+        Err("could not deal non-conflicting hands from the given ranges".to_string())
+    }
+
+    fn into_payload(self, acc: Accumulator, exact: bool) -> AllInEvPayload {
+        let samples = acc.samples as f64;
+        let players = (0..self.entries.len())
+            .map(|i| {
+                let expected_return = acc.returns[i] / samples;
+                AllInEvPlayer {
+                    hand: self.entries[i].clone(),
+                    folded: self.ranges[i].is_none(),
+                    contribution: self.contributions[i],
+                    expected_return,
+                    net_ev: expected_return - self.contributions[i],
+                }
+            })
+            .collect();
+        let pots = self
+            .pots
+            .into_iter()
+            .zip(acc.pot_shares)
+            .map(|((amount, eligible), shares)| AllInEvPot {
+                amount,
+                eligible,
+                equity: shares.iter().map(|s| s / samples).collect(),
+            })
+            .collect();
+        AllInEvPayload {
+            players,
+            pots,
+            samples: acc.samples,
+            exact,
+        }
+    }
+}
+
+/// 期待リターンとポットごとの取り分の合計。
+struct Accumulator {
+    returns: Vec<f64>,
+    /// ポットごとに、`eligible` と同じ並びで取り分の割合を足したもの。
+    pot_shares: Vec<Vec<f64>>,
+    samples: u64,
+}
+
+impl Accumulator {
+    fn new(pots: &[(f64, Vec<usize>)], players: usize) -> Self {
+        Accumulator {
+            returns: vec![0.0; players],
+            pot_shares: pots.iter().map(|(_, e)| vec![0.0; e.len()]).collect(),
+            samples: 0,
+        }
+    }
+
+    fn add(&mut self, pots: &[(f64, Vec<usize>)], ranks: &[Option<Rank>]) {
+        self.samples += 1;
+        for ((amount, eligible), shares) in pots.iter().zip(&mut self.pot_shares) {
+            let best = eligible.iter().filter_map(|&i| ranks[i]).max();
+            let winners = eligible.iter().filter(|&&i| ranks[i] == best).count() as f64;
+            for (j, &i) in eligible.iter().enumerate() {
+                if ranks[i] == best {
+                    shares[j] += 1.0 / winners;
+                    self.returns[i] += amount / winners;
+                }
+            }
+        }
+    }
+}
+
+fn used_cards(board: &[Card], deal: &[Option<(Card, Card)>]) -> Vec<Card> {
+    let mut used = board.to_vec();
+    used.extend(deal.iter().flatten().flat_map(|&(a, b)| [a, b]));
+    used
+}
+
+fn showdown_ranks(board: &[Card], deal: &[Option<(Card, Card)>]) -> Vec<Option<Rank>> {
+    deal.iter()
+        .map(|h| h.map(|(a, b)| evaluate_seven(board, a, b)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_two_cards;
+    use crate::settlement::{settle, OddChipRule};
+
+    #[test]
+    fn river_matches_settlement_and_turn_is_exact() {
+        let hands = ["KhKd", "JhJd", "AsKs", ""];
+        let contributions = [20.0, 50.0, 80.0, 30.0];
+        let board = "2c7d9hJsKc";
+        let r = run(&hands.join(";"), &contributions, board, 1, 0).unwrap();
+        assert!(r.exact);
+        assert_eq!(r.samples, 1);
+
+        let seats: Vec<Seat> = hands
+            .iter()
+            .zip(contributions)
+            .map(|(h, contribution)| Seat {
+                contribution,
+                folded: h.is_empty(),
+                hand: parse_two_cards(h).ok().map(|(a, b)| [a, b]),
+            })
+            .collect();
+        let board_cards = parse_cards_ordered(board).unwrap();
+        let settled = settle(&seats, &board_cards, OddChipRule::SplitEvenly).unwrap();
+        let returns: Vec<f64> = r.players.iter().map(|p| p.expected_return).collect();
+        assert_eq!(returns, settled.winnings);
+        assert_eq!(r.players[3].net_ev, -30.0);
+
+        // ターンからは残り 42 枚のリバーを全部数える
+        let r = run(&hands.join(";"), &contributions, "2c7d9hJs", 1, 0).unwrap();
+        assert!(r.exact);
+        assert_eq!(r.samples, 42);
+        let total: f64 = r.players.iter().map(|p| p.expected_return).sum();
+        assert!((total - 180.0).abs() < 1e-9);
+        // ショートの KK はメインポット（80）しか取れない。JJ はセットで、負けるのは Kc だけ
+        assert!(r.players[0].expected_return <= 80.0);
+        assert!((r.pots[0].equity[1] - 41.0 / 42.0).abs() < 1e-12);
+        for pot in &r.pots {
+            assert!((pot.equity.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn exact_path_rejects_ranges_without_a_valid_deal() {
+        let Err(err) = run("AsKs; AsKs", &[10.0, 10.0], "2c7d9hJs", 1, 0) else {
+            panic!("conflicting hands must not produce an EV");
+        };
+        assert!(err.contains("non-conflicting"), "{}", err);
+        assert!(run("AsKs; AsKs", &[10.0, 10.0], "2c7d9hJsKc", 1, 0).is_err());
+    }
+
+    #[test]
+    fn samples_ranges_preflop() {
+        let players = "AA; KK; QQ+,AK";
+        let contributions = [10.0, 100.0, 100.0];
+        let a = run(players, &contributions, "", 2000, 5).unwrap();
+        let b = run(players, &contributions, "", 2000, 5).unwrap();
+        assert!(!a.exact);
+        assert_eq!(a.samples, 2000);
+        assert_eq!(a.players[0].expected_return, b.players[0].expected_return);
+
+        // メインポット 30 と、KK と QQ+,AK のサイドポット 180
+        assert_eq!(a.pots.len(), 2);
+        assert_eq!(a.pots[1].eligible, [1, 2]);
+        let total: f64 = a.players.iter().map(|p| p.expected_return).sum();
+        assert!((total - 210.0).abs() < 1e-6);
+        assert!(a.players[0].expected_return <= 30.0);
+        assert!(a.players[1].net_ev > 0.0);
+
+        assert!(run("AA", &[10.0], "", 10, 0).is_err());
+        assert!(run("AA; KK", &[10.0, 10.0], "Ah Ad As Ac", 10, 0).is_err());
+    }
+}
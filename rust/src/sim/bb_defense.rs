@@ -0,0 +1,134 @@
+//! BB ディフェンス: オープンに対する BB のハンドクラスごとのコール / フォールドと MDF。
+//!
+//! BB の候補レンジとオープンレンジを [`super::range_vs_range`] と同じ全コンボペアの
+//! Monte Carlo で対戦させ、コンボの集計をハンドクラス（169 種）ごとにまとめる。
+//!
+//! 額はすべて BB 単位（BB 自身のブラインドが 1）。
+//!
+//! - `pot` はオープン前にポットにある額（ブラインドとアンテ。BB のブラインドを含み、
+//!   レイザーが置いていたブラインドは含めない）、`open_size` はレイザーの合計ベット額
+//! - コール額は `open_size − 1`、コール後のポットは `pot + open_size + コール額`
+//! - ポットオッズの必要 equity はコール額 / コール後のポット。実現率 `realization` を掛けた
+//!   equity がこれ以上ならコール
+//! - MDF は `pot / (pot + open_size)`。ディフェンス率は BB の全 1326 コンボのうち
+//!   コールと判定したコンボの割合（BB 以外はフォールド済みとみなす）
+
+use std::collections::HashMap;
+
+use crate::cards::combo_key;
+use crate::dto::{BbDefenseClass, BbDefensePayload};
+use crate::hand_class::HandClass;
+use crate::parser::parse_range;
+use crate::sim::range_vs_range::{accumulate_combos, ComboStats};
+
+/// プリフロップの全コンボ数。
+const ALL_COMBOS: f64 = 1326.0;
+
+pub fn run(
+    open_range: &str,
+    open_size: f64,
+    pot: f64,
+    defend_range: &str,
+    realization: f64,
+    trials: u32,
+    seed: u64,
+) -> Result<BbDefensePayload, String> {
+    if !open_size.is_finite() || open_size <= 1.0 {
+        return Err("open size must be larger than the big blind (1)".into());
+    }
+    if !pot.is_finite() || pot < 1.0 {
+        return Err("pot must include the big blind (1)".into());
+    }
+    if !realization.is_finite() || realization <= 0.0 {
+        return Err("equity realization must be positive".into());
+    }
+    let mut defend = parse_range(defend_range)?;
+    defend.dedup_by_key(|&mut (a, b)| combo_key(a, b));
+    let mut open = parse_range(open_range)?;
+    open.dedup_by_key(|&mut (a, b)| combo_key(a, b));
+    if defend.is_empty() || open.is_empty() {
+        return Err("both ranges must include at least 1 hand".into());
+    }
+
+    let acc = accumulate_combos(defend.clone(), open, Vec::new(), trials, seed)?;
+    let mut by_class: HashMap<HandClass, (usize, ComboStats)> = HashMap::new();
+    for (&(a, b), stats) in defend.iter().zip(&acc.hero) {
+        let (combos, total) = by_class.entry(HandClass::from_combo(a, b)).or_default();
+        *combos += 1;
+        total.merge(stats);
+    }
+
+    let call = open_size - 1.0;
+    let final_pot = pot + open_size + call;
+    let pot_odds = call / final_pot;
+    let mut defend_combos = 0;
+    let classes = HandClass::all()
+        .into_iter()
+        .filter_map(|class| {
+            let &(combos, stats) = by_class.get(&class)?;
+            let equity = stats.equity();
+            let realized_equity = equity * realization;
+            let defends = realized_equity >= pot_odds;
+            if defends {
+                defend_combos += combos;
+            }
+            Some(BbDefenseClass {
+                hand_class: class.to_string(),
+                combos,
+                equity,
+                realized_equity,
+                call_ev: realized_equity * final_pot - call,
+                action: if defends { "call" } else { "fold" }.to_string(),
+            })
+        })
+        .collect();
+
+    Ok(BbDefensePayload {
+        call_amount: call,
+        final_pot,
+        pot_odds,
+        required_equity: pot_odds / realization,
+        mdf: pot / (pot + open_size),
+        defend_frequency: defend_combos as f64 / ALL_COMBOS,
+        candidate_frequency: defend.len() as f64 / ALL_COMBOS,
+        classes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defends_strong_classes_and_folds_trash_against_a_tight_open() {
+        let r = run("QQ+,AKs", 2.5, 1.5, "AA,KK,72o,32o", 1.0, 4, 7).unwrap();
+        assert!((r.call_amount - 1.5).abs() < 1e-12);
+        assert!((r.final_pot - 5.5).abs() < 1e-12);
+        assert!((r.pot_odds - 1.5 / 5.5).abs() < 1e-12);
+        assert!((r.mdf - 1.5 / 4.0).abs() < 1e-12);
+
+        let names: Vec<&str> = r.classes.iter().map(|c| c.hand_class.as_str()).collect();
+        assert_eq!(names, ["AA", "KK", "72o", "32o"]);
+        let action = |name: &str| {
+            let c = r.classes.iter().find(|c| c.hand_class == name).unwrap();
+            (c.action.as_str(), c.combos)
+        };
+        assert_eq!(action("AA"), ("call", 6));
+        assert_eq!(action("KK"), ("call", 6));
+        assert_eq!(action("72o"), ("fold", 12));
+        assert_eq!(action("32o"), ("fold", 12));
+        assert!((r.defend_frequency - 12.0 / 1326.0).abs() < 1e-12);
+        assert!((r.candidate_frequency - 36.0 / 1326.0).abs() < 1e-12);
+        for c in &r.classes {
+            assert_eq!(c.call_ev > 0.0, c.action == "call", "{}", c.hand_class);
+        }
+
+        // 実現率が低いと、同じ equity でも必要なラインを割る
+        let r = run("QQ+,AKs", 2.5, 1.5, "KK", 0.3, 4, 7).unwrap();
+        assert_eq!(r.classes[0].action, "fold");
+        assert!((r.required_equity - r.pot_odds / 0.3).abs() < 1e-12);
+
+        assert!(run("AA", 1.0, 1.5, "KK", 1.0, 1, 1).is_err());
+        assert!(run("AA", 2.5, 1.5, "", 1.0, 1, 1).is_err());
+    }
+}
@@ -0,0 +1,36 @@
+//! [`crate::deal`] の WASM エクスポート用ロジック。
+//!
+//! デッキの順番と検証結果を、JS に返す文字列の形にする。
+
+use crate::cards::card_to_str;
+use crate::deal::{deck_hash, shuffled_deck, verify as verify_cards};
+use crate::dto::{DealVerificationPayload, ShuffledDeckPayload};
+use crate::parser::parse_cards_ordered;
+
+/// seeds から決まる 52 枚の順番とそのハッシュ。
+pub fn deck(server_seed: &str, client_seed: &str, nonce: u64) -> ShuffledDeckPayload {
+    let deck = shuffled_deck(server_seed, client_seed, nonce);
+    ShuffledDeckPayload {
+        deck_hash: deck_hash(&deck),
+        cards: deck.iter().map(card_to_str).collect(),
+    }
+}
+
+/// `dealt` は配った順のカード（`"AsKd..."`）。
+pub fn verify(
+    commitment: &str,
+    server_seed: &str,
+    client_seed: &str,
+    nonce: u64,
+    dealt: &str,
+) -> Result<DealVerificationPayload, String> {
+    let dealt = parse_cards_ordered(dealt)?;
+    let v = verify_cards(commitment, server_seed, client_seed, nonce, &dealt);
+    Ok(DealVerificationPayload {
+        valid: v.is_valid(),
+        seed_matches: v.seed_matches,
+        mismatch_at: v.mismatch_at,
+        deck_hash: deck_hash(&v.deck),
+        deck: v.deck.iter().map(card_to_str).collect(),
+    })
+}
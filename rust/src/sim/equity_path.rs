@@ -0,0 +1,194 @@
+//! 1 つのランアウトに沿った、ストリートごとの hero equity の推移。
+//!
+//! hero 1 ハンド対「相手ハンドのリスト」（[`super::vs_list`] と同じ入力）で、
+//! プリフロップ → フロップ → ターン → リバーの equity を返す。`board` が 5 枚に
+//! 満たなければ残りをシードから引いて埋める。
+//!
+//! フロップ以降は残りのカードと相手コンボを全列挙して厳密に数える。
+//! ターン / リバーに来うる各カードの equity も返すので、「どのカードで equity が
+//! 大きく動くか」（ベスト / ワーストカード）を表示できる。フロップ / ターンの equity は
+//! 次のカードごとの集計（[`super::next_card_stats`]）の和から求める。
+//! プリフロップだけは組み合わせが多すぎるので `trials` 回の Monte Carlo。
+
+use std::cmp::Ordering;
+
+use rand::prelude::IndexedRandom;
+use rs_poker::core::Card;
+
+use crate::cards::{card_to_str, deck_minus, pair_string};
+use crate::dto::{CardEquity, EquityPathPayload, StreetEquity};
+use crate::parser::{parse_cards_ordered, parse_hands_list, parse_two_cards};
+use crate::rng::seeded_rng;
+use crate::sim::range_vs_range::ComboStats;
+use crate::sim::{evaluate_seven, next_card_stats};
+
+pub fn run(
+    hero: &str,
+    board: &str,
+    compare: &str,
+    trials: u32,
+    seed: u64,
+) -> Result<EquityPathPayload, String> {
+    let (h1, h2) = parse_two_cards(hero)?;
+    let mut runout = parse_cards_ordered(board)?;
+    if runout.len() > 5 {
+        return Err(format!(
+            "expected at most 5 board cards, got {}",
+            runout.len()
+        ));
+    }
+    let mut known = runout.clone();
+    known.extend([h1, h2]);
+    for (i, c) in known.iter().enumerate() {
+        if known[..i].contains(c) {
+            return Err("duplicate card between hero and board".to_string());
+        }
+    }
+    let villains: Vec<(Card, Card)> = parse_hands_list(compare)?
+        .into_iter()
+        .filter(|(a, b)| a != b && ![h1, h2].iter().any(|h| h == a || h == b))
+        .collect();
+    if villains.is_empty() {
+        return Err("no compare hands left after removing hero cards".to_string());
+    }
+
+    // 足りないボードを引く。全相手コンボが持っているカード（相手が 1 ハンドならその 2 枚）は
+    // 山に無いものとして扱う。
+    let mut rng = seeded_rng(seed);
+    let mut dead = known.clone();
+    for c in [villains[0].0, villains[0].1] {
+        if villains.iter().all(|&(a, b)| a == c || b == c) {
+            dead.push(c);
+        }
+    }
+    let deck = deck_minus(&dead);
+    let missing = 5 - runout.len();
+    let drawn: Vec<Card> = deck.sample(&mut rng, missing).copied().collect();
+    runout.extend(drawn);
+
+    let (flop, turn, river) = (&runout[..3], runout[3], runout[4]);
+    let turn_cards = next_card_stats((h1, h2), &villains, flop);
+    let mut flop_turn = flop.to_vec();
+    flop_turn.push(turn);
+    let river_cards = next_card_stats((h1, h2), &villains, &flop_turn);
+
+    // フロップ / ターンの equity は、次のカードごとの集計を合算したもの。
+    let sum = |cards: &[(Card, ComboStats)]| {
+        let mut total = ComboStats::default();
+        for (_, t) in cards {
+            total.merge(t);
+        }
+        total
+    };
+    let flop_equity = sum(&turn_cards).equity();
+    let turn_equity = sum(&river_cards).equity();
+    let river_equity = river_cards
+        .iter()
+        .find(|(c, _)| *c == river)
+        .map(|(_, t)| t.equity())
+        .unwrap_or(0.0);
+    let preflop_equity = preflop_equity((h1, h2), &villains, trials, &mut rng);
+
+    let streets = [
+        ("preflop", 0, preflop_equity, false),
+        ("flop", 3, flop_equity, true),
+        ("turn", 4, turn_equity, true),
+        ("river", 5, river_equity, true),
+    ];
+    let mut prev = preflop_equity;
+    let streets = streets
+        .into_iter()
+        .map(|(street, shown, equity, exact)| {
+            let delta = equity - prev;
+            prev = equity;
+            StreetEquity {
+                street: street.to_string(),
+                board: runout[..shown].iter().map(card_to_str).collect(),
+                equity,
+                delta,
+                exact,
+            }
+        })
+        .collect();
+
+    Ok(EquityPathPayload {
+        hand: pair_string(h1, h2),
+        runout: runout.iter().map(card_to_str).collect(),
+        streets,
+        turn_cards: to_card_equities(turn_cards, flop_equity),
+        river_cards: to_card_equities(river_cards, turn_equity),
+    })
+}
+
+/// 相手コンボを一様に選び、ボード 5 枚を引く MC。
+fn preflop_equity(
+    hero: (Card, Card),
+    villains: &[(Card, Card)],
+    trials: u32,
+    rng: &mut impl rand::Rng,
+) -> f64 {
+    let mut stats = ComboStats::default();
+    for _ in 0..trials {
+        let Some(&(a, b)) = villains.choose(rng) else {
+            break;
+        };
+        let deck = deck_minus(&[hero.0, hero.1, a, b]);
+        let board: Vec<Card> = deck.sample(rng, 5).copied().collect();
+        stats.plays += 1;
+        match evaluate_seven(&board, hero.0, hero.1).cmp(&evaluate_seven(&board, a, b)) {
+            Ordering::Greater => stats.wins += 1,
+            Ordering::Equal => stats.ties += 1,
+            Ordering::Less => {}
+        }
+    }
+    stats.equity()
+}
+
+/// equity の高い順（先頭がベストカード、末尾がワーストカード）に並べる。
+fn to_card_equities(cards: Vec<(Card, ComboStats)>, before: f64) -> Vec<CardEquity> {
+    let mut out: Vec<CardEquity> = cards
+        .into_iter()
+        .filter(|(_, t)| t.plays > 0)
+        .map(|(c, t)| CardEquity {
+            card: card_to_str(&c),
+            equity: t.equity(),
+            delta: t.equity() - before,
+        })
+        .collect();
+    out.sort_by(|a, b| b.equity.total_cmp(&a.equity));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_follows_the_runout_and_ranks_next_cards() {
+        let r = run("AhKh", "Qh7h2c9s3d", "QsQd; JcTc; 7s7d", 2000, 1).unwrap();
+        assert_eq!(r.runout, ["Qh", "7h", "2c", "9s", "3d"]);
+        let streets: Vec<&str> = r.streets.iter().map(|s| s.street.as_str()).collect();
+        assert_eq!(streets, ["preflop", "flop", "turn", "river"]);
+        // リバーでは JT にだけ勝つ
+        assert!((r.streets[3].equity - 1.0 / 3.0).abs() < 1e-12);
+        assert!(!r.streets[0].exact && r.streets[1].exact);
+
+        // 9s のターンから見ると、ハートのリバーが最良（フラッシュ）
+        assert!(r.river_cards[0].card.ends_with('h'));
+        assert_eq!(r.river_cards[0].equity, 1.0);
+        assert!(r.river_cards.windows(2).all(|w| w[0].equity >= w[1].equity));
+        assert_eq!(r.turn_cards.len(), 47);
+
+        // 同じシードなら同じランアウト
+        let a = run("AhKh", "Qh7h", "QsQd; JcTc", 500, 7).unwrap();
+        let b = run("AhKh", "Qh7h", "QsQd; JcTc", 500, 7).unwrap();
+        assert_eq!(a.runout, b.runout);
+        assert_eq!(a.runout.len(), 5);
+
+        // 相手 1 ハンドなら、その 2 枚はランアウトに出ない
+        for seed in 0..20 {
+            let r = run("AhKh", "", "QsQd", 10, seed).unwrap();
+            assert!(!r.runout.iter().any(|c| c == "Qs" || c == "Qd"));
+        }
+    }
+}
@@ -3,12 +3,12 @@
 //! Monte Carlo ではなく、確定したカードだけを使って評価する。
 //! 「フロップ後にどの手が一番強いか」を一覧表示するのに使う。
 
-use rs_poker::core::Rank;
+use rs_poker::core::{Card, Rank};
 
-use crate::cards::pair_string;
+use crate::cards::{card_to_str, pair_string};
 use crate::dto::HandRankingEntry;
 use crate::parser::{parse_cards, parse_hands_list};
-use crate::rank::{rank_encoded, rank_index, RANK_LABELS};
+use crate::rank::{hand_detail, rank_encoded, rank_index, HandDetail, RANK_LABELS};
 use crate::sim::evaluate_seven;
 
 pub fn run(hands: &str, board: &str) -> Result<Vec<HandRankingEntry>, String> {
@@ -24,23 +24,36 @@ pub fn run(hands: &str, board: &str) -> Result<Vec<HandRankingEntry>, String> {
     let mut tagged: Vec<(Rank, HandRankingEntry)> = hands_list
         .into_iter()
         .map(|(a, b)| {
-            let r = evaluate_seven(&board_cards, a, b);
-            let idx = rank_index(&r);
-            (
-                r.clone(),
-                HandRankingEntry {
-                    hand: pair_string(a, b),
-                    rank_index: idx,
-                    rank_name: RANK_LABELS[idx].to_string(),
-                    encoded: rank_encoded(&r),
-                    kickers: Vec::new(),
-                },
-            )
+            let (r, detail) = evaluate_detail(&board_cards, a, b);
+            (r, entry(a, b, &r, detail))
         })
         .collect();
 
     // 強い役が先頭になるように降順ソート。
-    tagged.sort_by(|a, b| b.0.cmp(&a.0));
+    tagged.sort_by_key(|(r, _)| std::cmp::Reverse(*r));
 
     Ok(tagged.into_iter().map(|(_, e)| e).collect())
 }
+
+/// ボード + 手札 2 枚の役と、その内訳（[`hand_detail`]）。
+pub(crate) fn evaluate_detail(board: &[Card], a: Card, b: Card) -> (Rank, HandDetail) {
+    let r = evaluate_seven(board, a, b);
+    let mut cards = board.to_vec();
+    cards.extend([a, b]);
+    let detail = hand_detail(&cards, &r);
+    (r, detail)
+}
+
+/// 評価結果を JS に返す 1 行にする。
+pub(crate) fn entry(a: Card, b: Card, r: &Rank, detail: HandDetail) -> HandRankingEntry {
+    let idx = rank_index(r);
+    HandRankingEntry {
+        hand: pair_string(a, b),
+        rank_index: idx,
+        rank_name: RANK_LABELS[idx].to_string(),
+        encoded: rank_encoded(r),
+        best_five: detail.best_five.iter().map(card_to_str).collect(),
+        kickers: detail.kickers,
+        description: detail.description,
+    }
+}
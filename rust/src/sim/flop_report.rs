@@ -0,0 +1,276 @@
+//! 全フロップ（スート同型で 1755 通り）での range vs range の集計。
+//!
+//! [`crate::hand_class::canonical_flops`] の代表フロップごとに
+//! [`super::range_vs_range`] を走らせ、hero レンジの equity をフロップ単位で返す。
+//! 代表 1 つの結果を同型なフロップ全部（`weight` 枚分）に使うので、レンジは
+//! スートの入れ替えで変わらないもの（`"AKs"` / `"QQ+"` のようなレンジ式）に限る。
+//!
+//! フロップはテクスチャ（スートの枚数・ペア・つながり）とハイカードでまとめ、
+//! どのフロップで hero レンジが有利かを見られるようにする。グループと全体の equity は
+//! 各フロップの equity を `weight` で重み付けした平均。
+//!
+//! 各フロップの乱数は `derive_seed(seed, フロップ番号)` なので、並列版
+//! （[`run_parallel`]）でもシリアル版と同じ結果になる。
+
+use std::collections::{BTreeMap, HashSet};
+
+use rs_poker::core::{Card, Value};
+
+use crate::cards::{all_perms, card_to_str, combo_key, map_card};
+use crate::dto::{FlopEquityEntry, FlopGroup, FlopReportPayload};
+use crate::hand_class::canonical_flops;
+use crate::parser::parse_range;
+use crate::rng::derive_seed;
+use crate::sim::range_vs_range::{accumulate_combos, ComboStats};
+
+pub fn run(
+    hero_range: &str,
+    villain_range: &str,
+    trials: u32,
+    seed: u64,
+) -> Result<FlopReportPayload, String> {
+    let setup = Setup::parse(hero_range, villain_range)?;
+    let equities = (0..setup.flops.len())
+        .map(|i| setup.flop_equity(i, trials, seed))
+        .collect();
+    Ok(setup.into_payload(equities))
+}
+
+/// マルチスレッド版（ネイティブ限定）。フロップ 1 つを作業単位にする。
+/// 結果は `threads` に依存せず、シリアル版 [`run`] と一致する。
+#[cfg(not(target_arch = "wasm32"))]
+pub fn run_parallel(
+    hero_range: &str,
+    villain_range: &str,
+    trials: u32,
+    seed: u64,
+    threads: usize,
+) -> Result<FlopReportPayload, String> {
+    let setup = Setup::parse(hero_range, villain_range)?;
+    let n = setup.flops.len();
+    let equities = crate::parallel::fold_units(
+        n,
+        threads,
+        || vec![None; n],
+        |out, i| out[i] = setup.flop_equity(i, trials, seed),
+        |total, part| {
+            for (t, p) in total.iter_mut().zip(part) {
+                if p.is_some() {
+                    *t = p;
+                }
+            }
+        },
+    );
+    Ok(setup.into_payload(equities))
+}
+
+/// フロップのテクスチャ。スートの枚数・ペア・（ペアなしなら）ストレートのつながりを並べたもの。
+///
+/// 例: `"rainbow unpaired connected"`, `"two-tone paired"`, `"monotone unpaired disconnected"`。
+/// つながりは「2 枚足せばストレートになる」（3 ランクが A を 1 としても数えて 5 の幅に収まる）かどうか。
+pub fn texture(flop: [Card; 3]) -> String {
+    let suits: HashSet<_> = flop.iter().map(|c| c.suit).collect();
+    let suits = match suits.len() {
+        1 => "monotone",
+        2 => "two-tone",
+        _ => "rainbow",
+    };
+    let ranks: HashSet<u8> = flop.iter().map(|c| c.value as u8).collect();
+    match ranks.len() {
+        1 => format!("{} trips", suits),
+        2 => format!("{} paired", suits),
+        _ => {
+            let connected = if is_connected(&ranks) {
+                "connected"
+            } else {
+                "disconnected"
+            };
+            format!("{} unpaired {}", suits, connected)
+        }
+    }
+}
+
+fn is_connected(ranks: &HashSet<u8>) -> bool {
+    let span = |rs: &[u8]| rs.iter().max().unwrap() - rs.iter().min().unwrap() < 5;
+    let high: Vec<u8> = ranks.iter().copied().collect();
+    // A を 1（= 2 の下）として数えたホイール側
+    let low: Vec<u8> = ranks
+        .iter()
+        .map(|&r| if r == Value::Ace as u8 { 0 } else { r + 1 })
+        .collect();
+    span(&high) || span(&low)
+}
+
+struct Setup {
+    hero: Vec<(Card, Card)>,
+    villain: Vec<(Card, Card)>,
+    flops: Vec<([Card; 3], usize)>,
+}
+
+impl Setup {
+    fn parse(hero_range: &str, villain_range: &str) -> Result<Self, String> {
+        let hero = parse_range(hero_range)?;
+        let villain = parse_range(villain_range)?;
+        if hero.is_empty() || villain.is_empty() {
+            return Err("both ranges need at least one combo".to_string());
+        }
+        if !is_suit_symmetric(&hero) || !is_suit_symmetric(&villain) {
+            return Err(
+                "ranges must be suit-symmetric (use range notation like AKs, QQ+)".to_string(),
+            );
+        }
+        Ok(Setup {
+            hero,
+            villain,
+            flops: canonical_flops(),
+        })
+    }
+
+    /// フロップ `i` での hero レンジの equity。ボードと衝突して対戦がなければ `None`。
+    fn flop_equity(&self, i: usize, trials: u32, seed: u64) -> Option<f64> {
+        let acc = accumulate_combos(
+            self.hero.clone(),
+            self.villain.clone(),
+            self.flops[i].0.to_vec(),
+            trials,
+            derive_seed(seed, i as u64),
+        )
+        .expect("a flop has 3 board cards");
+        let mut total = ComboStats::default();
+        for stats in &acc.hero {
+            total.merge(stats);
+        }
+        (total.plays > 0).then(|| total.equity())
+    }
+
+    fn into_payload(self, equities: Vec<Option<f64>>) -> FlopReportPayload {
+        let mut overall = Weighted::default();
+        let mut textures: BTreeMap<String, Weighted> = BTreeMap::new();
+        let mut high_cards: BTreeMap<u8, Weighted> = BTreeMap::new();
+        let mut flops = Vec::with_capacity(self.flops.len());
+        for (&(flop, weight), equity) in self.flops.iter().zip(equities) {
+            let texture = texture(flop);
+            if let Some(e) = equity {
+                overall.add(e, weight);
+                textures.entry(texture.clone()).or_default().add(e, weight);
+                high_cards
+                    .entry(flop[0].value as u8)
+                    .or_default()
+                    .add(e, weight);
+            }
+            flops.push(FlopEquityEntry {
+                board: flop.iter().map(card_to_str).collect(),
+                weight,
+                texture,
+                hero_equity: equity,
+            });
+        }
+
+        let mut textures: Vec<FlopGroup> = textures
+            .into_iter()
+            .map(|(label, w)| w.group(label))
+            .collect();
+        let mut high_cards: Vec<FlopGroup> = high_cards
+            .into_iter()
+            .map(|(v, w)| w.group(format!("{}-high", Value::from_u8(v).to_char())))
+            .collect();
+        textures.sort_by(|a, b| b.hero_equity.total_cmp(&a.hero_equity));
+        high_cards.sort_by(|a, b| b.hero_equity.total_cmp(&a.hero_equity));
+
+        FlopReportPayload {
+            hero_equity: overall.mean(),
+            weight: overall.weight,
+            flops,
+            textures,
+            high_cards,
+        }
+    }
+}
+
+/// `weight` で重み付けした equity の平均。
+#[derive(Default)]
+struct Weighted {
+    sum: f64,
+    weight: usize,
+    flops: usize,
+}
+
+impl Weighted {
+    fn add(&mut self, equity: f64, weight: usize) {
+        self.sum += equity * weight as f64;
+        self.weight += weight;
+        self.flops += 1;
+    }
+
+    fn mean(&self) -> f64 {
+        if self.weight == 0 {
+            0.0
+        } else {
+            self.sum / self.weight as f64
+        }
+    }
+
+    fn group(&self, label: String) -> FlopGroup {
+        FlopGroup {
+            label,
+            flops: self.flops,
+            weight: self.weight,
+            hero_equity: self.mean(),
+        }
+    }
+}
+
+/// 24 通りのスート置換のどれで写しても同じコンボの集合になるか。
+fn is_suit_symmetric(combos: &[(Card, Card)]) -> bool {
+    let keys: HashSet<(u8, u8)> = combos.iter().map(|&(a, b)| combo_key(a, b)).collect();
+    all_perms().iter().all(|perm| {
+        combos
+            .iter()
+            .all(|&(a, b)| keys.contains(&combo_key(map_card(perm, a), map_card(perm, b))))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_cards;
+
+    fn flop(s: &str) -> [Card; 3] {
+        let cards = parse_cards(s).unwrap();
+        [cards[0], cards[1], cards[2]]
+    }
+
+    #[test]
+    fn labels_textures() {
+        assert_eq!(texture(flop("Kh7h2h")), "monotone unpaired disconnected");
+        assert_eq!(texture(flop("9h8h7c")), "two-tone unpaired connected");
+        assert_eq!(texture(flop("Ah2d4c")), "rainbow unpaired connected");
+        assert_eq!(texture(flop("AhKd9c")), "rainbow unpaired disconnected");
+        assert_eq!(texture(flop("QhQd5h")), "two-tone paired");
+        assert_eq!(texture(flop("5h5d5c")), "rainbow trips");
+    }
+
+    #[test]
+    fn covers_every_flop_and_groups_by_texture() {
+        let r = run("AA", "KK", 1, 3).unwrap();
+        assert_eq!(r.flops.len(), 1755);
+        // AAA / KKK のフロップ（4 通りずつ）ではどちらかのコンボが残らない
+        assert_eq!(r.weight, 22100 - 8);
+        assert_eq!(
+            r.flops.iter().filter(|f| f.hero_equity.is_none()).count(),
+            2
+        );
+        let texture_weight: usize = r.textures.iter().map(|g| g.weight).sum();
+        assert_eq!(texture_weight, r.weight);
+        assert_eq!(r.high_cards.len(), 13);
+        assert!(r.hero_equity > 0.7 && r.hero_equity < 0.9);
+        // K-high フロップは KK に有利
+        assert_eq!(r.high_cards.last().unwrap().label, "K-high");
+
+        // 並列版はシリアル版と同じ
+        let p = run_parallel("AA", "KK", 1, 3, 4).unwrap();
+        assert_eq!(p.hero_equity, r.hero_equity);
+
+        assert!(run("AsKs", "QQ", 1, 0).is_err());
+    }
+}
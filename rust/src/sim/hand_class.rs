@@ -0,0 +1,59 @@
+//! [`crate::hand_class`] の WASM エクスポート用ロジック。
+//!
+//! レンジ表の描画（クラス名・コンボ数）や、クラス ↔ コンボの変換、
+//! フロップの正規化を文字列入力で呼べるようにする。
+
+use crate::cards::card_to_str;
+use crate::dto::{CanonicalFlopPayload, HandClassCell};
+use crate::hand_class::{canonical_flop, isomorphic_flop_count, HandClass};
+use crate::parser::{parse_cards, parse_two_cards};
+
+/// 具体的なコンボ（`"AsKs"`）のクラス名（`"AKs"`）。
+pub fn class_of(hand: &str) -> Result<String, String> {
+    let (a, b) = parse_two_cards(hand)?;
+    if a == b {
+        return Err(format!("duplicate card in hand '{}'", hand));
+    }
+    Ok(HandClass::from_combo(a, b).to_string())
+}
+
+/// クラスの全コンボを `[高い方, 低い方]` で返す。`dead` を含むコンボは除く。
+pub fn combos(class: &str, dead: &str) -> Result<Vec<[String; 2]>, String> {
+    let class = HandClass::parse(class)?;
+    let dead = parse_cards(dead)?;
+    Ok(class
+        .live_combos(&dead)
+        .into_iter()
+        .map(|(a, b)| [card_to_str(&a), card_to_str(&b)])
+        .collect())
+}
+
+/// 169 セルをグリッドの並びで返す。`dead` はコンボ数の計算から除く。
+pub fn grid(dead: &str) -> Result<Vec<HandClassCell>, String> {
+    let dead = parse_cards(dead)?;
+    Ok(HandClass::all()
+        .into_iter()
+        .map(|class| {
+            let (row, col) = class.grid_position();
+            HandClassCell {
+                class: class.to_string(),
+                index: class.grid_index(),
+                row,
+                col,
+                combos: class.combo_count(),
+                live_combos: class.live_count(&dead),
+            }
+        })
+        .collect())
+}
+
+pub fn flop(board: &str) -> Result<CanonicalFlopPayload, String> {
+    let cards = parse_cards(board)?;
+    let flop: [_; 3] = cards
+        .try_into()
+        .map_err(|_| format!("expected 3 distinct flop cards from '{}'", board))?;
+    Ok(CanonicalFlopPayload {
+        board: canonical_flop(flop).iter().map(card_to_str).collect(),
+        isomorphs: isomorphic_flop_count(flop),
+    })
+}
@@ -0,0 +1,192 @@
+//! hand strength（HS）とポテンシャル（PPot / NPot）、EHS / E[HS²]。
+//!
+//! Billings らの定義に従い、相手レンジの全コンボとリバーまでの全ランアウトを
+//! 列挙して厳密に数える（乱数は使わない）。
+//!
+//! - HS: 今のボードで相手コンボに勝っている割合（引き分けは半分）
+//! - PPot: 今は負け / 引き分けの相手に、リバーで逆転する確率
+//! - NPot: 今は勝ち / 引き分けの相手に、リバーで逆転される確率
+//! - EHS: `HS × (1 − NPot) + (1 − HS) × PPot`
+//! - E[HS²]: ランアウトごとのリバー HS の二乗の平均。ドローほど分散が大きく値が上がる
+//!
+//! 評価回数はフロップで `ランアウト 990〜1081 × 相手コンボ数` 程度。
+
+use std::cmp::Ordering;
+
+use rs_poker::core::Card;
+
+use crate::cards::{deck_minus, pair_string};
+use crate::dto::HandStrengthPayload;
+use crate::parser::{parse_cards, parse_range, parse_two_cards};
+use crate::sim::evaluate_seven;
+
+const AHEAD: usize = 0;
+const TIED: usize = 1;
+const BEHIND: usize = 2;
+
+/// `villain_range` が空なら、残りカードの全コンボ（一様レンジ）を相手にする。
+pub fn run(hero: &str, board: &str, villain_range: &str) -> Result<HandStrengthPayload, String> {
+    let (h1, h2) = parse_two_cards(hero)?;
+    let board_cards = parse_cards(board)?;
+    if !(3..=5).contains(&board_cards.len()) {
+        return Err(format!(
+            "expected 3 to 5 board cards, got {} from '{}'",
+            board_cards.len(),
+            board
+        ));
+    }
+    let mut dead = board_cards.clone();
+    dead.extend([h1, h2]);
+    for (i, c) in dead.iter().enumerate() {
+        if dead[..i].contains(c) {
+            return Err("duplicate card between hero and board".to_string());
+        }
+    }
+
+    let villains: Vec<(Card, Card)> = if villain_range.trim().is_empty() {
+        let deck = deck_minus(&dead);
+        let mut out = Vec::with_capacity(deck.len() * (deck.len() - 1) / 2);
+        for i in 0..deck.len() {
+            for j in i + 1..deck.len() {
+                out.push((deck[i], deck[j]));
+            }
+        }
+        out
+    } else {
+        parse_range(villain_range)?
+            .into_iter()
+            .filter(|(a, b)| !dead.contains(a) && !dead.contains(b))
+            .collect()
+    };
+    if villains.is_empty() {
+        return Err("villain range has no combos left after removing dead cards".to_string());
+    }
+
+    // 今のボードでの勝敗。HS と、ポテンシャルの「今」側に使う。
+    let hero_now = evaluate_seven(&board_cards, h1, h2);
+    let now: Vec<usize> = villains
+        .iter()
+        .map(|&(a, b)| outcome(hero_now.cmp(&evaluate_seven(&board_cards, a, b))))
+        .collect();
+    let mut now_count = [0u64; 3];
+    for &o in &now {
+        now_count[o] += 1;
+    }
+    let hs = ratio(
+        now_count[AHEAD] as f64 + now_count[TIED] as f64 / 2.0,
+        villains.len() as f64,
+    );
+
+    // hp[今][リバー] の件数。
+    let mut hp = [[0u64; 3]; 3];
+    let mut hs_squared_sum = 0.0;
+    let mut runouts = 0usize;
+    let mut full = board_cards.clone();
+    for_each_runout(&deck_minus(&dead), 5 - board_cards.len(), &mut |runout| {
+        full.truncate(board_cards.len());
+        full.extend_from_slice(runout);
+        let hero_final = evaluate_seven(&full, h1, h2);
+        let mut river = [0u64; 3];
+        for (i, &(a, b)) in villains.iter().enumerate() {
+            if runout.contains(&a) || runout.contains(&b) {
+                continue;
+            }
+            let o = outcome(hero_final.cmp(&evaluate_seven(&full, a, b)));
+            hp[now[i]][o] += 1;
+            river[o] += 1;
+        }
+        let n = river.iter().sum::<u64>();
+        if n > 0 {
+            let river_hs = (river[AHEAD] as f64 + river[TIED] as f64 / 2.0) / n as f64;
+            hs_squared_sum += river_hs * river_hs;
+            runouts += 1;
+        }
+    });
+
+    let total = |now: usize| hp[now].iter().sum::<u64>() as f64;
+    let at = |now: usize, river: usize| hp[now][river] as f64;
+    let ppot = ratio(
+        at(BEHIND, AHEAD) + at(BEHIND, TIED) / 2.0 + at(TIED, AHEAD) / 2.0,
+        total(BEHIND) + total(TIED) / 2.0,
+    );
+    let npot = ratio(
+        at(AHEAD, BEHIND) + at(TIED, BEHIND) / 2.0 + at(AHEAD, TIED) / 2.0,
+        total(AHEAD) + total(TIED) / 2.0,
+    );
+
+    Ok(HandStrengthPayload {
+        hand: pair_string(h1, h2),
+        hs,
+        ppot,
+        npot,
+        ehs: hs * (1.0 - npot) + (1.0 - hs) * ppot,
+        ehs_squared: ratio(hs_squared_sum, runouts as f64),
+        villain_combos: villains.len(),
+        runouts,
+    })
+}
+
+/// hero 側から見た比較結果を hp の添字にする。
+fn outcome(o: Ordering) -> usize {
+    match o {
+        Ordering::Greater => AHEAD,
+        Ordering::Equal => TIED,
+        Ordering::Less => BEHIND,
+    }
+}
+
+fn ratio(num: f64, den: f64) -> f64 {
+    if den == 0.0 {
+        0.0
+    } else {
+        num / den
+    }
+}
+
+/// `deck` から `n` 枚（0〜2）選ぶ全組み合わせを順に渡す。
+fn for_each_runout(deck: &[Card], n: usize, f: &mut impl FnMut(&[Card])) {
+    match n {
+        0 => f(&[]),
+        1 => deck.iter().for_each(|&c| f(&[c])),
+        _ => {
+            for i in 0..deck.len() {
+                for j in i + 1..deck.len() {
+                    f(&[deck[i], deck[j]]);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_published_example_and_separates_draws() {
+        // Billings et al. の例: AdQc on 3h4cJh（一様レンジ）
+        let r = run("AdQc", "3h4cJh", "").unwrap();
+        assert_eq!(r.villain_combos, 1081);
+        assert_eq!(r.runouts, 1081);
+        assert!((r.hs - 0.585).abs() < 0.001, "hs = {}", r.hs);
+        assert!((r.ppot - 0.208).abs() < 0.001, "ppot = {}", r.ppot);
+        assert!((r.npot - 0.274).abs() < 0.001, "npot = {}", r.npot);
+        assert!((r.ehs - 0.511).abs() < 0.001, "ehs = {}", r.ehs);
+
+        // リバーではポテンシャルがなく EHS = HS、E[HS²] = HS²
+        let river = run("AdQc", "3h4cJh9s2d", "").unwrap();
+        assert_eq!((river.ppot, river.npot, river.runouts), (0.0, 0.0, 1));
+        assert!((river.ehs - river.hs).abs() < 1e-12);
+        assert!((river.ehs_squared - river.hs * river.hs).abs() < 1e-12);
+
+        // ナッツのフラッシュドローは PPot で EHS が上がり、セットは NPot の分だけ下がる
+        let draw = run("AhKh", "7h2h9c", "").unwrap();
+        let set = run("9s9d", "7h2h9c", "").unwrap();
+        assert!(draw.ppot > 0.4 && set.ppot == 0.0);
+        assert!(draw.ehs > draw.hs && set.ehs < set.hs);
+
+        let ranged = run("AhKh", "7h2h9c", "QQ+,AK").unwrap();
+        assert_eq!(ranged.villain_combos, 6 + 3 + 3 + 9);
+        assert!(run("AhKh", "7h2h9c", "AhAd").is_err());
+    }
+}
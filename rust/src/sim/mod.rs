@@ -3,11 +3,28 @@
 //! 1 シミュレーション = 1 ファイルの方針で分割している。共通の小さな
 //! ヘルパー（7 枚評価など）はこのモジュール直下に置く。
 
+use std::cmp::Ordering;
+
 use rs_poker::core::{Card, Rank, Rankable};
 
+use crate::cards::deck_minus;
+use range_vs_range::ComboStats;
+
+pub mod all_in_ev;
+pub mod bb_defense;
+pub mod deal;
+pub mod equity_path;
 pub mod evaluate;
+pub mod flop_report;
+pub mod hand_class;
+pub mod hand_strength;
+pub mod next_card;
+pub mod nuts;
 pub mod parse_range;
+pub mod range_update;
 pub mod range_vs_range;
+pub mod settlement;
+pub mod showdown;
 pub mod vs_list;
 pub mod vs_list_equity;
 
@@ -23,3 +40,57 @@ pub(super) fn evaluate_seven(board: &[Card], a: Card, b: Card) -> Rank {
     let slice: &[Card] = &v;
     slice.rank()
 }
+
+/// 5 枚ボードでの hero 対相手コンボの集計（hero 目線）。ボードとかぶる相手は数えない。
+pub(super) fn showdown_stats(
+    hero: (Card, Card),
+    villains: &[(Card, Card)],
+    board: &[Card],
+) -> ComboStats {
+    let hero_rank = evaluate_seven(board, hero.0, hero.1);
+    let mut stats = ComboStats::default();
+    for &(a, b) in villains {
+        if board.contains(&a) || board.contains(&b) {
+            continue;
+        }
+        stats.plays += 1;
+        match hero_rank.cmp(&evaluate_seven(board, a, b)) {
+            Ordering::Greater => stats.wins += 1,
+            Ordering::Equal => stats.ties += 1,
+            Ordering::Less => {}
+        }
+    }
+    stats
+}
+
+/// `board`（3 or 4 枚）に次の 1 枚を足したときの、カードごとの厳密な集計。
+///
+/// 並びは残りデッキの順（[`deck_minus`]）。ターンなら、その先のリバーも全列挙する。
+/// 全カード分を合算すると今のボードでの厳密な集計になる（ターン起点では各ランアウトが
+/// ちょうど 2 回ずつ数えられるので偏らない）。
+pub(super) fn next_card_stats(
+    hero: (Card, Card),
+    villains: &[(Card, Card)],
+    board: &[Card],
+) -> Vec<(Card, ComboStats)> {
+    let mut dead = board.to_vec();
+    dead.extend([hero.0, hero.1]);
+    let deck = deck_minus(&dead);
+    deck.iter()
+        .map(|&next| {
+            let mut next_board = board.to_vec();
+            next_board.push(next);
+            let mut stats = ComboStats::default();
+            if next_board.len() == 5 {
+                stats.merge(&showdown_stats(hero, villains, &next_board));
+            } else {
+                for &last in deck.iter().filter(|&&c| c != next) {
+                    next_board.truncate(4);
+                    next_board.push(last);
+                    stats.merge(&showdown_stats(hero, villains, &next_board));
+                }
+            }
+            (next, stats)
+        })
+        .collect()
+}
@@ -0,0 +1,122 @@
+//! 次の 1 枚（ターン or リバー）ごとの hero equity のヒートマップ。
+//!
+//! hero 1 ハンド対相手レンジ（[`super::range_vs_range`] と同じレンジ式。1 ハンドでもよい）で、
+//! フロップならターン、ターンならリバーに来うる各カードの equity を厳密に求める
+//! （ターンの各カードについてリバーを全列挙する）。`turn-equity-quiz` の
+//! 「どのターンカードが自分に良いか」表示用。
+
+use crate::binary::card_code;
+use crate::cards::{card_to_str, pair_string};
+use crate::dto::{CardEquity, NextCardBuckets, NextCardPayload};
+use crate::parser::{parse_cards_ordered, parse_range, parse_two_cards};
+use crate::sim::next_card_stats;
+use crate::sim::range_vs_range::ComboStats;
+
+/// 今の equity からこれ以上上がる / 下がるカードを good / bad に分ける。
+pub const SWING_THRESHOLD: f64 = 0.05;
+
+pub fn run(hero: &str, villain_range: &str, board: &str) -> Result<NextCardPayload, String> {
+    let (h1, h2) = parse_two_cards(hero)?;
+    let board_cards = parse_cards_ordered(board)?;
+    let street = match board_cards.len() {
+        3 => "turn",
+        4 => "river",
+        n => {
+            return Err(format!(
+                "expected 3 or 4 board cards, got {} from '{}'",
+                n, board
+            ))
+        }
+    };
+    let mut dead = board_cards.clone();
+    dead.extend([h1, h2]);
+    for (i, c) in dead.iter().enumerate() {
+        if dead[..i].contains(c) {
+            return Err("duplicate card between hero and board".to_string());
+        }
+    }
+    let villains: Vec<_> = parse_range(villain_range)?
+        .into_iter()
+        .filter(|(a, b)| !dead.contains(a) && !dead.contains(b))
+        .collect();
+    if villains.is_empty() {
+        return Err("villain range has no combos left after removing dead cards".to_string());
+    }
+
+    let per_card = next_card_stats((h1, h2), &villains, &board_cards);
+    let mut total = ComboStats::default();
+    for (_, stats) in &per_card {
+        total.merge(stats);
+    }
+    let equity = total.equity();
+
+    let mut grid = vec![None; 52];
+    let mut cards = Vec::with_capacity(per_card.len());
+    let mut buckets = NextCardBuckets::default();
+    for (card, stats) in per_card {
+        // 相手レンジが全部このカードを持っている（= 来ない）ときは空欄
+        if stats.plays == 0 {
+            continue;
+        }
+        let card_equity = stats.equity();
+        let delta = card_equity - equity;
+        grid[card_code(card) as usize] = Some(card_equity);
+        let name = card_to_str(&card);
+        if delta >= SWING_THRESHOLD {
+            buckets.good.push(name.clone());
+        } else if delta <= -SWING_THRESHOLD {
+            buckets.bad.push(name.clone());
+        } else {
+            buckets.neutral.push(name.clone());
+        }
+        cards.push(CardEquity {
+            card: name,
+            equity: card_equity,
+            delta,
+        });
+    }
+
+    Ok(NextCardPayload {
+        hand: pair_string(h1, h2),
+        board: board_cards.iter().map(card_to_str).collect(),
+        street: street.to_string(),
+        equity,
+        villain_combos: villains.len(),
+        grid,
+        cards,
+        buckets,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_every_live_card_and_buckets_by_swing() {
+        let r = run("AhKh", "QQ", "Qh7h2c").unwrap();
+        assert_eq!(r.street, "turn");
+        assert_eq!(r.villain_combos, 3);
+        assert_eq!(r.cards.len(), 47);
+        assert_eq!(r.grid.iter().filter(|e| e.is_some()).count(), 47);
+        // 使用済みのカード（Ah = 12 * 4 + 1）は空欄
+        assert_eq!(r.grid[49], None);
+
+        // ハートはフラッシュ完成、クイーンは相手のクワッズ
+        let heart = r.cards.iter().find(|c| c.card == "9h").unwrap();
+        assert!(heart.delta > SWING_THRESHOLD);
+        assert!(r.buckets.good.contains(&"9h".to_string()));
+        assert!(r.buckets.bad.contains(&"Qs".to_string()));
+        let counted = r.buckets.good.len() + r.buckets.neutral.len() + r.buckets.bad.len();
+        assert_eq!(counted, 47);
+
+        // 相手 1 ハンド・ターンからのリバー
+        let river = run("AhKh", "QsQd", "Qh7h2c9s").unwrap();
+        assert_eq!(river.street, "river");
+        assert_eq!(river.cards.len(), 44);
+        assert!(river
+            .cards
+            .iter()
+            .all(|c| c.equity == 0.0 || c.equity == 1.0));
+    }
+}
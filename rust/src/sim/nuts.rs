@@ -0,0 +1,144 @@
+//! ボード上のナッツ順位と、hero ハンドの順位・パーセンタイル。
+//!
+//! ボード以外の全 2 枚組（5 / 4 / 3 枚ボードで 1081 / 1128 / 1176 組）を
+//! [`super::evaluate`] と同じく `Rank` の降順に並べ、同じ `Rank` のコンボを
+//! 1 クラスにまとめる。ボードが 3 / 4 枚なら、その時点で完成している役で比べる
+//! （ランアウトは考えない）。
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use rs_poker::core::{Card, Rank};
+
+use crate::cards::{deck_minus, pair_string};
+use crate::dto::{HeroNutRank, NutClass, NutRankingPayload};
+use crate::parser::{parse_cards, parse_two_cards};
+use crate::rank::{hand_detail, rank_index, RANK_LABELS};
+use crate::sim::evaluate_seven;
+
+/// `hero` は空文字でもよい（そのときは `hero: None`）。
+pub fn run(hero: &str, board: &str) -> Result<NutRankingPayload, String> {
+    let board_cards = parse_cards(board)?;
+    if !(3..=5).contains(&board_cards.len()) {
+        return Err(format!(
+            "expected 3 to 5 board cards, got {} from '{}'",
+            board_cards.len(),
+            board
+        ));
+    }
+    let hero = if hero.trim().is_empty() {
+        None
+    } else {
+        Some(parse_two_cards(hero)?)
+    };
+    let mut used = board_cards.clone();
+    if let Some((a, b)) = hero {
+        used.extend([a, b]);
+    }
+    let unique: HashSet<_> = used.iter().collect();
+    if unique.len() != used.len() {
+        return Err("duplicate card between hero and board".to_string());
+    }
+
+    // 強いカード同士の組から作るので、クラス内のコンボも強い順に並ぶ。
+    let deck = deck_minus(&board_cards);
+    let mut ranked: Vec<(Rank, Card, Card)> = Vec::with_capacity(deck.len() * (deck.len() - 1) / 2);
+    for i in (0..deck.len()).rev() {
+        for j in (0..i).rev() {
+            let (a, b) = (deck[i], deck[j]);
+            ranked.push((evaluate_seven(&board_cards, a, b), a, b));
+        }
+    }
+    // 安定ソートなので、同じ Rank の中では上の生成順が保たれる。
+    ranked.sort_by_key(|(r, _, _)| std::cmp::Reverse(*r));
+
+    let classes: Vec<NutClass> = ranked
+        .chunk_by(|x, y| x.0 == y.0)
+        .enumerate()
+        .map(|(i, group)| {
+            let (r, a, b) = group[0];
+            let mut cards = board_cards.clone();
+            cards.extend([a, b]);
+            let idx = rank_index(&r);
+            NutClass {
+                position: i + 1,
+                rank_index: idx,
+                rank_name: RANK_LABELS[idx].to_string(),
+                description: hand_detail(&cards, &r).description,
+                combos: group.iter().map(|&(_, a, b)| pair_string(a, b)).collect(),
+            }
+        })
+        .collect();
+
+    let hero = hero.map(|(a, b)| {
+        let hero_rank = evaluate_seven(&board_cards, a, b);
+        // クラスは Rank 降順なので、hero より強いクラスの数 + 1 が順位。
+        let position = ranked
+            .chunk_by(|x, y| x.0 == y.0)
+            .take_while(|group| group[0].0 > hero_rank)
+            .count()
+            + 1;
+        let (mut beats, mut ties, mut loses) = (0, 0, 0);
+        for (r, c1, c2) in &ranked {
+            if [a, b].iter().any(|h| h == c1 || h == c2) {
+                continue;
+            }
+            match hero_rank.cmp(r) {
+                Ordering::Greater => beats += 1,
+                Ordering::Equal => ties += 1,
+                Ordering::Less => loses += 1,
+            }
+        }
+        let total = beats + ties + loses;
+        HeroNutRank {
+            hand: pair_string(a, b),
+            position,
+            beats,
+            ties,
+            loses,
+            percentile: if total == 0 {
+                0.0
+            } else {
+                beats as f64 / total as f64
+            },
+        }
+    });
+
+    Ok(NutRankingPayload {
+        classes,
+        total_combos: ranked.len(),
+        hero,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_every_combo_and_places_hero() {
+        for (board, total) in [("Ks9s4d2h7c", 1081), ("Ks9s4d2h", 1128), ("Ks9s4d", 1176)] {
+            let result = run("", board).unwrap();
+            assert_eq!(result.total_combos, total);
+            let listed: usize = result.classes.iter().map(|c| c.combos.len()).sum();
+            assert_eq!(listed, total);
+            assert!(result.hero.is_none());
+        }
+
+        // Ks9s4d2h7c: ナッツは KK（セット）、2nd は 99、3rd は 77
+        let result = run("7h7d", "Ks9s4d2h7c").unwrap();
+        assert_eq!(result.classes[0].combos, ["Kc Kd", "Kc Kh", "Kd Kh"]);
+        assert_eq!(
+            result.classes[0].description,
+            "Three of a Kind, Kings, Nine kicker"
+        );
+        let hero = result.hero.unwrap();
+        assert_eq!(hero.position, 3);
+        assert_eq!(hero.beats + hero.ties + hero.loses, 990);
+        assert_eq!(hero.loses, 6);
+        assert!(hero.percentile > 0.99);
+
+        assert!(run("Ks9s", "Ks9s4d").is_err());
+        assert!(run("", "Ks9s").is_err());
+    }
+}
@@ -0,0 +1,192 @@
+//! [`crate::range_update`] の WASM エクスポート用ロジック。
+//!
+//! 入力の文字列フォーマット:
+//!
+//! - レンジ: レンジ式のカンマ区切りで、項目ごとに `:重み` を付けられる（省略時 1）。
+//!   例 `"QQ+,AKs,AQs:0.5"`。同じコンボが複数の項目に入るときは後の項目の重み
+//! - アクション: `"<street> <action>"` のセミコロン区切り。例 `"preflop raise; flop bet; turn call"`
+//! - 頻度モデル: アクションと同じ並びのセミコロン区切り。各要素は `キー:頻度` のカンマ区切りで、
+//!   キーは強さの名前（`top_pair` 等）かレンジ式（含まれるハンドクラス全体に効く）。
+//!   空の要素・足りない分は組み込みのヒューリスティック。例 `"AA:1,KK:1;top_pair:0.8,air:0"`
+
+use std::collections::HashMap;
+
+use rs_poker::core::Card;
+
+use crate::approx::EPSILON;
+use crate::cards::{combo_key, pair_string};
+use crate::dto::{
+    ClassWeight, RangeUpdatePayload, RangeUpdateStepPayload, StrengthWeight, WeightedHandEntry,
+    WeightedRangeSnapshot,
+};
+use crate::hand_class::HandClass;
+use crate::parser::{parse_cards_ordered, parse_range};
+use crate::range_update::{
+    classify, update_range, FrequencyModel, ObservedAction, RangeAction, Street, Strength,
+    WeightedCombo,
+};
+
+pub fn run(
+    range: &str,
+    board: &str,
+    actions: &str,
+    models: &str,
+) -> Result<RangeUpdatePayload, String> {
+    let prior = parse_weighted_range(range)?;
+    let board_cards = parse_cards_ordered(board)?;
+    let mut observed = parse_actions(actions)?;
+    let models: Vec<&str> = if models.trim().is_empty() {
+        Vec::new()
+    } else {
+        models.split(';').collect()
+    };
+    if models.len() > observed.len() {
+        return Err(format!(
+            "{} frequency models for {} actions",
+            models.len(),
+            observed.len()
+        ));
+    }
+    for (a, m) in observed.iter_mut().zip(&models) {
+        a.model = parse_model(m)?;
+    }
+
+    let steps = update_range(&prior, &board_cards, &observed)?;
+    let initial: Vec<WeightedCombo> = prior
+        .into_iter()
+        .filter(|c| {
+            c.weight > EPSILON
+                && !board_cards.contains(&c.hand.0)
+                && !board_cards.contains(&c.hand.1)
+        })
+        .collect();
+    Ok(RangeUpdatePayload {
+        initial: snapshot(&initial, &[]),
+        steps: steps
+            .iter()
+            .map(|s| RangeUpdateStepPayload {
+                street: s.street.name().to_string(),
+                action: s.action.name().to_string(),
+                action_probability: s.action_probability,
+                range: snapshot(&s.combos, &board_cards[..s.street.board_len()]),
+            })
+            .collect(),
+    })
+}
+
+/// `"QQ+,AKs:0.5"` を重み付きのコンボにする。並びは [`combo_key`] の降順。
+fn parse_weighted_range(range: &str) -> Result<Vec<WeightedCombo>, String> {
+    let mut weights: HashMap<(u8, u8), WeightedCombo> = HashMap::new();
+    for item in range.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let (expr, weight) = match item.split_once(':') {
+            Some((expr, w)) => (expr, parse_unit(w, item)?),
+            None => (item, 1.0),
+        };
+        for hand in parse_range(expr)? {
+            weights.insert(combo_key(hand.0, hand.1), WeightedCombo { hand, weight });
+        }
+    }
+    if weights.is_empty() {
+        return Err("range is empty".to_string());
+    }
+    let mut combos: Vec<(_, WeightedCombo)> = weights.into_iter().collect();
+    combos.sort_unstable_by_key(|&(key, _)| std::cmp::Reverse(key));
+    Ok(combos.into_iter().map(|(_, c)| c).collect())
+}
+
+/// 0〜1 の数値。
+fn parse_unit(s: &str, item: &str) -> Result<f64, String> {
+    let v: f64 = s
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid weight in '{}'", item))?;
+    if !(0.0..=1.0).contains(&v) {
+        return Err(format!("weight must be between 0 and 1 in '{}'", item));
+    }
+    Ok(v)
+}
+
+fn parse_actions(actions: &str) -> Result<Vec<ObservedAction>, String> {
+    actions
+        .split(';')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|entry| {
+            let mut parts = entry.split_whitespace();
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(street), Some(action), None) => Ok(ObservedAction {
+                    street: Street::parse(street)?,
+                    action: RangeAction::parse(action)?,
+                    model: FrequencyModel::default(),
+                }),
+                _ => Err(format!("expected '<street> <action>', got '{}'", entry)),
+            }
+        })
+        .collect()
+}
+
+fn parse_model(model: &str) -> Result<FrequencyModel, String> {
+    let mut out = FrequencyModel::default();
+    for item in model.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let (key, freq) = item
+            .split_once(':')
+            .ok_or_else(|| format!("expected '<key>:<frequency>', got '{}'", item))?;
+        let freq = parse_unit(freq, item)?;
+        match Strength::parse(key) {
+            Ok(strength) => {
+                out.strengths.insert(strength, freq);
+            }
+            Err(_) => {
+                for (a, b) in parse_range(key)? {
+                    out.classes.insert(HandClass::from_combo(a, b), freq);
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// `board` はそのストリートで見えている分。空ならプリフロップとして強さを付けない。
+fn snapshot(combos: &[WeightedCombo], board: &[Card]) -> WeightedRangeSnapshot {
+    let mut classes: HashMap<HandClass, f64> = HashMap::new();
+    let mut strengths: HashMap<Strength, f64> = HashMap::new();
+    let entries = combos
+        .iter()
+        .map(|c| {
+            let class = HandClass::from_combo(c.hand.0, c.hand.1);
+            *classes.entry(class).or_default() += c.weight;
+            let strength = (!board.is_empty()).then(|| classify(c.hand, board));
+            if let Some(s) = strength {
+                *strengths.entry(s).or_default() += c.weight;
+            }
+            WeightedHandEntry {
+                hand: pair_string(c.hand.0, c.hand.1),
+                hand_class: class.to_string(),
+                strength: strength.map(|s| s.name().to_string()),
+                weight: c.weight,
+            }
+        })
+        .collect();
+    WeightedRangeSnapshot {
+        total_weight: combos.iter().map(|c| c.weight).sum(),
+        combos: entries,
+        classes: HandClass::all()
+            .into_iter()
+            .filter_map(|class| {
+                classes.get(&class).map(|&weight| ClassWeight {
+                    hand_class: class.to_string(),
+                    weight,
+                })
+            })
+            .collect(),
+        strengths: Strength::ALL
+            .into_iter()
+            .filter_map(|s| {
+                strengths.get(&s).map(|&weight| StrengthWeight {
+                    strength: s.name().to_string(),
+                    weight,
+                })
+            })
+            .collect(),
+    }
+}
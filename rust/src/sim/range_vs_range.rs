@@ -9,26 +9,116 @@
 //!
 //! 注意: 計算量は `n_hero × n_villain × trials × 2`（rank 評価回数）。
 //! レンジが極端に大きい場合（数百コンボずつ × trials=1000 など）は重くなる。
+//! ネイティブからは hero コンボ単位でスレッド分割する [`run_parallel`] も使える。
+//!
+//! 集計は [`RangeVsRangeAccumulator`] として取り出せ、別シードの実行同士を
+//! [`RangeVsRangeAccumulator::merge`] で正確に合算できる（段階的な精度向上やキャッシュ用）。
+//! [`run_grid`] は同じ集計を 13×13 グリッドのハンドクラス別にまとめる。
 
 use std::cmp::Ordering;
 
 use rand::prelude::IndexedRandom;
+use rand::Rng;
 use rs_poker::core::Card;
+use serde::{Deserialize, Serialize};
 
-use crate::cards::{deck_minus, pair_string};
-use crate::dto::{RangeEquityEntry, RangeVsRangePayload};
+use crate::cards::{cards_string, deck_minus, pair_string};
+use crate::dto::{RangeEquityEntry, RangeGridCell, RangeVsRangeGridPayload, RangeVsRangePayload};
+use crate::hand_class::{HandClass, CLASS_COUNT};
 use crate::parser::{parse_cards, parse_range};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::rng::derive_seed;
 use crate::rng::seeded_rng;
 use crate::sim::evaluate_seven;
 
 /// コンボ別の (wins, ties, plays) 集計。wins/ties は累積カウンタ。
-#[derive(Default, Clone, Copy)]
-struct ComboStats {
-    wins: u64,
-    ties: u64,
-    plays: u64,
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComboStats {
+    pub wins: u64,
+    pub ties: u64,
+    pub plays: u64,
+}
+
+impl ComboStats {
+    /// 別の集計を加算する。
+    #[inline]
+    pub fn merge(&mut self, other: &ComboStats) {
+        self.wins += other.wins;
+        self.ties += other.ties;
+        self.plays += other.plays;
+    }
+
+    /// `(wins + ties / 2) / plays`。1 度も対戦していなければ 0。
+    pub fn equity(&self) -> f64 {
+        if self.plays == 0 {
+            0.0
+        } else {
+            (self.wins as f64 + self.ties as f64 * 0.5) / self.plays as f64
+        }
+    }
 }
 
+/// range vs range の途中集計。
+///
+/// `hero` / `villain` は `hero_combos` / `villain_combos`（ボード衝突を除いたパース順、
+/// `"As Ks"` 形式）と添字で対応する。どちらかのレンジが空になる入力では両方とも空。
+///
+/// 同じシードの実行を合算すると同じサンプルを二重に数えるだけなので、
+/// 合算する実行にはそれぞれ別のシードを使うこと。
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RangeVsRangeAccumulator {
+    /// ボード（`"2h 7c 9d"` 形式）。
+    pub board: String,
+    pub hero_combos: Vec<String>,
+    pub hero: Vec<ComboStats>,
+    pub villain_combos: Vec<String>,
+    pub villain: Vec<ComboStats>,
+}
+
+impl RangeVsRangeAccumulator {
+    /// 同じレンジ・ボードに対する別の集計を加算する。
+    ///
+    /// ボードやコンボ列が一致しない場合や、コンボ列と集計列の長さが違う場合は
+    /// 何も変更せずにエラーを返す。
+    pub fn merge(&mut self, other: &RangeVsRangeAccumulator) -> Result<(), String> {
+        if self.board != other.board {
+            return Err("cannot merge results for different boards".into());
+        }
+        if self.hero_combos != other.hero_combos || self.villain_combos != other.villain_combos {
+            return Err("cannot merge results for different ranges".into());
+        }
+        if !self.is_consistent() || !other.is_consistent() {
+            return Err("combo labels and stats differ in length".into());
+        }
+        for (t, p) in self.hero.iter_mut().zip(&other.hero) {
+            t.merge(p);
+        }
+        for (t, p) in self.villain.iter_mut().zip(&other.villain) {
+            t.merge(p);
+        }
+        Ok(())
+    }
+
+    /// コンボ列と集計列の長さが揃っているか（デシリアライズした値は揃っているとは限らない）。
+    fn is_consistent(&self) -> bool {
+        self.hero.len() == self.hero_combos.len() && self.villain.len() == self.villain_combos.len()
+    }
+
+    /// コンボごとの equity を降順に並べた、JS に返す形へ変換する。
+    pub fn to_payload(&self) -> RangeVsRangePayload {
+        let mut payload = RangeVsRangePayload {
+            hero: build_entries(&self.hero_combos, &self.hero),
+            villain: build_entries(&self.villain_combos, &self.villain),
+        };
+
+        sort_desc(&mut payload.hero);
+        sort_desc(&mut payload.villain);
+
+        payload
+    }
+}
+
+/// シリアル版。JS から呼ばれる経路で、全ペアが 1 本の乱数列を共有する。
 pub fn run(
     hero_range: &str,
     villain_range: &str,
@@ -36,64 +126,220 @@ pub fn run(
     trials: u32,
     seed: u64,
 ) -> Result<RangeVsRangePayload, String> {
-    let hero_combos = parse_range(hero_range)?;
-    let villain_combos = parse_range(villain_range)?;
-    let board_cards = parse_cards(board)?;
-    if board_cards.len() > 5 {
-        return Err("board must be <=5 cards".into());
-    }
-    if hero_combos.is_empty() || villain_combos.is_empty() {
-        return Ok(empty_payload());
+    accumulate(hero_range, villain_range, board, trials, seed).map(|acc| acc.to_payload())
+}
+
+/// [`run`] と同じ計算の結果を、ハンドクラスごとの 169 セルで返す。
+pub fn run_grid(
+    hero_range: &str,
+    villain_range: &str,
+    board: &str,
+    trials: u32,
+    seed: u64,
+) -> Result<RangeVsRangeGridPayload, String> {
+    let setup = Setup::parse(hero_range, villain_range, board, trials)?;
+    let (hero_stats, villain_stats) = setup.simulate(seed);
+    Ok(RangeVsRangeGridPayload {
+        hero: build_grid(&setup.hero_combos, &hero_stats),
+        villain: build_grid(&setup.villain_combos, &villain_stats),
+    })
+}
+
+/// [`run`] と同じ計算を行い、payload に変換する前の集計を返す。
+pub fn accumulate(
+    hero_range: &str,
+    villain_range: &str,
+    board: &str,
+    trials: u32,
+    seed: u64,
+) -> Result<RangeVsRangeAccumulator, String> {
+    let setup = Setup::parse(hero_range, villain_range, board, trials)?;
+    Ok(accumulate_setup(setup, seed))
+}
+
+/// パース済みのコンボ・ボードに対する [`accumulate`]。キャッシュが正規化した入力を渡す。
+pub(crate) fn accumulate_combos(
+    hero_combos: Vec<(Card, Card)>,
+    villain_combos: Vec<(Card, Card)>,
+    board_cards: Vec<Card>,
+    trials: u32,
+    seed: u64,
+) -> Result<RangeVsRangeAccumulator, String> {
+    let setup = Setup::new(hero_combos, villain_combos, board_cards, trials)?;
+    Ok(accumulate_setup(setup, seed))
+}
+
+fn accumulate_setup(setup: Setup, seed: u64) -> RangeVsRangeAccumulator {
+    let (hero_stats, villain_stats) = setup.simulate(seed);
+    setup.into_accumulator(hero_stats, villain_stats)
+}
+
+/// マルチスレッド版（ネイティブ限定）。
+///
+/// hero コンボ 1 つを作業単位とし、単位ごとに `derive_seed(seed, hero_index)` の
+/// 乱数列を使う。villain 側の集計はスレッドごとに持って最後に加算するので、
+/// 結果は `threads` に依存しない（シリアル版 [`run`] とは乱数列が異なる）。
+/// `threads` が 0 のときは論理コア数を使う。
+#[cfg(not(target_arch = "wasm32"))]
+pub fn run_parallel(
+    hero_range: &str,
+    villain_range: &str,
+    board: &str,
+    trials: u32,
+    seed: u64,
+    threads: usize,
+) -> Result<RangeVsRangePayload, String> {
+    accumulate_parallel(hero_range, villain_range, board, trials, seed, threads)
+        .map(|acc| acc.to_payload())
+}
+
+/// [`run_parallel`] の集計を返す版（ネイティブ限定）。
+#[cfg(not(target_arch = "wasm32"))]
+pub fn accumulate_parallel(
+    hero_range: &str,
+    villain_range: &str,
+    board: &str,
+    trials: u32,
+    seed: u64,
+    threads: usize,
+) -> Result<RangeVsRangeAccumulator, String> {
+    let setup = Setup::parse(hero_range, villain_range, board, trials)?;
+
+    let n_hero = setup.hero_combos.len();
+    let n_villain = setup.villain_combos.len();
+    let (hero_stats, villain_stats) = crate::parallel::fold_units(
+        n_hero,
+        threads,
+        || {
+            (
+                vec![ComboStats::default(); n_hero],
+                vec![ComboStats::default(); n_villain],
+            )
+        },
+        |(hero_stats, villain_stats), h_idx| {
+            let mut rng = seeded_rng(derive_seed(seed, h_idx as u64));
+            setup.simulate_hero_combo(h_idx, &mut rng, &mut hero_stats[h_idx], villain_stats);
+        },
+        |(hero_total, villain_total), (hero_part, villain_part)| {
+            for (t, p) in hero_total.iter_mut().zip(&hero_part) {
+                t.merge(p);
+            }
+            for (t, p) in villain_total.iter_mut().zip(&villain_part) {
+                t.merge(p);
+            }
+        },
+    );
+
+    Ok(setup.into_accumulator(hero_stats, villain_stats))
+}
+
+/// パース・ボード衝突除去を済ませた入力。シリアル版と並列版で共有する。
+struct Setup {
+    hero_combos: Vec<(Card, Card)>,
+    villain_combos: Vec<(Card, Card)>,
+    board_cards: Vec<Card>,
+    community_to_deal: usize,
+    trials: u32,
+}
+
+impl Setup {
+    /// 入力をパースする。どちらかのレンジが空になる場合は両方とも空にする。
+    fn parse(
+        hero_range: &str,
+        villain_range: &str,
+        board: &str,
+        trials: u32,
+    ) -> Result<Self, String> {
+        Self::new(
+            parse_range(hero_range)?,
+            parse_range(villain_range)?,
+            parse_cards(board)?,
+            trials,
+        )
     }
 
-    // ボードと衝突するコンボは事前に弾く。
-    let hero_combos = filter_board_overlap(hero_combos, &board_cards);
-    let villain_combos = filter_board_overlap(villain_combos, &board_cards);
-    if hero_combos.is_empty() || villain_combos.is_empty() {
-        return Ok(empty_payload());
+    /// パース済みの入力から組み立てる。コンボの並びがそのまま乱数の割り当て順になる。
+    fn new(
+        hero_combos: Vec<(Card, Card)>,
+        villain_combos: Vec<(Card, Card)>,
+        board_cards: Vec<Card>,
+        trials: u32,
+    ) -> Result<Self, String> {
+        if board_cards.len() > 5 {
+            return Err("board must be <=5 cards".into());
+        }
+
+        // ボードと衝突するコンボは事前に弾く。
+        let mut hero_combos = filter_board_overlap(hero_combos, &board_cards);
+        let mut villain_combos = filter_board_overlap(villain_combos, &board_cards);
+        if hero_combos.is_empty() || villain_combos.is_empty() {
+            hero_combos.clear();
+            villain_combos.clear();
+        }
+
+        Ok(Self {
+            hero_combos,
+            villain_combos,
+            community_to_deal: 5usize.saturating_sub(board_cards.len()),
+            board_cards,
+            trials: trials.max(1),
+        })
     }
 
-    let community_to_deal = 5usize.saturating_sub(board_cards.len());
-    let trials = trials.max(1);
+    /// 全 hero コンボを 1 本の乱数列で順に走らせ、(hero, villain) のコンボ別集計を返す。
+    fn simulate(&self, seed: u64) -> (Vec<ComboStats>, Vec<ComboStats>) {
+        let mut hero_stats = vec![ComboStats::default(); self.hero_combos.len()];
+        let mut villain_stats = vec![ComboStats::default(); self.villain_combos.len()];
+        let mut rng = seeded_rng(seed);
 
-    let mut hero_stats = vec![ComboStats::default(); hero_combos.len()];
-    let mut villain_stats = vec![ComboStats::default(); villain_combos.len()];
+        for (h_idx, hero_stat) in hero_stats.iter_mut().enumerate() {
+            self.simulate_hero_combo(h_idx, &mut rng, hero_stat, &mut villain_stats);
+        }
 
-    let mut rng = seeded_rng(seed);
+        (hero_stats, villain_stats)
+    }
 
-    // すべての (hero_combo × villain_combo) ペアを列挙。
-    // hands_overlap は単純な 4 枚比較なので、ホットループでも問題ない速度。
-    for (h_idx, &hero) in hero_combos.iter().enumerate() {
-        for (v_idx, &villain) in villain_combos.iter().enumerate() {
+    /// hero コンボ `h_idx` と全 villain コンボのペアを `trials` 回ずつ走らせる。
+    ///
+    /// hands_overlap は単純な 4 枚比較なので、ホットループでも問題ない速度。
+    fn simulate_hero_combo<R: Rng>(
+        &self,
+        h_idx: usize,
+        rng: &mut R,
+        hero_stat: &mut ComboStats,
+        villain_stats: &mut [ComboStats],
+    ) {
+        let hero = self.hero_combos[h_idx];
+        for (v_idx, &villain) in self.villain_combos.iter().enumerate() {
             if hands_overlap(hero, villain) {
                 continue;
             }
 
             // この (hero, villain) ペア用の残デッキ。ボード完成カードのみここから引く。
             let used = [hero.0, hero.1, villain.0, villain.1];
-            let mut all_used: Vec<Card> = board_cards.clone();
+            let mut all_used: Vec<Card> = self.board_cards.clone();
             all_used.extend_from_slice(&used);
             let deck = deck_minus(&all_used);
 
-            for _ in 0..trials {
-                let extras: Vec<Card> = deck.sample(&mut rng, community_to_deal).copied().collect();
-                if extras.len() != community_to_deal {
+            for _ in 0..self.trials {
+                let extras: Vec<Card> = deck.sample(rng, self.community_to_deal).copied().collect();
+                if extras.len() != self.community_to_deal {
                     break;
                 }
                 let mut full_board: Vec<Card> = Vec::with_capacity(5);
-                full_board.extend_from_slice(&board_cards);
+                full_board.extend_from_slice(&self.board_cards);
                 full_board.extend_from_slice(&extras);
 
                 let hero_rank = evaluate_seven(&full_board, hero.0, hero.1);
                 let villain_rank = evaluate_seven(&full_board, villain.0, villain.1);
 
-                hero_stats[h_idx].plays += 1;
+                hero_stat.plays += 1;
                 villain_stats[v_idx].plays += 1;
                 match hero_rank.cmp(&villain_rank) {
-                    Ordering::Greater => hero_stats[h_idx].wins += 1,
+                    Ordering::Greater => hero_stat.wins += 1,
                     Ordering::Less => villain_stats[v_idx].wins += 1,
                     Ordering::Equal => {
-                        hero_stats[h_idx].ties += 1;
+                        hero_stat.ties += 1;
                         villain_stats[v_idx].ties += 1;
                     }
                 }
@@ -101,22 +347,21 @@ pub fn run(
         }
     }
 
-    let mut payload = RangeVsRangePayload {
-        hero: build_entries(&hero_combos, &hero_stats),
-        villain: build_entries(&villain_combos, &villain_stats),
-    };
-
-    sort_desc(&mut payload.hero);
-    sort_desc(&mut payload.villain);
-
-    Ok(payload)
-}
-
-#[inline]
-fn empty_payload() -> RangeVsRangePayload {
-    RangeVsRangePayload {
-        hero: Vec::new(),
-        villain: Vec::new(),
+    fn into_accumulator(
+        self,
+        hero: Vec<ComboStats>,
+        villain: Vec<ComboStats>,
+    ) -> RangeVsRangeAccumulator {
+        let combo_strings = |combos: &[(Card, Card)]| -> Vec<String> {
+            combos.iter().map(|&(a, b)| pair_string(a, b)).collect()
+        };
+        RangeVsRangeAccumulator {
+            board: cards_string(&self.board_cards),
+            hero_combos: combo_strings(&self.hero_combos),
+            hero,
+            villain_combos: combo_strings(&self.villain_combos),
+            villain,
+        }
     }
 }
 
@@ -132,21 +377,171 @@ fn hands_overlap(a: (Card, Card), b: (Card, Card)) -> bool {
     a.0 == b.0 || a.0 == b.1 || a.1 == b.0 || a.1 == b.1
 }
 
-fn build_entries(combos: &[(Card, Card)], stats: &[ComboStats]) -> Vec<RangeEquityEntry> {
+fn build_entries(combos: &[String], stats: &[ComboStats]) -> Vec<RangeEquityEntry> {
     combos
         .iter()
         .zip(stats.iter())
-        .map(|(&(a, b), s)| RangeEquityEntry {
-            hand: pair_string(a, b),
-            equity: if s.plays == 0 {
-                0.0
-            } else {
-                (s.wins as f64 + s.ties as f64 * 0.5) / s.plays as f64
-            },
+        .map(|(hand, s)| RangeEquityEntry {
+            hand: hand.clone(),
+            equity: s.equity(),
         })
         .collect()
 }
 
+/// セルの equity はクラス内の wins/ties/plays を合算して求める（対戦数で重み付けした平均）。
+/// 1 度も対戦しなかったコンボ（相手のコンボが全部ブロックされた等）は
+/// コンボ数にだけ数え、equity・最小・最大には入れない。
+fn build_grid(combos: &[(Card, Card)], stats: &[ComboStats]) -> Vec<RangeGridCell> {
+    let mut cells: Vec<RangeGridCell> = HandClass::all()
+        .into_iter()
+        .map(|class| RangeGridCell {
+            class: class.to_string(),
+            combos: 0,
+            equity: None,
+            min: None,
+            max: None,
+        })
+        .collect();
+    let mut totals = [ComboStats::default(); CLASS_COUNT];
+    for (&(a, b), s) in combos.iter().zip(stats) {
+        let index = HandClass::from_combo(a, b).grid_index();
+        let cell = &mut cells[index];
+        cell.combos += 1;
+        if s.plays == 0 {
+            continue;
+        }
+        totals[index].merge(s);
+        let equity = s.equity();
+        cell.min = Some(cell.min.map_or(equity, |m| m.min(equity)));
+        cell.max = Some(cell.max.map_or(equity, |m| m.max(equity)));
+    }
+    for (cell, total) in cells.iter_mut().zip(&totals) {
+        if total.plays > 0 {
+            cell.equity = Some(total.equity());
+        }
+    }
+    cells
+}
+
 fn sort_desc(entries: &mut [RangeEquityEntry]) {
     entries.sort_by(|a, b| b.equity.partial_cmp(&a.equity).unwrap_or(Ordering::Equal));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summed(a: &[ComboStats], b: &[ComboStats]) -> Vec<ComboStats> {
+        a.iter()
+            .zip(b)
+            .map(|(x, y)| ComboStats {
+                wins: x.wins + y.wins,
+                ties: x.ties + y.ties,
+                plays: x.plays + y.plays,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn merge_sums_runs_with_different_seeds() {
+        let a = accumulate("QQ+,AKs", "JJ,AQo", "Kh 7c 2d", 3, 1).unwrap();
+        let b = accumulate("QQ+,AKs", "JJ,AQo", "Kh 7c 2d", 5, 2).unwrap();
+        let mut merged = a.clone();
+        merged.merge(&b).unwrap();
+        assert_eq!(merged.hero_combos, a.hero_combos);
+        assert_eq!(merged.hero, summed(&a.hero, &b.hero));
+        assert_eq!(merged.villain, summed(&a.villain, &b.villain));
+        assert!(merged.hero.iter().all(|s| s.plays > 0));
+    }
+
+    #[test]
+    fn merge_rejects_mismatched_inputs_without_changing_anything() {
+        let base = accumulate("QQ+", "JJ", "Kh 7c 2d", 2, 1).unwrap();
+        for other in [
+            accumulate("QQ+", "JJ", "Kh 7c 3d", 2, 2).unwrap(),
+            accumulate("KK+", "JJ", "Kh 7c 2d", 2, 2).unwrap(),
+            accumulate("QQ+", "TT", "Kh 7c 2d", 2, 2).unwrap(),
+        ] {
+            let mut acc = base.clone();
+            assert!(acc.merge(&other).is_err());
+            assert_eq!(acc, base);
+        }
+
+        // ラベルは同じでも集計列が短い（壊れたデシリアライズ結果など）
+        let mut truncated = base.clone();
+        truncated.hero.pop();
+        let mut acc = base.clone();
+        assert!(acc.merge(&truncated).is_err());
+        assert_eq!(acc, base);
+        assert!(truncated.merge(&base).is_err());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn run_parallel_does_not_depend_on_thread_count() {
+        let args = ("QQ+,AKs,AQo", "JJ-99,AJs+", "Kh 7c 2d", 3, 21);
+        let one = accumulate_parallel(args.0, args.1, args.2, args.3, args.4, 1).unwrap();
+        for threads in [2, 4, 0] {
+            let many =
+                accumulate_parallel(args.0, args.1, args.2, args.3, args.4, threads).unwrap();
+            assert_eq!(many, one, "threads = {}", threads);
+        }
+        let payload = run_parallel(args.0, args.1, args.2, args.3, args.4, 4).unwrap();
+        assert_eq!(payload.hero.len(), one.hero_combos.len());
+    }
+
+    #[test]
+    fn accumulator_survives_a_postcard_round_trip() {
+        let acc = accumulate("QQ+,AKs", "JJ,AQo", "Kh 7c 2d", 2, 4).unwrap();
+        let bytes = postcard::to_allocvec(&acc).unwrap();
+        let decoded: RangeVsRangeAccumulator = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, acc);
+    }
+
+    #[test]
+    fn grid_groups_combos_by_class_after_board_blockers() {
+        let grid = run_grid("AA,AKs", "KK,QJo", "As 2c 7d", 2, 9).unwrap();
+        assert_eq!(grid.hero.len(), CLASS_COUNT);
+        assert_eq!(grid.villain.len(), CLASS_COUNT);
+
+        let cell = |cells: &[RangeGridCell], class: &str| {
+            cells[HandClass::parse(class).unwrap().grid_index()].combos
+        };
+        assert_eq!(cell(&grid.hero, "AA"), 3);
+        assert_eq!(cell(&grid.hero, "AKs"), 3);
+        assert_eq!(cell(&grid.villain, "KK"), 6);
+        assert_eq!(cell(&grid.villain, "QJo"), 12);
+        assert_eq!(grid.hero.iter().map(|c| c.combos).sum::<usize>(), 6);
+
+        for c in grid.hero.iter().chain(&grid.villain) {
+            match (c.equity, c.min, c.max) {
+                (Some(e), Some(lo), Some(hi)) => {
+                    assert!(c.combos > 0);
+                    assert!(lo <= e && e <= hi, "{}", c.class);
+                }
+                (None, None, None) => assert_eq!(c.combos, 0, "{}", c.class),
+                _ => panic!("partial cell {}", c.class),
+            }
+        }
+        assert_eq!(grid.hero[0].class, "AA");
+        assert!(grid.hero[0].equity.unwrap() > 0.9);
+    }
+
+    #[test]
+    fn grid_equity_weights_combos_by_plays() {
+        // AhKh / AdKd は villain の KhKd をブロックするので、AKs の中で対戦数が違う
+        let (hero, villain, board) = ("AKs", "KhKd,QQ", "2c 7d 9s");
+        let grid = run_grid(hero, villain, board, 3, 11).unwrap();
+        let acc = accumulate(hero, villain, board, 3, 11).unwrap();
+        let plays: Vec<u64> = acc.hero.iter().map(|s| s.plays).collect();
+        assert!(plays.iter().any(|&p| p != plays[0]), "{:?}", plays);
+
+        let mut total = ComboStats::default();
+        for s in &acc.hero {
+            total.merge(s);
+        }
+        let cell = &grid.hero[HandClass::parse("AKs").unwrap().grid_index()];
+        assert_eq!(cell.combos, 4);
+        assert_eq!(cell.equity, Some(total.equity()));
+    }
+}
@@ -0,0 +1,63 @@
+//! 文字列入力でのサイドポット精算（[`crate::settlement::settle`]）。
+//!
+//! `hands` は席順のセミコロン区切り（`"AsKs; ; QdQc"`）で、フォールドした席は空でよい。
+//! `chip` が 0 なら端数も等分し、正なら `chip` 単位で分けて余りをボタンの左から配る。
+
+use rs_poker::core::Card;
+
+use crate::dto::{PotSnapshot, SettlementPayload};
+use crate::parser::{parse_cards_ordered, parse_two_cards};
+use crate::settlement::{settle, OddChipRule, Seat};
+
+pub fn run(
+    contributions: &[f64],
+    folded: &[bool],
+    hands: &str,
+    board: &str,
+    button: usize,
+    chip: f64,
+) -> Result<SettlementPayload, String> {
+    let n = contributions.len();
+    let hands: Vec<Option<[Card; 2]>> = if hands.trim().is_empty() {
+        vec![None; n]
+    } else {
+        hands
+            .split(';')
+            .map(str::trim)
+            .map(|h| {
+                if h.is_empty() {
+                    Ok(None)
+                } else {
+                    parse_two_cards(h).map(|(a, b)| Some([a, b]))
+                }
+            })
+            .collect::<Result<_, _>>()?
+    };
+    if folded.len() != n || hands.len() != n {
+        return Err(format!(
+            "expected {} folded flags and hands, got {} and {}",
+            n,
+            folded.len(),
+            hands.len()
+        ));
+    }
+    let seats: Vec<Seat> = (0..n)
+        .map(|i| Seat {
+            contribution: contributions[i],
+            folded: folded[i],
+            hand: hands[i],
+        })
+        .collect();
+    let rule = if chip == 0.0 {
+        OddChipRule::SplitEvenly
+    } else {
+        OddChipRule::LeftOfButton { button, chip }
+    };
+    let result = settle(&seats, &parse_cards_ordered(board)?, rule)?;
+    Ok(SettlementPayload {
+        pots: result.pots.iter().map(PotSnapshot::from).collect(),
+        winnings: result.winnings,
+        net: result.net,
+        showdown: result.showdown,
+    })
+}
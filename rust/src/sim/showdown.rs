@@ -0,0 +1,127 @@
+//! 確定したボードでの 2 ハンドの勝敗と、その決め手。
+//!
+//! 勝敗は `Rank` の大小だけで決め、説明は [`crate::rank::hand_detail`] の並び（役の札 → キッカー）を
+//! 先頭から比べて最初に差が出た位置から作る。ショーダウンクイズの解説用。
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use rs_poker::core::Card;
+
+use crate::dto::ShowdownComparison;
+use crate::parser::{parse_cards, parse_two_cards};
+use crate::rank::{made_card_count, rank_index, HandDetail};
+use crate::sim::evaluate::{entry, evaluate_detail};
+
+const ORDINALS: [&str; 5] = ["first", "second", "third", "fourth", "fifth"];
+
+pub fn run(first: &str, second: &str, board: &str) -> Result<ShowdownComparison, String> {
+    let (a1, a2) = parse_two_cards(first)?;
+    let (b1, b2) = parse_two_cards(second)?;
+    let board_cards = parse_cards(board)?;
+    if board_cards.len() != 5 {
+        return Err(format!(
+            "expected 5 board cards, got {} from '{}'",
+            board_cards.len(),
+            board
+        ));
+    }
+    let mut used = vec![a1, a2, b1, b2];
+    used.extend_from_slice(&board_cards);
+    let unique: HashSet<_> = used.iter().collect();
+    if unique.len() != used.len() {
+        return Err("duplicate card between hands and board".to_string());
+    }
+
+    let (first_rank, first_detail) = evaluate_detail(&board_cards, a1, a2);
+    let (second_rank, second_detail) = evaluate_detail(&board_cards, b1, b2);
+    let winner = match first_rank.cmp(&second_rank) {
+        Ordering::Greater => 0,
+        Ordering::Less => 1,
+        Ordering::Equal => 2,
+    };
+    let (win, lose) = if winner == 1 {
+        (&second_detail, &first_detail)
+    } else {
+        (&first_detail, &second_detail)
+    };
+    let reason = if rank_index(&first_rank) != rank_index(&second_rank) {
+        "higher category".to_string()
+    } else {
+        deciding_factor(rank_index(&first_rank), win, lose, &board_cards)
+    };
+
+    Ok(ShowdownComparison {
+        winner,
+        first: entry(a1, a2, &first_rank, first_detail),
+        second: entry(b1, b2, &second_rank, second_detail),
+        reason,
+    })
+}
+
+/// 同じカテゴリの 2 ハンドの決め手。`win` が勝った側（スプリットならどちらでも）。
+fn deciding_factor(index: usize, win: &HandDetail, lose: &HandDetail, board: &[Card]) -> String {
+    let diff = win
+        .best_five
+        .iter()
+        .zip(&lose.best_five)
+        .position(|(w, l)| w.value != l.value);
+    let Some(i) = diff else {
+        let board_plays = [win, lose]
+            .iter()
+            .all(|d| d.best_five.iter().all(|c| board.contains(c)));
+        return if board_plays {
+            "board plays - split".to_string()
+        } else {
+            "same hand - split".to_string()
+        };
+    };
+
+    let made = made_card_count(index);
+    let what = match (index, i) {
+        (0, 0) => "higher card".to_string(),
+        (1, 0) => "higher pair".to_string(),
+        (2, 0) => "higher top pair".to_string(),
+        (2, 2) => "higher second pair".to_string(),
+        (3, 0) | (6, 0) => "higher three of a kind".to_string(),
+        (6, _) => "higher pair".to_string(),
+        (7, 0) => "higher four of a kind".to_string(),
+        (4 | 8, _) => "higher straight".to_string(),
+        (5, 0) => "higher flush".to_string(),
+        (5, _) => format!("higher flush, {} card", ORDINALS[i]),
+        _ if win.best_five.len() - made == 1 => "kicker".to_string(),
+        _ => format!("{} kicker", ORDINALS[i - made]),
+    };
+    format!(
+        "{} {} vs {}",
+        what,
+        win.best_five[i].value.to_char(),
+        lose.best_five[i].value.to_char()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explains_the_deciding_factor() {
+        let cases = [
+            ("AhKd", "QcQd", "Qs7c4h2d9s", 1, "higher category"),
+            ("KhKd", "QcQd", "As7c4h2d9s", 0, "higher pair K vs Q"),
+            ("AhQd", "AcJd", "AsKc4h2d9s", 0, "second kicker Q vs J"),
+            ("Kh9d", "Kc8s", "AsAd4h4c4d", 2, "board plays - split"),
+            ("Ah5d", "Ac5c", "Ks5s4h7d2c", 2, "same hand - split"),
+            ("9h8h", "8d4d", "7c6s5h2d2c", 0, "higher straight 9 vs 8"),
+            ("AhKh", "AcJc", "AsKs7d7c2h", 0, "higher second pair K vs 7"),
+            ("AhQh", "AdJd", "AsTc7d7c2h", 0, "kicker Q vs J"),
+        ];
+        for (first, second, board, winner, reason) in cases {
+            let result = run(first, second, board).unwrap();
+            assert_eq!(result.winner, winner, "{first} vs {second} on {board}");
+            assert_eq!(result.reason, reason, "{first} vs {second} on {board}");
+        }
+        assert!(run("AhKh", "AhQd", "2c3c4c5s9d").is_err());
+        assert!(run("AhKh", "QdJd", "2c3c4c").is_err());
+    }
+}
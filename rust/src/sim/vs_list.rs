@@ -2,20 +2,144 @@
 //!
 //! 各相手ごとに独立して `trials` 回ボードを走らせ、勝敗と
 //! 「hero がどんな役で勝ったか / 負けたか」を集計する。
+//! 集計は [`VsListAccumulator`] として取り出せ、別シードの実行同士を合算できる。
 
 use std::cmp::Ordering;
 use std::collections::HashSet;
 
 use rand::prelude::IndexedRandom;
 use rs_poker::core::Card;
+use serde::{Deserialize, Serialize};
 
-use crate::cards::{deck_minus, pair_string};
+use crate::cards::{cards_string, deck_minus, pair_string};
 use crate::dto::{CombinedEntry, CombinedPayload};
 use crate::parser::{parse_cards, parse_hands_list, parse_two_cards};
 use crate::rank::{rank_index, RankBuckets};
 use crate::rng::seeded_rng;
 use crate::sim::evaluate_seven;
 
+/// 相手 1 人分の集計。カウンタはすべて hero 目線。
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpponentStats {
+    /// 相手ハンド（`"As Ks"` 形式）。
+    pub hand: String,
+    pub wins: u32,
+    pub ties: u32,
+    pub plays: u32,
+    /// hero の役カテゴリ別の勝ち/引き分け/負け。
+    pub buckets: RankBuckets,
+}
+
+impl OpponentStats {
+    fn empty(opp: (Card, Card)) -> Self {
+        Self {
+            hand: pair_string(opp.0, opp.1),
+            ..Self::default()
+        }
+    }
+
+    /// 別の集計を加算する。
+    pub fn merge(&mut self, other: &OpponentStats) {
+        self.wins = self.wins.saturating_add(other.wins);
+        self.ties = self.ties.saturating_add(other.ties);
+        self.plays = self.plays.saturating_add(other.plays);
+        self.buckets.merge(&other.buckets);
+    }
+
+    fn to_entry(&self) -> CombinedEntry {
+        CombinedEntry {
+            hand: self.hand.clone(),
+            count: self.plays,
+            win: self
+                .plays
+                .saturating_sub(self.wins)
+                .saturating_sub(self.ties),
+            tie: self.ties,
+            // 相手目線の lose = hero の win 数。
+            lose: self.wins,
+            results: (&self.buckets).into(),
+        }
+    }
+}
+
+/// hero vs list の途中集計。
+///
+/// `opponents` は入力順。同じシードの実行を合算すると同じサンプルを
+/// 二重に数えるだけなので、合算する実行にはそれぞれ別のシードを使うこと。
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VsListAccumulator {
+    /// hero ハンド（`"As Ks"` 形式）。
+    pub hero: String,
+    /// ボード（`"2h 7c 9d"` 形式）。
+    pub board: String,
+    pub opponents: Vec<OpponentStats>,
+}
+
+impl VsListAccumulator {
+    /// 同じ hero・ボード・相手リストに対する別の集計を加算する。
+    ///
+    /// 入力が一致しない場合は何も変更せずにエラーを返す。
+    pub fn merge(&mut self, other: &VsListAccumulator) -> Result<(), String> {
+        if self.hero != other.hero || self.board != other.board {
+            return Err("cannot merge results for different hero hands or boards".into());
+        }
+        if self.opponents.len() != other.opponents.len()
+            || self
+                .opponents
+                .iter()
+                .zip(&other.opponents)
+                .any(|(a, b)| a.hand != b.hand)
+        {
+            return Err("cannot merge results for different opponent lists".into());
+        }
+        for (t, p) in self.opponents.iter_mut().zip(&other.opponents) {
+            t.merge(p);
+        }
+        Ok(())
+    }
+
+    /// 相手を equity 降順に並べ、末尾に hero の合算エントリを付けた JS 向けの形へ変換する。
+    pub fn to_payload(&self) -> CombinedPayload {
+        let mut entries = self.opponent_entries();
+        sort_by_equity_desc(&mut entries);
+
+        // hero の集計エントリは末尾に追加（v1 互換）。
+        let hero = self.hero_entry();
+        let equity = equity_of(&hero);
+        entries.push(hero);
+
+        CombinedPayload {
+            hand: self.hero.clone(),
+            equity,
+            data: entries,
+        }
+    }
+
+    /// 相手ごとのエントリ（入力順、相手目線の win/lose）。
+    pub(crate) fn opponent_entries(&self) -> Vec<CombinedEntry> {
+        self.opponents.iter().map(OpponentStats::to_entry).collect()
+    }
+
+    /// 全相手分を合算した hero のエントリ。
+    pub(crate) fn hero_entry(&self) -> CombinedEntry {
+        let mut hero_stats = OpponentStats::default();
+        for opp in &self.opponents {
+            hero_stats.merge(opp);
+        }
+        CombinedEntry {
+            hand: self.hero.clone(),
+            count: hero_stats.plays,
+            win: hero_stats.wins,
+            tie: hero_stats.ties,
+            lose: hero_stats
+                .plays
+                .saturating_sub(hero_stats.wins)
+                .saturating_sub(hero_stats.ties),
+            results: (&hero_stats.buckets).into(),
+        }
+    }
+}
+
 pub fn run(
     hero: &str,
     board: &str,
@@ -23,6 +147,17 @@ pub fn run(
     trials: u32,
     seed: u64,
 ) -> Result<CombinedPayload, String> {
+    accumulate(hero, board, compare, trials, seed).map(|acc| acc.to_payload())
+}
+
+/// [`run`] と同じ計算を行い、payload に変換する前の集計を返す。
+pub fn accumulate(
+    hero: &str,
+    board: &str,
+    compare: &str,
+    trials: u32,
+    seed: u64,
+) -> Result<VsListAccumulator, String> {
     let hero_pair = parse_two_cards(hero)?;
     let board_cards = parse_cards(board)?;
     let opponents = parse_hands_list(compare)?;
@@ -30,64 +165,49 @@ pub fn run(
         return Err("No compare hands provided".into());
     }
 
+    Ok(accumulate_cards(
+        hero_pair,
+        &board_cards,
+        &opponents,
+        trials,
+        seed,
+    ))
+}
+
+/// パース済みのカードに対する [`accumulate`]。キャッシュが正規化した入力を渡す。
+pub(crate) fn accumulate_cards(
+    hero_pair: (Card, Card),
+    board_cards: &[Card],
+    opponents: &[(Card, Card)],
+    trials: u32,
+    seed: u64,
+) -> VsListAccumulator {
     // ボードがまだ完成していない場合、足りないカード枚数。
     let community_to_deal = 5usize.saturating_sub(board_cards.len());
 
-    // hero 集計用バケット（全相手分の合算）。
-    let mut hero_buckets = RankBuckets::default();
-    let mut hero_plays = 0u32;
-    let mut hero_wins = 0u32;
-    let mut hero_ties = 0u32;
-
-    let mut entries: Vec<CombinedEntry> = Vec::with_capacity(opponents.len());
     let mut rng = seeded_rng(seed);
+    let stats = opponents
+        .iter()
+        .map(|&opp| {
+            simulate_against_opponent(
+                hero_pair,
+                opp,
+                board_cards,
+                community_to_deal,
+                trials,
+                &mut rng,
+            )
+        })
+        .collect();
 
-    for &(va, vb) in &opponents {
-        if let Some(entry) = simulate_against_opponent(
-            hero_pair,
-            (va, vb),
-            &board_cards,
-            community_to_deal,
-            trials,
-            &mut rng,
-            &mut hero_buckets,
-            &mut hero_plays,
-            &mut hero_wins,
-            &mut hero_ties,
-        ) {
-            entries.push(entry);
-        }
+    VsListAccumulator {
+        hero: pair_string(hero_pair.0, hero_pair.1),
+        board: cards_string(board_cards),
+        opponents: stats,
     }
-
-    sort_by_equity_desc(&mut entries);
-
-    // hero の集計エントリは末尾に追加（v1 互換）。
-    entries.push(CombinedEntry {
-        hand: pair_string(hero_pair.0, hero_pair.1),
-        count: hero_plays,
-        win: hero_wins,
-        tie: hero_ties,
-        lose: hero_plays
-            .saturating_sub(hero_wins)
-            .saturating_sub(hero_ties),
-        results: (&hero_buckets).into(),
-    });
-
-    let equity = if hero_plays == 0 {
-        0.0
-    } else {
-        (hero_wins as f64 + hero_ties as f64 * 0.5) / hero_plays as f64
-    };
-
-    Ok(CombinedPayload {
-        hand: pair_string(hero_pair.0, hero_pair.1),
-        equity,
-        data: entries,
-    })
 }
 
-/// 1 人の相手に対する MC ループ。hero 集計用の参照も受け取って加算する。
-#[allow(clippy::too_many_arguments)]
+/// 1 人の相手に対する MC ループ。
 fn simulate_against_opponent(
     hero: (Card, Card),
     opp: (Card, Card),
@@ -95,11 +215,7 @@ fn simulate_against_opponent(
     community_to_deal: usize,
     trials: u32,
     rng: &mut impl rand::Rng,
-    hero_buckets: &mut RankBuckets,
-    hero_plays: &mut u32,
-    hero_wins: &mut u32,
-    hero_ties: &mut u32,
-) -> Option<CombinedEntry> {
+) -> OpponentStats {
     // hero / board / opp で重複があれば、その相手はスキップしてゼロ件で返す。
     let mut used: Vec<Card> = Vec::with_capacity(2 + board.len() + 2);
     used.push(hero.0);
@@ -109,21 +225,11 @@ fn simulate_against_opponent(
     used.push(opp.1);
     let unique: HashSet<&Card> = used.iter().collect();
     if unique.len() != used.len() {
-        return Some(CombinedEntry {
-            hand: pair_string(opp.0, opp.1),
-            count: 0,
-            win: 0,
-            tie: 0,
-            lose: 0,
-            results: (&RankBuckets::default()).into(),
-        });
+        return OpponentStats::empty(opp);
     }
 
     let deck = deck_minus(&used);
-    let mut buckets = RankBuckets::default();
-    let mut wins = 0u32;
-    let mut ties = 0u32;
-    let mut plays = 0u32;
+    let mut stats = OpponentStats::empty(opp);
 
     for _ in 0..trials {
         let extras: Vec<Card> = deck.sample(rng, community_to_deal).copied().collect();
@@ -139,39 +245,23 @@ fn simulate_against_opponent(
         let opp_rank = evaluate_seven(&full_board, opp.0, opp.1);
         let hero_idx = rank_index(&hero_rank);
 
-        plays += 1;
+        stats.plays += 1;
         match hero_rank.cmp(&opp_rank) {
             Ordering::Greater => {
-                wins += 1;
-                buckets.win[hero_idx] += 1;
-                hero_buckets.win[hero_idx] += 1;
+                stats.wins += 1;
+                stats.buckets.win[hero_idx] += 1;
             }
             Ordering::Equal => {
-                ties += 1;
-                buckets.tie[hero_idx] += 1;
-                hero_buckets.tie[hero_idx] += 1;
+                stats.ties += 1;
+                stats.buckets.tie[hero_idx] += 1;
             }
             Ordering::Less => {
-                buckets.lose[hero_idx] += 1;
-                hero_buckets.lose[hero_idx] += 1;
+                stats.buckets.lose[hero_idx] += 1;
             }
         }
     }
 
-    let opp_wins = plays.saturating_sub(wins).saturating_sub(ties);
-    *hero_plays += plays;
-    *hero_wins += wins;
-    *hero_ties += ties;
-
-    Some(CombinedEntry {
-        hand: pair_string(opp.0, opp.1),
-        count: plays,
-        win: opp_wins,
-        tie: ties,
-        // 相手目線の lose = hero の win 数。
-        lose: wins,
-        results: (&buckets).into(),
-    })
+    stats
 }
 
 /// equity (= (win + tie / 2) / count) の降順に並べる。
@@ -191,3 +281,51 @@ fn equity_of(e: &CombinedEntry) -> f64 {
         (e.win as f64 + e.tie as f64 * 0.5) / e.count as f64
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_sums_opponent_stats_and_buckets() {
+        let a = accumulate("As Ks", "Qh 7c 2d", "Qs Jd; 9h 9d", 4, 1).unwrap();
+        let b = accumulate("As Ks", "Qh 7c 2d", "Qs Jd; 9h 9d", 6, 2).unwrap();
+        let mut merged = a.clone();
+        merged.merge(&b).unwrap();
+        for ((m, x), y) in merged.opponents.iter().zip(&a.opponents).zip(&b.opponents) {
+            assert_eq!(m.hand, x.hand);
+            assert_eq!(m.wins, x.wins + y.wins);
+            assert_eq!(m.ties, x.ties + y.ties);
+            assert_eq!(m.plays, x.plays + y.plays);
+            assert_eq!(m.plays, 10);
+            for i in 0..9 {
+                assert_eq!(m.buckets.win[i], x.buckets.win[i] + y.buckets.win[i]);
+                assert_eq!(m.buckets.tie[i], x.buckets.tie[i] + y.buckets.tie[i]);
+                assert_eq!(m.buckets.lose[i], x.buckets.lose[i] + y.buckets.lose[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn merge_rejects_mismatched_inputs_without_changing_anything() {
+        let base = accumulate("As Ks", "Qh 7c 2d", "Qs Jd; 9h 9d", 2, 1).unwrap();
+        for other in [
+            accumulate("Ah Kh", "Qh 7c 2d", "Qs Jd; 9h 9d", 2, 2).unwrap(),
+            accumulate("As Ks", "Qh 7c 3d", "Qs Jd; 9h 9d", 2, 2).unwrap(),
+            accumulate("As Ks", "Qh 7c 2d", "Qs Jd; 8h 8d", 2, 2).unwrap(),
+            accumulate("As Ks", "Qh 7c 2d", "Qs Jd", 2, 2).unwrap(),
+        ] {
+            let mut acc = base.clone();
+            assert!(acc.merge(&other).is_err());
+            assert_eq!(acc, base);
+        }
+    }
+
+    #[test]
+    fn accumulator_survives_a_postcard_round_trip() {
+        let acc = accumulate("As Ks", "Qh 7c 2d", "Qs Jd; 9h 9d", 3, 5).unwrap();
+        let bytes = postcard::to_allocvec(&acc).unwrap();
+        let decoded: VsListAccumulator = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, acc);
+    }
+}
@@ -1,3 +1,5 @@
 export const DEFAULT_WASM_URL = "/wasm-v1/rust_wasm_bg.wasm";
+// `bun run build:wasm-shards` で生成する、シャード分割 export 入りのビルド
+export const SHARDED_WASM_URL = "/wasm-v1/rust_wasm_shards_bg.wasm";
 export const PAGE_SIZE = 64 * 1024; // WebAssembly page size in bytes
 export const HEAP_START = 1024; // simple bump allocator starting offset
@@ -116,5 +116,12 @@ export function createHeap(memory: WebAssembly.Memory) {
     ensureCapacity(memory, heap);
     return ptr;
   };
-  return { writeString, allocU32 };
+  const allocF64 = (count: number) => {
+    heap = (heap + 7) & ~7;
+    const ptr = heap;
+    heap += count * 8;
+    ensureCapacity(memory, heap);
+    return ptr;
+  };
+  return { writeString, allocU32, allocF64 };
 }
@@ -1,13 +1,26 @@
-export { DEFAULT_WASM_URL } from "./constants";
+export { DEFAULT_WASM_URL, SHARDED_WASM_URL } from "./constants";
+export { evaluateSixMaxPreflop } from "./simulation/evaluate-six-max-preflop";
+// Binary result format (Uint32Array) and its decoders
+export {
+  runSimulateRangeVsRangeEquityBinary,
+  runSimulateVsListWithRanksBinary,
+} from "./simulation/simulate-binary-core";
 export { simulateMultiHandEquity } from "./simulation/simulate-multi-hand-equity";
 export { simulateOpenRangesMonteCarlo } from "./simulation/simulate-open-ranges-monte-carlo";
 export { simulateRangeVsRangeEquity } from "./simulation/simulate-range-vs-range-equity";
 // Worker-backed APIs (see simulation.worker.ts)
 export { simulateRankDistribution } from "./simulation/simulate-rank-distribution";
+// Multi-worker (map/reduce) APIs; need the `build:wasm-shards` artifact
+export {
+  simulateRankDistributionSharded,
+  simulateVsListWithRanksSharded,
+} from "./simulation/simulate-sharded";
 export { simulateVsListEquity } from "./simulation/simulate-vs-list-equity";
 export {
+  replayVsListWithRanksTrial,
   simulateVsListWithRanks,
   simulateVsListWithRanksTrace,
+  streamVsListWithRanksTrace,
 } from "./simulation/simulate-vs-list-with-ranks";
 export type {
   CombinedPayload,
@@ -15,6 +28,7 @@ export type {
   EquityPayload,
   EvaluateHandsRankingParams,
   HandRankingEntry,
+  MonteCarloRunout,
   MonteCarloTraceEntry,
   MultiHandEquityEntry,
   MultiHandEquityParams,
@@ -29,7 +43,17 @@ export type {
   RankOutcome,
   RankOutcomeResults,
   RankResults,
+  ShardedSimulationOptions,
+  SixMaxAction,
+  SixMaxPosition,
+  SixMaxPreflopParams,
+  SixMaxPreflopPayload,
 } from "./types";
+export {
+  decodeBinaryHeader,
+  decodeRangeVsRangeBinary,
+  decodeVsListWithRanksBinary,
+} from "./utils/binary-format";
 // Main-thread APIs (direct WASM calls)
 export { evaluateHandsRanking } from "./utils/evaluate-hands-ranking";
 export { parseRangeToHands } from "./utils/parse-range";
@@ -0,0 +1,91 @@
+import { DEFAULT_WASM_URL } from "../constants";
+import { createHeap, loadWasm } from "../loader";
+import type {
+  SixMaxAction,
+  SixMaxPosition,
+  SixMaxPreflopParams,
+  SixMaxPreflopPayload,
+} from "../types";
+
+const POSITIONS: SixMaxPosition[] = ["UTG", "MP", "CO", "BTN", "SB", "BB"];
+
+const ACTIONS: SixMaxAction[] = ["open", "call", "threeBet"];
+
+const joinRanges = (ranges: Record<SixMaxPosition, string>) =>
+  POSITIONS.map((position) => ranges[position].trim()).join(";");
+
+export async function runEvaluateSixMaxPreflop({
+  heroPosition,
+  heroRange,
+  action,
+  openerPosition,
+  openRanges,
+  callRanges,
+  threeBetRanges,
+  smallBlind = 50,
+  bigBlind = 100,
+  openSize,
+  threeBetSize,
+  trials,
+  seed = 123456789n,
+  wasmUrl = DEFAULT_WASM_URL,
+}: SixMaxPreflopParams): Promise<SixMaxPreflopPayload> {
+  const { exports, memory } = await loadWasm(wasmUrl);
+  const evaluate = exports.evaluate_six_max_preflop;
+  if (typeof evaluate !== "function") {
+    throw new Error("WASM export 'evaluate_six_max_preflop' not found");
+  }
+
+  const { writeString, allocF64 } = createHeap(memory);
+  const heroBuf = writeString(heroRange.trim());
+  const openBuf = writeString(joinRanges(openRanges));
+  const callBuf = writeString(joinRanges(callRanges));
+  const threeBetBuf = writeString(joinRanges(threeBetRanges));
+
+  const outLen = 13;
+  const outPtr = allocF64(outLen);
+
+  const rc = evaluate(
+    heroBuf.ptr,
+    heroBuf.len,
+    openBuf.ptr,
+    openBuf.len,
+    callBuf.ptr,
+    callBuf.len,
+    threeBetBuf.ptr,
+    threeBetBuf.len,
+    POSITIONS.indexOf(heroPosition),
+    ACTIONS.indexOf(action),
+    openerPosition === undefined
+      ? POSITIONS.length
+      : POSITIONS.indexOf(openerPosition),
+    smallBlind,
+    bigBlind,
+    openSize,
+    threeBetSize,
+    trials,
+    seed,
+    outPtr,
+    outLen,
+  );
+  if (rc < 0) {
+    throw new Error(`evaluate_six_max_preflop failed with code ${rc}`);
+  }
+
+  const out = new Float64Array(memory.buffer, outPtr, outLen);
+  const continueFrequency = {} as Record<SixMaxPosition, number>;
+  POSITIONS.forEach((position, i) => {
+    continueFrequency[position] = out[7 + i] ?? 0;
+  });
+
+  return {
+    deals: out[0] ?? 0,
+    folds: out[1] ?? 0,
+    equity: out[2] ?? 0,
+    averageOpponents: out[3] ?? 0,
+    ev: out[4] ?? 0,
+    potBefore: out[5] ?? 0,
+    investment: out[6] ?? 0,
+    continueFrequency,
+  };
+}
@@ -0,0 +1,19 @@
+import {
+  resolveWorkerWasmUrl,
+  runWorkerRequest,
+} from "@/lib/worker/wasm-worker-client";
+import { DEFAULT_WASM_URL } from "../constants";
+import type { SixMaxPreflopParams, SixMaxPreflopPayload } from "../types";
+
+export async function evaluateSixMaxPreflop(
+  params: SixMaxPreflopParams,
+): Promise<SixMaxPreflopPayload> {
+  const request = {
+    type: "evaluateSixMaxPreflop",
+    params: {
+      ...params,
+      wasmUrl: resolveWorkerWasmUrl(params.wasmUrl, DEFAULT_WASM_URL),
+    },
+  };
+  return runWorkerRequest<SixMaxPreflopPayload>(request);
+}
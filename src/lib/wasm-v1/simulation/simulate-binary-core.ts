@@ -0,0 +1,123 @@
+import { DEFAULT_WASM_URL } from "../constants";
+import { createHeap, loadWasm } from "../loader";
+import type { RangeVsRangeParams, SimulateParams } from "../types";
+import { BINARY_HEADER_LEN } from "../utils/binary-format";
+
+// バイナリ形式（utils/binary-format.ts）で結果を返す WASM 直呼び。
+// 生の Uint32Array を返すので、worker 間の受け渡しや保存にそのまま使える。
+
+const MAX_STARTING_HANDS = 1326;
+const RANGE_VS_RANGE_RECORD_LEN = 4;
+const VS_LIST_WITH_RANKS_RECORD_LEN = 35;
+
+const normalizeRangeInput = (range: RangeVsRangeParams["heroRange"]) => {
+  if (Array.isArray(range)) {
+    return range
+      .map((hand) => hand.join("").trim())
+      .filter(Boolean)
+      .join(",");
+  }
+  return range;
+};
+
+/** hero vs list（役内訳つき）。`decodeVsListWithRanksBinary` で読める。 */
+export async function runSimulateVsListWithRanksBinary({
+  hero,
+  board,
+  compare,
+  trials,
+  seed = 123456789n,
+  wasmUrl = DEFAULT_WASM_URL,
+}: SimulateParams): Promise<Uint32Array> {
+  const heroTrimmed = hero.join(" ").trim();
+  const boardTrimmed = board.join(" ").trim();
+  const compareTrimmed = compare.join("; ").replaceAll(",", " ").trim();
+  const compareCount = compareTrimmed.split(";").filter((h) => h.trim()).length;
+  if (compareCount === 0) {
+    throw new Error("No compare hands provided");
+  }
+
+  const { exports, memory } = await loadWasm(wasmUrl);
+  const simulate = exports.simulate_vs_list_with_ranks_binary;
+  if (typeof simulate !== "function") {
+    throw new Error(
+      "WASM export 'simulate_vs_list_with_ranks_binary' not found",
+    );
+  }
+
+  const { writeString, allocU32 } = createHeap(memory);
+  const heroBuf = writeString(heroTrimmed);
+  const boardBuf = writeString(boardTrimmed);
+  const compareBuf = writeString(compareTrimmed);
+  // opponents + hero aggregate
+  const outLen =
+    BINARY_HEADER_LEN + (compareCount + 1) * VS_LIST_WITH_RANKS_RECORD_LEN;
+  const outPtr = allocU32(outLen);
+
+  const rc = simulate(
+    heroBuf.ptr,
+    heroBuf.len,
+    boardBuf.ptr,
+    boardBuf.len,
+    compareBuf.ptr,
+    compareBuf.len,
+    trials,
+    seed,
+    outPtr,
+    outLen,
+  );
+  if (rc < 0) {
+    throw new Error(
+      `simulate_vs_list_with_ranks_binary failed with code ${rc}`,
+    );
+  }
+
+  // memory.buffer は次の呼び出しで書き換わるのでコピーして返す
+  return new Uint32Array(memory.buffer, outPtr, rc).slice();
+}
+
+/** range vs range。`decodeRangeVsRangeBinary` で読める。 */
+export async function runSimulateRangeVsRangeEquityBinary({
+  heroRange,
+  villainRange,
+  board,
+  trials,
+  seed = 123456789n,
+  wasmUrl = DEFAULT_WASM_URL,
+}: RangeVsRangeParams): Promise<Uint32Array> {
+  const { exports, memory } = await loadWasm(wasmUrl);
+  const simulate = exports.simulate_range_vs_range_equity_binary;
+  if (typeof simulate !== "function") {
+    throw new Error(
+      "WASM export 'simulate_range_vs_range_equity_binary' not found",
+    );
+  }
+
+  const { writeString, allocU32 } = createHeap(memory);
+  const heroBuf = writeString(normalizeRangeInput(heroRange).trim());
+  const villainBuf = writeString(normalizeRangeInput(villainRange).trim());
+  const boardBuf = writeString(board.join(" ").trim());
+  const outLen =
+    BINARY_HEADER_LEN + MAX_STARTING_HANDS * 2 * RANGE_VS_RANGE_RECORD_LEN;
+  const outPtr = allocU32(outLen);
+
+  const rc = simulate(
+    heroBuf.ptr,
+    heroBuf.len,
+    villainBuf.ptr,
+    villainBuf.len,
+    boardBuf.ptr,
+    boardBuf.len,
+    trials,
+    seed,
+    outPtr,
+    outLen,
+  );
+  if (rc < 0) {
+    throw new Error(
+      `simulate_range_vs_range_equity_binary failed with code ${rc}`,
+    );
+  }
+
+  return new Uint32Array(memory.buffer, outPtr, rc).slice();
+}
@@ -79,6 +79,24 @@ export async function runSimulateRankDistribution(
   }
 
   const out = new Uint32Array(memory.buffer, outPtr, rc * 9);
+  const data = decodeRankDistribution(out, rc, handsTrimmed);
+
+  const end = performance.now();
+  console.log(
+    `runSimulateRankDistribution took ${(end - start).toFixed(2)} ms`,
+  );
+
+  return data;
+}
+
+/**
+ * `simulate_rank_distribution` 系の出力（1 ハンド 9 u32）を RankDistributionEntry[] に変換する。
+ */
+export function decodeRankDistribution(
+  out: Uint32Array,
+  count: number,
+  handsTrimmed: string[],
+): RankDistributionEntry[] {
   const labels: (keyof RankResults)[] = [
     "High Card",
     "One Pair",
@@ -92,7 +110,7 @@ export async function runSimulateRankDistribution(
   ];
 
   const data: RankDistributionEntry[] = [];
-  for (let i = 0; i < rc; i += 1) {
+  for (let i = 0; i < count; i += 1) {
     const base = i * 9;
     const chunk = out.subarray(base, base + 9);
     const resultsObject = {} as RankResults;
@@ -105,10 +123,5 @@ export async function runSimulateRankDistribution(
     });
   }
 
-  const end = performance.now();
-  console.log(
-    `runSimulateRankDistribution took ${(end - start).toFixed(2)} ms`,
-  );
-
   return data;
 }
@@ -0,0 +1,70 @@
+import { resolveWorkerWasmUrl } from "@/lib/worker/wasm-worker-client";
+import {
+  createShardSeed,
+  resolvePoolSize,
+  runPoolRequest,
+  runShardedRequests,
+} from "@/lib/worker/wasm-worker-pool";
+import { SHARDED_WASM_URL } from "../constants";
+import type {
+  CombinedPayload,
+  RankDistributionEntry,
+  RankDistributionParams,
+  ShardedSimulationOptions,
+  SimulateParams,
+} from "../types";
+
+// 1 シミュレーションを複数 worker に分割する API（map/reduce）。
+// 各 worker が作業単位のシャードを実行し、worker 0 が Rust 側の merge で集計する。
+// 結果は worker 数に依存しない（同じ seed なら同じ値）。
+// `bun run build:wasm-shards` で生成した SHARDED_WASM_URL のビルドが必要。
+
+export async function simulateVsListWithRanksSharded(
+  params: SimulateParams & ShardedSimulationOptions,
+): Promise<CombinedPayload> {
+  const { workers, onProgress, wasmUrl, ...rest } = params;
+  const shardCount = resolvePoolSize(workers);
+  const shared = {
+    ...rest,
+    seed: rest.seed ?? createShardSeed(),
+    wasmUrl: resolveWorkerWasmUrl(wasmUrl, SHARDED_WASM_URL),
+  };
+
+  const parts = await runShardedRequests<Uint32Array>(
+    shardCount,
+    (shardIndex) => ({
+      type: "simulateVsListWithRanksShard",
+      params: { ...shared, shardIndex, shardCount },
+    }),
+    onProgress,
+  );
+  return runPoolRequest<CombinedPayload>(0, {
+    type: "mergeVsListWithRanksShards",
+    params: { ...shared, parts },
+  });
+}
+
+export async function simulateRankDistributionSharded(
+  params: RankDistributionParams & ShardedSimulationOptions,
+): Promise<RankDistributionEntry[]> {
+  const { workers, onProgress, wasmUrl, ...rest } = params;
+  const shardCount = resolvePoolSize(workers);
+  const shared = {
+    ...rest,
+    seed: rest.seed ?? createShardSeed(),
+    wasmUrl: resolveWorkerWasmUrl(wasmUrl, SHARDED_WASM_URL),
+  };
+
+  const parts = await runShardedRequests<Uint32Array>(
+    shardCount,
+    (shardIndex) => ({
+      type: "simulateRankDistributionShard",
+      params: { ...shared, shardIndex, shardCount },
+    }),
+    onProgress,
+  );
+  return runPoolRequest<RankDistributionEntry[]>(0, {
+    type: "mergeRankDistributionShards",
+    params: { ...shared, parts },
+  });
+}
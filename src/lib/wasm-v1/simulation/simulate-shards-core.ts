@@ -0,0 +1,219 @@
+import { SHARDED_WASM_URL } from "../constants";
+import { createHeap, loadWasm } from "../loader";
+import type {
+  CombinedPayload,
+  RankDistributionEntry,
+  RankDistributionParams,
+  ShardParams,
+  SimulateParams,
+} from "../types";
+import { decodeRankDistribution } from "./simulate-rank-distribution-core";
+import { decodeVsListWithRanksRecords } from "./simulate-vs-list-with-ranks-core";
+
+// 1 シミュレーションを複数 worker に分割する map/reduce 用の WASM 直呼び。
+// 各 worker が `*_shard` で部分集計を返し、1 つの worker が `merge_*` でまとめる。
+
+const VS_LIST_RECORD_LEN = 32;
+const RANK_RECORD_LEN = 9;
+
+const normalizeVsListInput = ({
+  hero,
+  board,
+  compare,
+}: Pick<SimulateParams, "hero" | "board" | "compare">) => {
+  const heroTrimmed = hero.join(" ").trim();
+  const boardTrimmed = board.join(" ").trim();
+  const compareTrimmed = compare.join("; ").replaceAll(",", " ").trim();
+  const handList = compareTrimmed
+    .split(";")
+    .map((h) => h.trim())
+    .filter(Boolean);
+  return { heroTrimmed, boardTrimmed, compareTrimmed, handList };
+};
+
+const normalizeHands = (hands: string[][]) =>
+  hands.map((hand) => hand.join(" ").trim()).filter(Boolean);
+
+// シャードの生レコードを連結して WASM メモリに書き込み、同じ長さの出力領域を確保する。
+// 各シャードのレコード数は同じなので、出力は 1 シャード分あれば足りる。
+const writeParts = (memory: WebAssembly.Memory, parts: Uint32Array[]) => {
+  const { allocU32 } = createHeap(memory);
+  const inLen = parts.reduce((sum, part) => sum + part.length, 0);
+  const inPtr = allocU32(inLen);
+  const outLen = parts[0]?.length ?? 0;
+  const outPtr = allocU32(outLen);
+
+  const view = new Uint32Array(memory.buffer, inPtr, inLen);
+  let offset = 0;
+  for (const part of parts) {
+    view.set(part, offset);
+    offset += part.length;
+  }
+  return { inPtr, inLen, outPtr, outLen };
+};
+
+/** hero vs list（役内訳つき）の 1 シャード分。部分集計の生レコードを返す。 */
+export async function runSimulateVsListWithRanksShard({
+  hero,
+  board,
+  compare,
+  trials,
+  seed = 123456789n,
+  shardIndex,
+  shardCount,
+  wasmUrl = SHARDED_WASM_URL,
+}: SimulateParams & ShardParams): Promise<Uint32Array> {
+  const { heroTrimmed, boardTrimmed, compareTrimmed, handList } =
+    normalizeVsListInput({ hero, board, compare });
+  if (handList.length === 0) {
+    throw new Error("No compare hands provided");
+  }
+
+  const { exports, memory } = await loadWasm(wasmUrl);
+  const simulate = exports.simulate_vs_list_with_ranks_shard;
+  if (typeof simulate !== "function") {
+    throw new Error(
+      "WASM export 'simulate_vs_list_with_ranks_shard' not found",
+    );
+  }
+
+  const { writeString, allocU32 } = createHeap(memory);
+  const heroBuf = writeString(heroTrimmed);
+  const boardBuf = writeString(boardTrimmed);
+  const compareBuf = writeString(compareTrimmed);
+  // opponents + hero aggregate
+  const outLen = (handList.length + 1) * VS_LIST_RECORD_LEN;
+  const outPtr = allocU32(outLen);
+
+  const rc = simulate(
+    heroBuf.ptr,
+    heroBuf.len,
+    boardBuf.ptr,
+    boardBuf.len,
+    compareBuf.ptr,
+    compareBuf.len,
+    trials,
+    seed,
+    shardIndex,
+    shardCount,
+    outPtr,
+    outLen,
+  );
+  if (rc <= 0) {
+    throw new Error(`simulate_vs_list_with_ranks_shard failed with code ${rc}`);
+  }
+
+  // memory.buffer は次の呼び出しで書き換わるのでコピーして返す
+  return new Uint32Array(
+    memory.buffer,
+    outPtr,
+    rc * VS_LIST_RECORD_LEN,
+  ).slice();
+}
+
+/** 全シャードの生レコードを Rust 側でマージし、通常版と同じ CombinedPayload にする。 */
+export async function runMergeVsListWithRanksShards({
+  hero,
+  board,
+  compare,
+  parts,
+  wasmUrl = SHARDED_WASM_URL,
+}: Pick<SimulateParams, "hero" | "board" | "compare" | "wasmUrl"> & {
+  parts: Uint32Array[];
+}): Promise<CombinedPayload> {
+  const { heroTrimmed, handList } = normalizeVsListInput({
+    hero,
+    board,
+    compare,
+  });
+
+  const { exports, memory } = await loadWasm(wasmUrl);
+  const merge = exports.merge_vs_list_with_ranks_shards;
+  if (typeof merge !== "function") {
+    throw new Error("WASM export 'merge_vs_list_with_ranks_shards' not found");
+  }
+
+  const { inPtr, inLen, outPtr, outLen } = writeParts(memory, parts);
+  const rc = merge(inPtr, inLen, parts.length, outPtr, outLen);
+  if (rc <= 0) {
+    throw new Error(`merge_vs_list_with_ranks_shards failed with code ${rc}`);
+  }
+
+  const out = new Uint32Array(memory.buffer, outPtr, rc * VS_LIST_RECORD_LEN);
+  return decodeVsListWithRanksRecords(out, rc, heroTrimmed, handList);
+}
+
+/** 役分布の 1 シャード分。部分集計の生レコードを返す。 */
+export async function runSimulateRankDistributionShard({
+  hands,
+  board,
+  trials,
+  seed = 123456789n,
+  shardIndex,
+  shardCount,
+  wasmUrl = SHARDED_WASM_URL,
+}: RankDistributionParams & ShardParams): Promise<Uint32Array> {
+  const handsTrimmed = normalizeHands(hands);
+  if (handsTrimmed.length === 0 || board.length < 3 || board.length > 5) {
+    return new Uint32Array(0);
+  }
+
+  const { exports, memory } = await loadWasm(wasmUrl);
+  const simulate = exports.simulate_rank_distribution_shard;
+  if (typeof simulate !== "function") {
+    throw new Error("WASM export 'simulate_rank_distribution_shard' not found");
+  }
+
+  const { writeString, allocU32 } = createHeap(memory);
+  const handsBuf = writeString(handsTrimmed.join("; "));
+  const boardBuf = writeString(board.join(" "));
+  const outLen = handsTrimmed.length * RANK_RECORD_LEN;
+  const outPtr = allocU32(outLen);
+
+  const rc = simulate(
+    handsBuf.ptr,
+    handsBuf.len,
+    boardBuf.ptr,
+    boardBuf.len,
+    trials,
+    seed,
+    shardIndex,
+    shardCount,
+    outPtr,
+    outLen,
+  );
+  if (rc < 0) {
+    throw new Error(`simulate_rank_distribution_shard failed with code ${rc}`);
+  }
+
+  return new Uint32Array(memory.buffer, outPtr, rc * RANK_RECORD_LEN).slice();
+}
+
+/** 役分布の全シャードを Rust 側でマージする。 */
+export async function runMergeRankDistributionShards({
+  hands,
+  parts,
+  wasmUrl = SHARDED_WASM_URL,
+}: Pick<RankDistributionParams, "hands" | "wasmUrl"> & {
+  parts: Uint32Array[];
+}): Promise<RankDistributionEntry[]> {
+  const handsTrimmed = normalizeHands(hands);
+  if (parts.every((part) => part.length === 0)) {
+    return [];
+  }
+
+  const { exports, memory } = await loadWasm(wasmUrl);
+  const merge = exports.merge_rank_distribution_shards;
+  if (typeof merge !== "function") {
+    throw new Error("WASM export 'merge_rank_distribution_shards' not found");
+  }
+
+  const { inPtr, inLen, outPtr, outLen } = writeParts(memory, parts);
+  const rc = merge(inPtr, inLen, parts.length, outPtr, outLen);
+  if (rc < 0) {
+    throw new Error(`merge_rank_distribution_shards failed with code ${rc}`);
+  }
+
+  const out = new Uint32Array(memory.buffer, outPtr, rc * RANK_RECORD_LEN);
+  return decodeRankDistribution(out, rc, handsTrimmed);
+}
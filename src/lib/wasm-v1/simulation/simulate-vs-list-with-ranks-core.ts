@@ -103,6 +103,31 @@ export async function runSimulateVsListWithRanks(
   }
 
   const out = new Uint32Array(memory.buffer, outPtr, records * 32);
+  const payload = decodeVsListWithRanksRecords(
+    out,
+    records,
+    heroTrimmed,
+    handList,
+  );
+
+  const end = performance.now(); // For performance measurement
+  console.info(
+    `runSimulateVsListWithRanks took ${(end - start).toFixed(2)} ms`,
+  ); // Log the time taken
+
+  return payload;
+}
+
+/**
+ * `simulate_vs_list_with_ranks` 系の出力（1 レコード 32 u32、hero 集計が末尾）を
+ * CombinedPayload に変換する。シャード版のマージ結果も同じレイアウト。
+ */
+export function decodeVsListWithRanksRecords(
+  out: Uint32Array,
+  records: number,
+  heroTrimmed: string,
+  handList: string[],
+): CombinedPayload {
   const labels: (keyof RankOutcomeResults)[] = [
     "High Card",
     "One Pair",
@@ -193,11 +218,6 @@ export async function runSimulateVsListWithRanks(
       ? 0
       : (heroEntry.win + heroEntry.tie * 0.5) / heroEntry.count;
 
-  const end = performance.now(); // For performance measurement
-  console.info(
-    `runSimulateVsListWithRanks took ${(end - start).toFixed(2)} ms`,
-  ); // Log the time taken
-
   return {
     hand: heroTrimmed,
     equity,
@@ -1,7 +1,15 @@
 import { CARD_RANK_ORDER } from "@/utils/card";
 import { DEFAULT_WASM_URL } from "../constants";
 import { createHeap, loadWasm } from "../loader";
-import type { MonteCarloTraceEntry, SimulateParams } from "../types";
+import type {
+  MonteCarloRunout,
+  MonteCarloTraceEntry,
+  SimulateParams,
+} from "../types";
+
+const TRACE_RECORD_LEN = 11;
+// ストリーミング版・replay 版は末尾に best five と両者の役を追加した 18 語
+const RUNOUT_RECORD_LEN = 18;
 
 const RANK_LABELS = [
   "High Card",
@@ -38,6 +46,41 @@ const outcomeLabel = (outcome: number) => {
   return "tie";
 };
 
+const decodeTraceRecord = (chunk: Uint32Array): MonteCarloTraceEntry => {
+  const rankIndex = chunk[10];
+  return {
+    hero: formatHand(decodeCard(chunk[0]), decodeCard(chunk[1])),
+    board: Array.from(chunk.subarray(2, 7), decodeCard).join(" "),
+    villain: formatHand(decodeCard(chunk[7]), decodeCard(chunk[8])),
+    outcome: outcomeLabel(chunk[9]),
+    rankIndex,
+    rankName: RANK_LABELS[rankIndex] ?? "Unknown",
+  };
+};
+
+const decodeRunoutRecord = (
+  chunk: Uint32Array,
+  seed: bigint,
+  trialIndex: number,
+): MonteCarloRunout => ({
+  ...decodeTraceRecord(chunk),
+  seed,
+  trialIndex,
+  bestFive: Array.from(chunk.subarray(11, 16), decodeCard),
+  heroRankIndex: chunk[16],
+  villainRankIndex: chunk[17],
+});
+
+const normalizeTraceInput = ({
+  hero,
+  board,
+  compare,
+}: Pick<SimulateParams, "hero" | "board" | "compare">) => ({
+  heroTrimmed: hero.join(" ").trim(),
+  boardTrimmed: board.join(" ").trim(),
+  compareTrimmed: compare.join("; ").replaceAll(",", " ").trim(),
+});
+
 export async function runSimulateVsListWithRanksTrace({
   hero,
   board,
@@ -83,7 +126,7 @@ export async function runSimulateVsListWithRanksTrace({
   }
 
   const records = compareCount * Math.max(trials, 1);
-  const outLen = records * 11;
+  const outLen = records * TRACE_RECORD_LEN;
   const outPtr = allocU32(outLen);
 
   const rc = simulate(
@@ -105,34 +148,129 @@ export async function runSimulateVsListWithRanksTrace({
     return [];
   }
 
-  const out = new Uint32Array(memory.buffer, outPtr, rc * 11);
+  const out = new Uint32Array(memory.buffer, outPtr, rc * TRACE_RECORD_LEN);
   const data: MonteCarloTraceEntry[] = [];
-
   for (let i = 0; i < rc; i += 1) {
-    const base = i * 11;
-    const chunk = out.subarray(base, base + 11);
-    const heroCards = formatHand(decodeCard(chunk[0]), decodeCard(chunk[1]));
-    const boardCards = [
-      decodeCard(chunk[2]),
-      decodeCard(chunk[3]),
-      decodeCard(chunk[4]),
-      decodeCard(chunk[5]),
-      decodeCard(chunk[6]),
-    ].join(" ");
-    const villainCards = formatHand(decodeCard(chunk[7]), decodeCard(chunk[8]));
-    const outcome = outcomeLabel(chunk[9]);
-    const rankIndex = chunk[10];
-    const rankName = RANK_LABELS[rankIndex] ?? "Unknown";
-
-    data.push({
-      hero: heroCards,
-      board: boardCards,
-      villain: villainCards,
-      outcome,
-      rankIndex,
-      rankName,
-    });
+    const base = i * TRACE_RECORD_LEN;
+    data.push(decodeTraceRecord(out.subarray(base, base + TRACE_RECORD_LEN)));
   }
 
   return data;
 }
+
+/**
+ * trace の `start` 件目から最大 `count` 件だけを返す。全件を一度に確保しないので、
+ * 大きな trials でもページ単位で読み進められる。末尾を過ぎると空配列。
+ */
+export async function runSimulateVsListWithRanksTracePage({
+  hero,
+  board,
+  compare,
+  trials,
+  seed = 123456789n,
+  start,
+  count,
+  wasmUrl = DEFAULT_WASM_URL,
+}: SimulateParams & {
+  start: number;
+  count: number;
+}): Promise<MonteCarloRunout[]> {
+  const { heroTrimmed, boardTrimmed, compareTrimmed } = normalizeTraceInput({
+    hero,
+    board,
+    compare,
+  });
+
+  const { exports, memory } = await loadWasm(wasmUrl);
+  const simulate = exports.simulate_vs_list_with_ranks_trace_page;
+  if (typeof simulate !== "function") {
+    throw new Error(
+      "WASM export 'simulate_vs_list_with_ranks_trace_page' not found",
+    );
+  }
+
+  const { writeString, allocU32 } = createHeap(memory);
+  const heroBuf = writeString(heroTrimmed);
+  const boardBuf = writeString(boardTrimmed);
+  const compareBuf = writeString(compareTrimmed);
+  const outLen = Math.max(count, 1) * RUNOUT_RECORD_LEN;
+  const outPtr = allocU32(outLen);
+
+  const rc = simulate(
+    heroBuf.ptr,
+    heroBuf.len,
+    boardBuf.ptr,
+    boardBuf.len,
+    compareBuf.ptr,
+    compareBuf.len,
+    trials,
+    seed,
+    BigInt(start),
+    outPtr,
+    outLen,
+  );
+  if (rc < 0) {
+    throw new Error(
+      `simulate_vs_list_with_ranks_trace_page failed with code ${rc}`,
+    );
+  }
+
+  const out = new Uint32Array(memory.buffer, outPtr, rc * RUNOUT_RECORD_LEN);
+  return Array.from({ length: rc }, (_, i) => {
+    const base = i * RUNOUT_RECORD_LEN;
+    return decodeRunoutRecord(
+      out.subarray(base, base + RUNOUT_RECORD_LEN),
+      seed,
+      start + i,
+    );
+  });
+}
+
+/** (seed, trialIndex) から trace の 1 件だけを再現する。前の試行は計算しない。 */
+export async function runReplayVsListWithRanksTrial({
+  hero,
+  board,
+  compare,
+  trials,
+  seed = 123456789n,
+  trialIndex,
+  wasmUrl = DEFAULT_WASM_URL,
+}: SimulateParams & { trialIndex: number }): Promise<MonteCarloRunout> {
+  const { heroTrimmed, boardTrimmed, compareTrimmed } = normalizeTraceInput({
+    hero,
+    board,
+    compare,
+  });
+
+  const { exports, memory } = await loadWasm(wasmUrl);
+  const replay = exports.replay_vs_list_with_ranks_trial;
+  if (typeof replay !== "function") {
+    throw new Error("WASM export 'replay_vs_list_with_ranks_trial' not found");
+  }
+
+  const { writeString, allocU32 } = createHeap(memory);
+  const heroBuf = writeString(heroTrimmed);
+  const boardBuf = writeString(boardTrimmed);
+  const compareBuf = writeString(compareTrimmed);
+  const outPtr = allocU32(RUNOUT_RECORD_LEN);
+
+  const rc = replay(
+    heroBuf.ptr,
+    heroBuf.len,
+    boardBuf.ptr,
+    boardBuf.len,
+    compareBuf.ptr,
+    compareBuf.len,
+    trials,
+    seed,
+    BigInt(trialIndex),
+    outPtr,
+    RUNOUT_RECORD_LEN,
+  );
+  if (rc !== 1) {
+    throw new Error(`replay_vs_list_with_ranks_trial failed with code ${rc}`);
+  }
+
+  const out = new Uint32Array(memory.buffer, outPtr, RUNOUT_RECORD_LEN);
+  return decodeRunoutRecord(out, seed, trialIndex);
+}
@@ -5,10 +5,16 @@ import {
 import { DEFAULT_WASM_URL } from "../constants";
 import type {
   CombinedPayload,
+  MonteCarloRunout,
   MonteCarloTraceEntry,
   SimulateParams,
 } from "../types";
 
+const DEFAULT_TRACE_PAGE_SIZE = 1024;
+
+const createTraceSeed = () =>
+  (BigInt(Date.now()) << 16n) ^ BigInt(Math.floor(Math.random() * 0xffff));
+
 type SimulateParamsWithOptionalProgress = SimulateParams & {
   onProgress?: (pct: number) => void;
 };
@@ -51,3 +57,46 @@ export async function simulateVsListWithRanksTrace(
   };
   return runWorkerRequest<MonteCarloTraceEntry[]>(request);
 }
+
+/**
+ * trace を `pageSize` 件ずつ worker から受け取り、1 件ずつ返す。
+ * `simulateVsListWithRanksTrace` と違い全件をメモリに載せない。途中で break してよい。
+ * seed 未指定時はここで 1 つ決め、各件の `seed` / `trialIndex` で replay できる。
+ */
+export async function* streamVsListWithRanksTrace(
+  params: SimulateParams & { pageSize?: number },
+): AsyncGenerator<MonteCarloRunout> {
+  const { pageSize = DEFAULT_TRACE_PAGE_SIZE, wasmUrl, ...rest } = params;
+  const shared = {
+    ...rest,
+    seed: rest.seed ?? createTraceSeed(),
+    wasmUrl: resolveWorkerWasmUrl(wasmUrl, DEFAULT_WASM_URL),
+  };
+
+  let start = 0;
+  let page: MonteCarloRunout[];
+  do {
+    page = await runWorkerRequest<MonteCarloRunout[]>({
+      type: "simulateVsListWithRanksTracePage",
+      params: { ...shared, start, count: pageSize },
+    });
+    yield* page;
+    start += page.length;
+  } while (page.length > 0);
+}
+
+/**
+ * trace の 1 件を (seed, trialIndex) から再現する。
+ * `streamVsListWithRanksTrace` と同じ入力・trials・seed を渡すこと。
+ */
+export async function replayVsListWithRanksTrial(
+  params: SimulateParams & { seed: bigint; trialIndex: number },
+): Promise<MonteCarloRunout> {
+  return runWorkerRequest<MonteCarloRunout>({
+    type: "replayVsListWithRanksTrial",
+    params: {
+      ...params,
+      wasmUrl: resolveWorkerWasmUrl(params.wasmUrl, DEFAULT_WASM_URL),
+    },
+  });
+}
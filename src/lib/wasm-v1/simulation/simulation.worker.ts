@@ -3,6 +3,7 @@
 import type {
   CombinedPayload,
   EquityPayload,
+  MonteCarloRunout,
   MonteCarloTraceEntry,
   MultiHandEquityParams,
   MultiHandEquityPayload,
@@ -12,24 +13,42 @@ import type {
   RangeVsRangePayload,
   RankDistributionEntry,
   RankDistributionParams,
+  ShardParams,
   SimulateParams,
+  SixMaxPreflopParams,
+  SixMaxPreflopPayload,
 } from "../types";
+import { runEvaluateSixMaxPreflop } from "./evaluate-six-max-preflop-core";
 import { runSimulateMultiHandEquity } from "./simulate-multi-hand-equity-core";
 import { runSimulateOpenRangesMonteCarlo } from "./simulate-open-ranges-monte-carlo-core";
 import { runSimulateRangeVsRangeEquity } from "./simulate-range-vs-range-equity-core";
 import { runSimulateRankDistribution } from "./simulate-rank-distribution-core";
+import {
+  runMergeRankDistributionShards,
+  runMergeVsListWithRanksShards,
+  runSimulateRankDistributionShard,
+  runSimulateVsListWithRanksShard,
+} from "./simulate-shards-core";
 import { runSimulateVsListEquity } from "./simulate-vs-list-equity-core";
 import { runSimulateVsListWithRanks } from "./simulate-vs-list-with-ranks-core";
-import { runSimulateVsListWithRanksTrace } from "./simulate-vs-list-with-ranks-trace-core";
+import {
+  runReplayVsListWithRanksTrial,
+  runSimulateVsListWithRanksTrace,
+  runSimulateVsListWithRanksTracePage,
+} from "./simulate-vs-list-with-ranks-trace-core";
 
 type SimulateResult =
   | CombinedPayload
   | EquityPayload
   | OpenRangesPayload
+  | SixMaxPreflopPayload
   | RangeVsRangePayload
   | MultiHandEquityPayload
   | RankDistributionEntry[]
-  | MonteCarloTraceEntry[];
+  | MonteCarloTraceEntry[]
+  | MonteCarloRunout[]
+  | MonteCarloRunout
+  | Uint32Array;
 
 type SimulateWorkerParams = SimulateParams & { useProgressExport?: boolean };
 
@@ -41,6 +60,16 @@ type WorkerRequest =
       params: SimulateParams;
     }
   | { id: number; type: "simulateVsListWithRanksTrace"; params: SimulateParams }
+  | {
+      id: number;
+      type: "simulateVsListWithRanksTracePage";
+      params: SimulateParams & { start: number; count: number };
+    }
+  | {
+      id: number;
+      type: "replayVsListWithRanksTrial";
+      params: SimulateParams & { trialIndex: number };
+    }
   | { id: number; type: "simulateVsListEquity"; params: SimulateWorkerParams }
   | {
       id: number;
@@ -86,6 +115,31 @@ type WorkerRequest =
       id: number;
       type: "simulateOpenRangesMonteCarlo";
       params: OpenRangesParams;
+    }
+  | {
+      id: number;
+      type: "evaluateSixMaxPreflop";
+      params: SixMaxPreflopParams;
+    }
+  | {
+      id: number;
+      type: "simulateVsListWithRanksShard";
+      params: SimulateParams & ShardParams;
+    }
+  | {
+      id: number;
+      type: "mergeVsListWithRanksShards";
+      params: SimulateParams & { parts: Uint32Array[] };
+    }
+  | {
+      id: number;
+      type: "simulateRankDistributionShard";
+      params: RankDistributionParams & ShardParams;
+    }
+  | {
+      id: number;
+      type: "mergeRankDistributionShards";
+      params: RankDistributionParams & { parts: Uint32Array[] };
     };
 
 type WorkerResponse =
@@ -149,6 +203,19 @@ ctx.onmessage = async (event) => {
       ctx.postMessage(response);
       return;
     }
+    // ページ送り・replay は呼び出し側が seed を固定して渡す（同じ trace を読み続けるため）
+    if (message.type === "simulateVsListWithRanksTracePage") {
+      const data = await runSimulateVsListWithRanksTracePage(message.params);
+      const response: WorkerResponse = { id: message.id, type: "result", data };
+      ctx.postMessage(response);
+      return;
+    }
+    if (message.type === "replayVsListWithRanksTrial") {
+      const data = await runReplayVsListWithRanksTrial(message.params);
+      const response: WorkerResponse = { id: message.id, type: "result", data };
+      ctx.postMessage(response);
+      return;
+    }
     if (message.type === "simulateVsListEquity") {
       const params =
         message.params.seed === undefined
@@ -296,6 +363,53 @@ ctx.onmessage = async (event) => {
       ctx.postMessage(response);
       return;
     }
+    if (message.type === "evaluateSixMaxPreflop") {
+      const params =
+        message.params.seed === undefined
+          ? { ...message.params, seed: createRandomSeed() }
+          : message.params;
+      const progressStart: WorkerResponse = {
+        id: message.id,
+        type: "progress",
+        pct: 0,
+      };
+      ctx.postMessage(progressStart);
+      const data = await runEvaluateSixMaxPreflop(params);
+      const progressEnd: WorkerResponse = {
+        id: message.id,
+        type: "progress",
+        pct: 100,
+      };
+      ctx.postMessage(progressEnd);
+      const response: WorkerResponse = { id: message.id, type: "result", data };
+      ctx.postMessage(response);
+      return;
+    }
+    // シャード分割: 呼び出し側が全シャードで同じ seed を渡すので、ここでは乱数シードを補わない
+    if (message.type === "simulateVsListWithRanksShard") {
+      const data = await runSimulateVsListWithRanksShard(message.params);
+      const response: WorkerResponse = { id: message.id, type: "result", data };
+      ctx.postMessage(response);
+      return;
+    }
+    if (message.type === "mergeVsListWithRanksShards") {
+      const data = await runMergeVsListWithRanksShards(message.params);
+      const response: WorkerResponse = { id: message.id, type: "result", data };
+      ctx.postMessage(response);
+      return;
+    }
+    if (message.type === "simulateRankDistributionShard") {
+      const data = await runSimulateRankDistributionShard(message.params);
+      const response: WorkerResponse = { id: message.id, type: "result", data };
+      ctx.postMessage(response);
+      return;
+    }
+    if (message.type === "mergeRankDistributionShards") {
+      const data = await runMergeRankDistributionShards(message.params);
+      const response: WorkerResponse = { id: message.id, type: "result", data };
+      ctx.postMessage(response);
+      return;
+    }
   } catch (error) {
     const response: WorkerResponse = {
       id: message.id,
@@ -88,6 +88,18 @@ export type WasmExports = {
     outPtr: number,
     outLen: number,
   ) => number;
+  simulate_range_vs_range_equity_binary?: (
+    heroPtr: number,
+    heroLen: number,
+    villainPtr: number,
+    villainLen: number,
+    boardPtr: number,
+    boardLen: number,
+    trials: number,
+    seed: bigint,
+    outPtr: number,
+    outLen: number,
+  ) => number;
   simulate_range_vs_range_equity_with_progress?: (
     heroPtr: number,
     heroLen: number,
@@ -112,6 +124,18 @@ export type WasmExports = {
     outPtr: number,
     outLen: number,
   ) => number;
+  simulate_vs_list_with_ranks_binary?: (
+    heroPtr: number,
+    heroLen: number,
+    boardPtr: number,
+    boardLen: number,
+    comparePtr: number,
+    compareLen: number,
+    trials: number,
+    seed: bigint,
+    outPtr: number,
+    outLen: number,
+  ) => number;
   simulate_vs_list_with_ranks_monte_carlo?: (
     heroPtr: number,
     heroLen: number,
@@ -148,6 +172,32 @@ export type WasmExports = {
     outPtr: number,
     outLen: number,
   ) => number;
+  simulate_vs_list_with_ranks_trace_page?: (
+    heroPtr: number,
+    heroLen: number,
+    boardPtr: number,
+    boardLen: number,
+    comparePtr: number,
+    compareLen: number,
+    trials: number,
+    seed: bigint,
+    start: bigint,
+    outPtr: number,
+    outLen: number,
+  ) => number;
+  replay_vs_list_with_ranks_trial?: (
+    heroPtr: number,
+    heroLen: number,
+    boardPtr: number,
+    boardLen: number,
+    comparePtr: number,
+    compareLen: number,
+    trials: number,
+    seed: bigint,
+    trialIndex: bigint,
+    outPtr: number,
+    outLen: number,
+  ) => number;
   simulate_open_ranges_monte_carlo?: (
     heroPtr: number,
     heroLen: number,
@@ -158,6 +208,67 @@ export type WasmExports = {
     outPtr: number,
     outLen: number,
   ) => number;
+  evaluate_six_max_preflop?: (
+    heroPtr: number,
+    heroLen: number,
+    openPtr: number,
+    openLen: number,
+    callPtr: number,
+    callLen: number,
+    threeBetPtr: number,
+    threeBetLen: number,
+    heroPosition: number,
+    action: number,
+    openerPosition: number,
+    smallBlind: number,
+    bigBlind: number,
+    openSize: number,
+    threeBetSize: number,
+    trials: number,
+    seed: bigint,
+    outPtr: number,
+    outLen: number,
+  ) => number;
+  simulate_vs_list_with_ranks_shard?: (
+    heroPtr: number,
+    heroLen: number,
+    boardPtr: number,
+    boardLen: number,
+    comparePtr: number,
+    compareLen: number,
+    trials: number,
+    seed: bigint,
+    shardIndex: number,
+    shardCount: number,
+    outPtr: number,
+    outLen: number,
+  ) => number;
+  merge_vs_list_with_ranks_shards?: (
+    inPtr: number,
+    inLen: number,
+    shardCount: number,
+    outPtr: number,
+    outLen: number,
+  ) => number;
+  simulate_rank_distribution_shard?: (
+    handsPtr: number,
+    handsLen: number,
+    boardPtr: number,
+    boardLen: number,
+    trials: number,
+    seed: bigint,
+    shardIndex: number,
+    shardCount: number,
+    outPtr: number,
+    outLen: number,
+  ) => number;
+  merge_rank_distribution_shards?: (
+    inPtr: number,
+    inLen: number,
+    shardCount: number,
+    outPtr: number,
+    outLen: number,
+  ) => number;
   parse_range_to_hands?: (
     rangePtr: number,
     rangeLen: number,
@@ -241,6 +352,25 @@ export type RangeVsRangePayload = {
   villain: RangeEquityEntry[];
 };
 
+/** range vs range のハンドクラス 1 つ分。レンジにないセルは combos が 0 で equity 系は null。 */
+export type RangeGridCell = {
+  /** "AKs" / "AKo" / "QQ" */
+  class: string;
+  /** ボードとかぶるコンボを除いた、レンジ内のコンボ数。 */
+  combos: number;
+  /** クラス内の wins/ties/plays を合算した equity（対戦数で重み付け）。 */
+  equity: number | null;
+  /** コンボ単位の equity の最小・最大。 */
+  min: number | null;
+  max: number | null;
+};
+
+/** どちらも 169 要素で、添字はグリッドの並び（row * 13 + col）。 */
+export type RangeVsRangeGridPayload = {
+  hero: RangeGridCell[];
+  villain: RangeGridCell[];
+};
+
 export type MonteCarloTraceEntry = {
   hero: string;
   board: string;
@@ -250,6 +380,16 @@ export type MonteCarloTraceEntry = {
   rankName: string;
 };
 
+/** ストリーミング trace / replay の 1 件。`seed` と `trialIndex` で同じランアウトを再現できる。 */
+export type MonteCarloRunout = MonteCarloTraceEntry & {
+  seed: bigint;
+  trialIndex: number;
+  /** 勝った側（引き分けは hero）の役を作る 5 枚 */
+  bestFive: string[];
+  heroRankIndex: number;
+  villainRankIndex: number;
+};
+
 export type SimulateParams = {
   hero: string[];
   board: string[];
@@ -271,6 +411,17 @@ export type RankDistributionParams = {
   wasmUrl?: string;
 };
 
+export type ShardParams = {
+  shardIndex: number;
+  shardCount: number;
+};
+
+export type ShardedSimulationOptions = {
+  /** 分割に使う worker 数。省略時は `navigator.hardwareConcurrency`（最大 8）。 */
+  workers?: number;
+  onProgress?: (pct: number) => void;
+};
+
 export type MultiHandEquityParams = {
   hands: string[][];
   board: string[];
@@ -305,7 +456,497 @@ export type HandRankingEntry = {
   rankIndex: number;
   rankName: string;
   encoded: number;
+  /** 役を作る 5 枚（"Ks" 形式）。役の札が先、キッカーが後。 */
+  bestFive: string[];
+  /** 勝敗に効くキッカーのランク（0 = 2 .. 12 = A）。強い順。 */
   kickers: number[];
+  /** "Two Pair, Kings and Fives, Queen kicker" のような説明文。 */
+  description: string;
+};
+
+export type CompareShowdownParams = {
+  first: string[];
+  second: string[];
+  /** 5 枚ちょうど。 */
+  board: string[];
+};
+
+export type ShowdownComparison = {
+  /** 0 = first の勝ち、1 = second の勝ち、2 = スプリット。 */
+  winner: 0 | 1 | 2;
+  first: HandRankingEntry;
+  second: HandRankingEntry;
+  /** 決め手。"higher category" / "second kicker Q vs J" / "board plays - split" など。 */
+  reason: string;
+};
+
+export type NutRankingParams = {
+  /** 省略時は hero の順位を計算しない。 */
+  hero?: string[];
+  /** 3〜5 枚。 */
+  board: string[];
+};
+
+/** 同じ強さになるコンボの組。position 1 がナッツ。 */
+export type NutClass = {
+  position: number;
+  rankIndex: number;
+  rankName: string;
+  description: string;
+  combos: string[];
+};
+
+export type HeroNutRank = {
+  hand: string;
+  position: number;
+  /** hero のカードとかぶらないコンボのうち、勝つ / 引き分け / 負ける数。 */
+  beats: number;
+  ties: number;
+  loses: number;
+  /** beats の割合（0..1）。「87% のコンボに勝つ」の 0.87。 */
+  percentile: number;
+};
+
+export type NutRankingPayload = {
+  classes: NutClass[];
+  totalCombos: number;
+  hero: HeroNutRank | null;
+};
+
+export type HandStrengthParams = {
+  hero: string[];
+  /** フロップ / ターン（リバーも可。そのときポテンシャルは 0）。 */
+  board: string[];
+  /** 省略または空なら一様レンジ（残りカードの全コンボ）。 */
+  villainRange?: string | string[][];
+};
+
+export type HandStrengthPayload = {
+  hand: string;
+  /** 今のボードで相手コンボに勝っている割合（引き分けは半分）。 */
+  hs: number;
+  /** 今は負け / 引き分けの相手にリバーで逆転する確率。 */
+  ppot: number;
+  /** 今は勝ち / 引き分けの相手にリバーで逆転される確率。 */
+  npot: number;
+  /** HS × (1 − NPot) + (1 − HS) × PPot。 */
+  ehs: number;
+  /** ランアウトごとのリバー HS の二乗の平均（E[HS²]）。 */
+  ehsSquared: number;
+  villainCombos: number;
+  runouts: number;
+};
+
+export type EquityPathParams = {
+  hero: string[];
+  /** 0〜5 枚。足りない分は seed から引く。 */
+  board: string[];
+  compare: string[][];
+  /** プリフロップ equity の Monte Carlo 試行数。 */
+  trials: number;
+  seed?: bigint;
+};
+
+export type StreetEquity = {
+  street: "preflop" | "flop" | "turn" | "river";
+  board: string[];
+  equity: number;
+  /** 前のストリートからの変化（プリフロップは 0）。 */
+  delta: number;
+  /** false ならプリフロップの Monte Carlo。 */
+  exact: boolean;
+};
+
+export type CardEquity = {
+  card: string;
+  equity: number;
+  delta: number;
+};
+
+export type EquityPathPayload = {
+  hand: string;
+  /** 使ったボード 5 枚。 */
+  runout: string[];
+  streets: StreetEquity[];
+  /** 各ターンカードの equity。高い順（先頭がベスト、末尾がワースト）。 */
+  turnCards: CardEquity[];
+  /** ランアウトのターンに続く各リバーカードの equity。高い順。 */
+  riverCards: CardEquity[];
+};
+
+export type NextCardEquityParams = {
+  hero: string[];
+  /** レンジ式または展開済みのハンド。1 ハンドでもよい。 */
+  villainRange: string | string[][];
+  /** フロップ（3 枚）ならターン、ターン（4 枚）ならリバーを調べる。 */
+  board: string[];
+};
+
+export type NextCardEquityPayload = {
+  hand: string;
+  board: string[];
+  street: "turn" | "river";
+  /** 今のボードでの equity。 */
+  equity: number;
+  villainCombos: number;
+  /** 52 要素。添字はバイナリ形式と同じカード番号（rank * 4 + suit、s/h/d/c）。来ないカードは null。 */
+  grid: (number | null)[];
+  /** 来うるカードごとの equity（デッキ順）。 */
+  cards: CardEquity[];
+  /** 今の equity から ±5% 以上動くかで分けたカード。 */
+  buckets: { good: string[]; neutral: string[]; bad: string[] };
+};
+
+/** 13×13 レンジ表の 1 セル。並びは utils/v2/combo.ts の Range と同じ。 */
+export type HandClassCell = {
+  /** "AKs" / "AKo" / "QQ" */
+  class: string;
+  /** row * 13 + col */
+  index: number;
+  row: number;
+  col: number;
+  /** 使用済みカードがないときのコンボ数（6 / 4 / 12）。 */
+  combos: number;
+  /** 使用済みカードを除いたコンボ数。 */
+  liveCombos: number;
+};
+
+export type HandClassCombosParams = {
+  handClass: string;
+  deadCards?: string[];
+};
+
+export type CanonicalFlopPayload = {
+  /** スート同型なフロップの代表（カード番号の降順）。 */
+  board: string[];
+  /** 代表と同型なフロップの数（1 / 4 / 6 / 12 / 24）。 */
+  isomorphs: number;
+};
+
+export type FlopReportParams = {
+  /** スート対称なレンジ（"AKs", "QQ+" のようなレンジ式）に限る。 */
+  heroRange: string | string[][];
+  villainRange: string | string[][];
+  /** フロップごと・コンボペアごとの試行回数。 */
+  trials: number;
+  seed?: bigint;
+};
+
+export type FlopEquityEntry = {
+  /** スート同型なフロップの代表。 */
+  board: string[];
+  /** 代表と同型なフロップの数（全フロップで合計 22100）。 */
+  weight: number;
+  /** "two-tone unpaired connected" のようなテクスチャ。 */
+  texture: string;
+  /** ボードとの衝突で対戦がなければ null。 */
+  heroEquity: number | null;
+};
+
+export type FlopGroup = {
+  label: string;
+  flops: number;
+  weight: number;
+  /** weight で重み付けした hero の equity。 */
+  heroEquity: number;
+};
+
+export type FlopReportPayload = {
+  heroEquity: number;
+  weight: number;
+  flops: FlopEquityEntry[];
+  /** テクスチャ別。hero の equity が高い順。 */
+  textures: FlopGroup[];
+  /** ハイカード別（"A-high" 等）。hero の equity が高い順。 */
+  highCards: FlopGroup[];
+};
+
+export type ShuffledDeckParams = {
+  serverSeed: string;
+  clientSeed: string;
+  /** ハンドごとに変える番号。 */
+  nonce: number;
+};
+
+export type ShuffledDeckPayload = {
+  /** 配る順の 52 枚。 */
+  cards: string[];
+  /** 順番の SHA-256（16 進）。ハンドごとの約束として先に公開できる。 */
+  deckHash: string;
+};
+
+export type VerifyDealParams = ShuffledDeckParams & {
+  /** 配る前に公開していた server seed のハッシュ。 */
+  commitment: string;
+  /** 配った順のカード。 */
+  dealt: string[];
+};
+
+export type DealVerificationPayload = {
+  /** seed が約束どおりで、配ったカードが全部デッキの順番どおり。 */
+  valid: boolean;
+  seedMatches: boolean;
+  /** 順番どおりでなくなった最初の位置。全部一致なら null。 */
+  mismatchAt: number | null;
+  /** seeds から作り直したデッキ。 */
+  deck: string[];
+  deckHash: string;
+};
+
+export type AllInEvParams = {
+  /** 席順のハンド（["As", "Ks"]）かレンジ式（"QQ+,AK"）。フォールドした席は null。 */
+  players: (string[] | string | null)[];
+  /** 各プレイヤーがハンド全体でポットに出した額。 */
+  contributions: number[];
+  board: string[];
+  /** ボードが 3 枚以下（または組み合わせが多すぎる）ときの Monte Carlo 回数。 */
+  trials: number;
+  seed?: bigint;
+};
+
+export type AllInEvPlayer = {
+  /** 入力したハンドかレンジ。フォールドした席は空文字。 */
+  hand: string;
+  folded: boolean;
+  contribution: number;
+  /** 受け取るチップの期待値。 */
+  expectedReturn: number;
+  /** expectedReturn − contribution */
+  netEv: number;
+};
+
+export type AllInEvPot = {
+  amount: number;
+  eligible: number[];
+  /** eligible と同じ並びの、このポットの取り分の期待値（割合）。 */
+  equity: number[];
+};
+
+export type AllInEvPayload = {
+  players: AllInEvPlayer[];
+  /** 先頭がメインポット。 */
+  pots: AllInEvPot[];
+  /** 数えたランアウト（配り）の数。 */
+  samples: number;
+  /** 全列挙した厳密値か（false なら Monte Carlo）。 */
+  exact: boolean;
+};
+
+export type SettledPot = {
+  amount: number;
+  /** このポットを争えたプレイヤー。 */
+  eligible: number[];
+  winners: number[];
+  /** winners と同じ並びの取り分。 */
+  shares: number[];
+};
+
+export type SettlePotsParams = {
+  /** 各プレイヤーがハンド全体でポットに出した額（アンティ・ブラインド込み）。 */
+  contributions: number[];
+  folded: boolean[];
+  /** 席順のホールカード。フォールドした席は null でよい。 */
+  hands: (string[] | null)[];
+  board: string[];
+  button: number;
+  /** チップの最小単位。省略時は端数も等分し、指定時は余りをボタンの左から配る。 */
+  chip?: number;
+};
+
+export type SettlementPayload = {
+  /** 先頭がメインポット。 */
+  pots: SettledPot[];
+  /** 各プレイヤーが受け取る額。 */
+  winnings: number[];
+  /** 受け取る額 − 出した額。 */
+  net: number[];
+  /** ショーダウンした各プレイヤーの役の説明。 */
+  showdown: (string | null)[];
+};
+
+export type RangeStreet = "preflop" | "flop" | "turn" | "river";
+
+export type RangeAction = "check" | "bet" | "call" | "raise";
+
+/** フロップ以降のボードに対する強さ。強い順。 */
+export type StrengthBucket =
+  | "strong"
+  | "top_pair"
+  | "middle_pair"
+  | "weak_pair"
+  | "draw"
+  | "air";
+
+export type ObservedAction = {
+  street: RangeStreet;
+  action: RangeAction;
+  /**
+   * 分類ごとにこのアクションを選ぶ頻度（0〜1）。キーは StrengthBucket か
+   * レンジ式（"AA", "QQ+"）。指定しない分類は組み込みのヒューリスティック。
+   */
+  frequencies?: Record<string, number>;
+};
+
+export type UpdateRangeParams = {
+  /** レンジ式のカンマ区切り。":重み"（0〜1）を付けられる（例 "QQ+,AKs,AQs:0.5"）。 */
+  range: string;
+  /** 最後のアクションまでに見えているボード。 */
+  board: string[];
+  actions: ObservedAction[];
+};
+
+export type WeightedHandEntry = {
+  hand: string;
+  handClass: string;
+  /** そのストリートのボードに対する強さ。プリフロップは null。 */
+  strength: StrengthBucket | null;
+  weight: number;
+};
+
+export type WeightedRangeSnapshot = {
+  /** 重みの合計。確率にするときはこれで割る。 */
+  totalWeight: number;
+  /** 重みが 0 のコンボは含まない。 */
+  combos: WeightedHandEntry[];
+  /** ハンドクラスごとの重み（グリッドの並び）。 */
+  classes: { handClass: string; weight: number }[];
+  /** 強さごとの重み（強い順）。プリフロップは空。 */
+  strengths: { strength: StrengthBucket; weight: number }[];
+};
+
+export type RangeUpdateStep = {
+  street: RangeStreet;
+  action: RangeAction;
+  /** 直前のレンジがこのアクションを選ぶ確率。 */
+  actionProbability: number;
+  range: WeightedRangeSnapshot;
+};
+
+export type RangeUpdatePayload = {
+  /** ボードとかぶるコンボを除いた開始レンジ。 */
+  initial: WeightedRangeSnapshot;
+  steps: RangeUpdateStep[];
+};
+
+export type BbDefenseParams = {
+  /** レイザーのオープンレンジ。 */
+  openRange: string;
+  /** レイザーの合計ベット額（BB 単位）。 */
+  openSize: number;
+  /** オープン前のブラインドとアンテ（BB 単位、BB のブラインドを含む）。既定 1.5 */
+  pot?: number;
+  /** BB のディフェンス候補レンジ。 */
+  defendRange: string;
+  /** BB の equity 実現率。既定 1（実現率を考えない） */
+  realization?: number;
+  /** コンボペアごとの試行回数。 */
+  trials: number;
+  seed?: bigint;
+};
+
+export type BbDefenseClass = {
+  handClass: string;
+  /** 候補レンジに入っているこのクラスのコンボ数。 */
+  combos: number;
+  /** オープンレンジに対する equity。 */
+  equity: number;
+  /** equity × realization。 */
+  realizedEquity: number;
+  /** フォールドと比べたコールの期待値（BB 単位）。 */
+  callEv: number;
+  action: "call" | "fold";
+};
+
+export type BbDefensePayload = {
+  callAmount: number;
+  /** コールした後のポット。 */
+  finalPot: number;
+  /** コールに必要な equity（実現率なし）。 */
+  potOdds: number;
+  /** 実現率を考えた生の equity の必要ライン。 */
+  requiredEquity: number;
+  mdf: number;
+  /** 全 1326 コンボのうちコールと判定した割合。 */
+  defendFrequency: number;
+  /** 全 1326 コンボのうち候補レンジの割合。 */
+  candidateFrequency: number;
+  /** 候補レンジに入っているクラス（グリッドの並び）。 */
+  classes: BbDefenseClass[];
+};
+
+export type HoldemEngineOptions = {
+  stacks: number[];
+  /** 既定 0.5 */
+  smallBlind?: number;
+  /** 既定 1 */
+  bigBlind?: number;
+  ante?: number;
+  dealerIdx?: number;
+  /** デッキのシャッフルに使う。省略時はランダム。 */
+  seed?: bigint;
+};
+
+export type HoldemEngineRound =
+  | "Starting"
+  | "Ante"
+  | "DealPreflop"
+  | "Preflop"
+  | "DealFlop"
+  | "Flop"
+  | "DealTurn"
+  | "Turn"
+  | "DealRiver"
+  | "River"
+  | "Showdown"
+  | "Complete";
+
+export type HoldemLegalActions = {
+  player: number;
+  canCheck: boolean;
+  /** コールに必要な額（足りなければ残りスタック）。チェックできるときは 0。 */
+  callAmount: number;
+  /** ベット / レイズできないとき（アンダーレイズで再オープンされていない等）は null。 */
+  minRaiseTo: number | null;
+  /** オールインの額。 */
+  maxRaiseTo: number | null;
+};
+
+/** features/game/holdem.ts の HoldemGameState と同じ shape（カードは "As" 形式）。 */
+export type HoldemEngineSnapshot = {
+  numPlayers: number;
+  playerActive: boolean[];
+  playerAllIn: boolean[];
+  totalPot: number;
+  stacks: number[];
+  startingStacks: number[];
+  playerBet: number[];
+  playerWinnings: number[];
+  bigBlind: number;
+  smallBlind: number;
+  ante: number;
+  hands: [string, string][];
+  dealerIdx: number;
+  round: HoldemEngineRound;
+  roundBefore: HoldemEngineRound;
+  roundData: {
+    startingPlayerActive: boolean[];
+    needsAction: boolean[];
+    minRaise: number;
+    bet: number;
+    playerBet: number[];
+    totalBetCount: number;
+    totalRaiseCount: number;
+    toActIdx: number;
+  };
+  board: string[];
+  bbPosted: boolean;
+  sbPosted: boolean;
+  /** ベッティングラウンド以外では null。 */
+  legal: HoldemLegalActions | null;
+  /** 精算したポット（Complete になるまで空）。先頭がメインポット。 */
+  pots: SettledPot[];
+  /** ショーダウンした各プレイヤーの役の説明。 */
+  showdown: (string | null)[];
 };
 
 export type RankDistributionEntry = {
@@ -330,3 +971,42 @@ export type OpenRangesPayload = {
   equity: number;
   rankWins: RankResults;
 };
+
+/** 6-max のポジション（アクション順）。 */
+export type SixMaxPosition = "UTG" | "MP" | "CO" | "BTN" | "SB" | "BB";
+
+export type SixMaxAction = "open" | "call" | "threeBet";
+
+export type SixMaxPreflopParams = {
+  heroPosition: SixMaxPosition;
+  /** ハンド（`"AsKs"`）またはレンジ。 */
+  heroRange: string;
+  action: SixMaxAction;
+  /** コール / 3 ベットのときのオープナー（ヒーローより前）。 */
+  openerPosition?: SixMaxPosition;
+  openRanges: Record<SixMaxPosition, string>;
+  callRanges: Record<SixMaxPosition, string>;
+  threeBetRanges: Record<SixMaxPosition, string>;
+  /** チップ単位。サイズはその時点の合計ベット額。 */
+  smallBlind?: number;
+  bigBlind?: number;
+  openSize: number;
+  threeBetSize: number;
+  trials: number;
+  seed?: bigint;
+  wasmUrl?: string;
+};
+
+export type SixMaxPreflopPayload = {
+  deals: number;
+  /** 全員が降りた配り数。 */
+  folds: number;
+  /** ショーダウンになった配りでのエクイティ。 */
+  equity: number;
+  averageOpponents: number;
+  /** 追加投資に対する平均の収支（チップ）。 */
+  ev: number;
+  potBefore: number;
+  investment: number;
+  continueFrequency: Record<SixMaxPosition, number>;
+};
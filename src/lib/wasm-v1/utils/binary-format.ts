@@ -0,0 +1,176 @@
+import { CARD_RANK_ORDER } from "@/utils/card";
+import type {
+  CombinedEntry,
+  CombinedPayload,
+  RangeEquityEntry,
+  RangeVsRangePayload,
+  RankOutcomeResults,
+} from "../types";
+
+// `*_binary` エクスポート（v1: rust-wasm/src/sim/binary.rs、v2: rust/src/binary.rs）が
+// 返す Uint32Array のデコーダ。レイアウトの詳細は Rust 側のモジュールドキュメントを参照。
+// レコード位置はヘッダのヘッダ長・レコード長から求める（末尾に語が増えても読める）。
+
+export const BINARY_MAGIC = 0x42525050; // "PPRB"（リトルエンディアン）
+export const BINARY_VERSION = 1;
+export const BINARY_HEADER_LEN = 6;
+export const BINARY_KIND_RANGE_VS_RANGE = 1;
+export const BINARY_KIND_VS_LIST_WITH_RANKS = 2;
+export const BINARY_EQUITY_SCALE = 1_000_000;
+
+const RANGE_VS_RANGE_RECORD_LEN = 4;
+const VS_LIST_WITH_RANKS_RECORD_LEN = 35;
+const FLAG_HERO_AGGREGATE = 1;
+
+const RANK_LABELS: (keyof RankOutcomeResults)[] = [
+  "High Card",
+  "One Pair",
+  "Two Pair",
+  "Three of a Kind",
+  "Straight",
+  "Flush",
+  "Full House",
+  "Four of a Kind",
+  "Straight Flush",
+];
+
+export type BinaryHeader = {
+  kind: number;
+  headerLen: number;
+  recordLen: number;
+  recordCount: number;
+};
+
+const decodeCard = (v: number) => {
+  const rank = v >> 2;
+  const suit = v & 0b11;
+  const rankChar = "23456789TJQKA"[rank] ?? "?";
+  const suitChar = ["s", "h", "d", "c"][suit] ?? "?";
+  return `${rankChar}${suitChar}`;
+};
+
+const sortByRankDesc = (a: string, b: string) => {
+  const aRank = CARD_RANK_ORDER[a[0] ?? ""] ?? 0;
+  const bRank = CARD_RANK_ORDER[b[0] ?? ""] ?? 0;
+  return bRank - aRank;
+};
+
+const decodeHand = (c1: number, c2: number) => {
+  const [high, low] = [decodeCard(c1), decodeCard(c2)].sort(sortByRankDesc);
+  return `${high} ${low}`;
+};
+
+export function decodeBinaryHeader(words: Uint32Array): BinaryHeader {
+  if (words.length < BINARY_HEADER_LEN) {
+    throw new Error("Binary result is shorter than the header");
+  }
+  if (words[0] !== BINARY_MAGIC) {
+    throw new Error("Binary result has wrong magic");
+  }
+  if (words[1] !== BINARY_VERSION) {
+    throw new Error(`Unsupported binary result version ${words[1]}`);
+  }
+  const header = {
+    kind: words[2],
+    headerLen: words[3],
+    recordLen: words[4],
+    recordCount: words[5],
+  };
+  const needed = header.headerLen + header.recordLen * header.recordCount;
+  if (header.headerLen < BINARY_HEADER_LEN || needed > words.length) {
+    throw new Error("Binary result is truncated");
+  }
+  return header;
+}
+
+// 種類を確認し、各レコードの先頭 minLen 語を返す
+const readRecords = (words: Uint32Array, kind: number, minLen: number) => {
+  const header = decodeBinaryHeader(words);
+  if (header.kind !== kind) {
+    throw new Error(`Expected binary result kind ${kind}, got ${header.kind}`);
+  }
+  if (header.recordLen < minLen) {
+    throw new Error("Binary result records are too short");
+  }
+  return Array.from({ length: header.recordCount }, (_, i) => {
+    const base = header.headerLen + i * header.recordLen;
+    return words.subarray(base, base + minLen);
+  });
+};
+
+/** `simulate_range_vs_range_equity_binary` の結果を RangeVsRangePayload にする。 */
+export function decodeRangeVsRangeBinary(
+  words: Uint32Array,
+): RangeVsRangePayload {
+  const hero: RangeEquityEntry[] = [];
+  const villain: RangeEquityEntry[] = [];
+  for (const record of readRecords(
+    words,
+    BINARY_KIND_RANGE_VS_RANGE,
+    RANGE_VS_RANGE_RECORD_LEN,
+  )) {
+    const entry = {
+      hand: decodeHand(record[0], record[1]),
+      equity: record[3] / BINARY_EQUITY_SCALE,
+    };
+    if (record[2] === 1) {
+      villain.push(entry);
+    } else {
+      hero.push(entry);
+    }
+  }
+
+  hero.sort((a, b) => b.equity - a.equity);
+  villain.sort((a, b) => b.equity - a.equity);
+  return { hero, villain };
+}
+
+/** `simulate_vs_list_with_ranks_binary` の結果を CombinedPayload にする。 */
+export function decodeVsListWithRanksBinary(
+  words: Uint32Array,
+): CombinedPayload {
+  const data: CombinedEntry[] = [];
+  let heroEntry: CombinedEntry | null = null;
+
+  for (const record of readRecords(
+    words,
+    BINARY_KIND_VS_LIST_WITH_RANKS,
+    VS_LIST_WITH_RANKS_RECORD_LEN,
+  )) {
+    const results = {} as RankOutcomeResults;
+    for (let r = 0; r < RANK_LABELS.length; r += 1) {
+      results[RANK_LABELS[r]] = {
+        win: record[8 + r],
+        tie: record[17 + r],
+        lose: record[26 + r],
+      };
+    }
+    const entry = {
+      hand: decodeHand(record[0], record[1]),
+      count: record[3],
+      win: record[4],
+      tie: record[5],
+      lose: record[6],
+      results,
+    };
+    if (record[2] & FLAG_HERO_AGGREGATE) {
+      heroEntry = entry;
+    }
+    data.push(entry);
+  }
+
+  if (!heroEntry) {
+    throw new Error("Hero aggregate record missing from binary result");
+  }
+
+  data.sort(
+    (a, b) => (b.win + b.tie / 2) / b.count - (a.win + a.tie / 2) / a.count,
+  );
+
+  const equity =
+    heroEntry.count === 0
+      ? 0
+      : (heroEntry.win + heroEntry.tie * 0.5) / heroEntry.count;
+
+  return { hand: heroEntry.hand, equity, data };
+}
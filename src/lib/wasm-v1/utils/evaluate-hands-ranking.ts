@@ -15,6 +15,65 @@ const RANK_LABELS = [
   "Straight Flush",
 ] as const;
 
+const RECORD_LEN = 14;
+
+// bestFive の先頭から何枚が役の札か（残りがキッカー）。rankIndex 順。
+const MADE_CARD_COUNT = [1, 2, 4, 3, 5, 5, 5, 4, 5];
+
+const VALUE_NAMES = [
+  "Two",
+  "Three",
+  "Four",
+  "Five",
+  "Six",
+  "Seven",
+  "Eight",
+  "Nine",
+  "Ten",
+  "Jack",
+  "Queen",
+  "King",
+  "Ace",
+];
+
+const valueName = (card: string) =>
+  VALUE_NAMES["23456789TJQKA".indexOf(card[0] ?? "")] ?? "?";
+
+const pluralName = (card: string) => {
+  const name = valueName(card);
+  return name === "Six" ? "Sixes" : `${name}s`;
+};
+
+// rust/src/rank.rs の hand_detail と同じ書式で説明文を作る
+const describeHand = (rankIndex: number, bestFive: string[]) => {
+  const label = RANK_LABELS[rankIndex] ?? "Unknown";
+  const [first = "", , third = "", fourth = ""] = bestFive;
+  let body: string;
+  switch (rankIndex) {
+    case 0:
+      body = valueName(first);
+      break;
+    case 1:
+    case 3:
+    case 7:
+      body = pluralName(first);
+      break;
+    case 2:
+      body = `${pluralName(first)} and ${pluralName(third)}`;
+      break;
+    case 6:
+      body = `${pluralName(first)} full of ${pluralName(fourth)}`;
+      break;
+    default:
+      body = `${valueName(first)} high`;
+  }
+  const made = MADE_CARD_COUNT[rankIndex] ?? 5;
+  const kicker = bestFive[made];
+  return kicker
+    ? `${label}, ${body}, ${valueName(kicker)} kicker`
+    : `${label}, ${body}`;
+};
+
 const decodeCard = (v: number) => {
   const rank = v >> 2;
   const suit = v & 0b11;
@@ -53,7 +112,7 @@ export async function evaluateHandsRanking({
   const handsBuf = writeString(handsStr);
   const boardBuf = writeString(boardStr);
 
-  const outLen = hands.length * 9;
+  const outLen = hands.length * RECORD_LEN;
   const outPtr = allocU32(outLen);
 
   const rc = exports.evaluate_hands_ranking(
@@ -69,22 +128,28 @@ export async function evaluateHandsRanking({
   }
   if (rc === 0) return [];
 
-  const out = new Uint32Array(memory.buffer, outPtr, rc * 9);
+  const out = new Uint32Array(memory.buffer, outPtr, rc * RECORD_LEN);
   const entries: HandRankingEntry[] = [];
   for (let i = 0; i < rc; i += 1) {
-    const base = i * 9;
+    const base = i * RECORD_LEN;
     const card1 = decodeCard(out[base] ?? 0);
     const card2 = decodeCard(out[base + 1] ?? 0);
     const [high, low] = [card1, card2].sort(sortByRankDesc);
     const rankIndex = out[base + 2] ?? 0;
     const encoded = out[base + 3] ?? 0;
-    const kickers = Array.from(out.slice(base + 4, base + 9));
+    // base + 4..9 はタイブレーク用ランク。キッカーは bestFive から取る
+    const bestFive = Array.from(out.slice(base + 9, base + 14), decodeCard);
+    const kickers = bestFive
+      .slice(MADE_CARD_COUNT[rankIndex] ?? 5)
+      .map((card) => "23456789TJQKA".indexOf(card[0] ?? ""));
     entries.push({
       hand: `${high} ${low}`,
       rankIndex,
       rankName: RANK_LABELS[rankIndex] ?? "Unknown",
       encoded,
+      bestFive,
       kickers,
+      description: describeHand(rankIndex, bestFive),
     });
   }
 
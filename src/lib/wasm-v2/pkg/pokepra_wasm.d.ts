@@ -1,21 +1,183 @@
 /* tslint:disable */
 /* eslint-disable */
 
+/**
+ * NLHE の 1 ハンドを進めるゲームエンジン（[`game::GameState`]）。
+ *
+ * 各アクションは検証してから適用し、適用後のスナップショット（JS オブジェクト）を返す。
+ * 不正なアクションは Error になり、状態は変わらない。
+ */
+export class HoldemGame {
+    free(): void;
+    [Symbol.dispose](): void;
+    all_in(): any;
+    call(): any;
+    check(): any;
+    fold(): any;
+    /**
+     * `seed` でシャッフルしたデッキから配り、ブラインドを払ってプリフロップの手番まで進める。
+     */
+    constructor(stacks: Float64Array, small_blind: number, big_blind: number, ante: number, dealer_idx: number, seed: bigint);
+    /**
+     * このラウンドの合計ベット額が `amount` になるようにベット / レイズする。
+     */
+    raise_to(amount: number): any;
+    snapshot(): any;
+}
+
+/**
+ * 入力をスート同型まで正規化したキーで結果を使い回すキャッシュ（[`cache::ResultCache`]）。
+ *
+ * JS 側でインスタンスを持ち続け、`export_bytes` / `import_bytes` で永続化する。
+ */
+export class SimulationCache {
+    free(): void;
+    [Symbol.dispose](): void;
+    clear(): void;
+    /**
+     * キャッシュの状態をバイト列（JS では `Uint8Array`）にする。
+     */
+    export_bytes(): Uint8Array;
+    /**
+     * `export_bytes` の出力で状態を置き換える。
+     */
+    import_bytes(bytes: Uint8Array): void;
+    /**
+     * `capacity` 件まで保持する。0 のときは既定値（[`cache::DEFAULT_CAPACITY`]）。
+     */
+    constructor(capacity: number);
+    /**
+     * キャッシュを通した [`simulate_range_vs_range_equity`]。
+     */
+    simulate_range_vs_range_equity(hero_range: string, villain_range: string, board: string, trials: number, seed: bigint): any;
+    /**
+     * キャッシュを通した [`simulate_vs_list_with_ranks`]。
+     */
+    simulate_vs_list_with_ranks(hero: string, board: string, compare: string, trials: number, seed: bigint): any;
+    /**
+     * 保持しているエントリ数。
+     */
+    size(): number;
+}
+
+/**
+ * マルチウェイのオールインで、サイドポットを考えた各プレイヤーの期待リターン。
+ *
+ * `players` は席順のセミコロン区切りのハンドかレンジ（フォールドした席は空）。
+ * ボードが 4 枚以上なら全列挙、それ以外は `trials` 回の Monte Carlo。
+ */
+export function all_in_ev(players: string, contributions: Float64Array, board: string, trials: number, seed: bigint): any;
+
+/**
+ * オープンに対する BB のハンドクラスごとのコール / フォールドと、ディフェンス率・MDF。
+ *
+ * 額は BB 単位。`pot` はオープン前のブラインドとアンテ、`open_size` はレイザーの合計ベット額。
+ * `realization` は BB の equity 実現率（1 なら実現率を考えない）。
+ */
+export function bb_defense(open_range: string, open_size: number, pot: number, defend_range: string, realization: number, trials: number, seed: bigint): any;
+
+/**
+ * フロップをスート同型の代表に揃え、同型なフロップの数と一緒に返す。
+ */
+export function canonical_flop(board: string): any;
+
+/**
+ * 5 枚のボードで 2 ハンドを比べ、勝敗と決め手（キッカー差など）を返す。
+ */
+export function compare_showdown(first: string, second: string, board: string): any;
+
+/**
+ * server seed のハッシュ。配る前に公開するコミットメント。
+ */
+export function deal_commitment(server_seed: string): string;
+
+/**
+ * ランアウトに沿ったストリートごとの equity と、ターン / リバーのカード別 equity。
+ * `board` が 5 枚未満なら残りを `seed` から引く。
+ */
+export function equity_path(hero: string, board: string, compare: string, trials: number, seed: bigint): any;
+
 /**
  * ボードが確定している前提で、複数ハンドの役を評価し強い順に並べる。
  */
 export function evaluate_hands_ranking(hands: string, board: string): any;
 
+/**
+ * スート同型で 1755 通りの全フロップで range vs range を走らせ、フロップ別・テクスチャ別の
+ * hero equity を返す。レンジはスート対称なもの（レンジ式）に限る。
+ */
+export function flop_report(hero_range: string, villain_range: string, trials: number, seed: bigint): any;
+
+/**
+ * 32 バイトの乱数から作った server seed（16 進）。
+ */
+export function generate_server_seed(): string;
+
+/**
+ * ハンドクラス（`"AKs"` 等）の全コンボ。`dead` を含むコンボは除く。
+ */
+export function hand_class_combos(_class: string, dead: string): any;
+
+/**
+ * 13×13 レンジ表の 169 セル（クラス名・位置・使用済みカードを除いたコンボ数）。
+ */
+export function hand_class_grid(dead: string): any;
+
+/**
+ * 具体的なコンボ（`"AsKs"`）をハンドクラス（`"AKs"`）にする。
+ */
+export function hand_class_of(hand: string): string;
+
+/**
+ * フロップ / ターンでの hero の HS・PPot・NPot・EHS・E[HS²]。
+ * `villain_range` が空なら一様レンジ。
+ */
+export function hand_strength(hero: string, board: string, villain_range: string): any;
+
+/**
+ * フロップ / ターンから、次に来うる各カードでの hero equity（52 枠のヒートマップ）。
+ */
+export function next_card_equity(hero: string, villain_range: string, board: string): any;
+
+/**
+ * ボード以外の全コンボを役の強さでクラス分けし、ナッツ順と hero の順位を返す。
+ * `hero` は空文字でもよい。
+ */
+export function nut_ranking(hero: string, board: string): any;
+
 /**
  * レンジ式を全コンボに展開する。`excluded` に含まれるカードを使うコンボは弾く。
  */
 export function parse_range_to_hands(range: string, excluded: string): any;
 
+/**
+ * 各プレイヤーが出した額からメインポット・サイドポットを組み、ショーダウンで精算する。
+ *
+ * `folded` は席ごとの 0 / 1、`hands` は席順のセミコロン区切り（フォールドした席は空でよい）。
+ * `chip` が 0 なら端数も等分し、正なら `chip` 単位で分けて余りをボタンの左から配る。
+ */
+export function settle_pots(contributions: Float64Array, folded: Uint8Array, hands: string, board: string, button: number, chip: number): any;
+
+/**
+ * server seed・client seed・nonce から決まる 52 枚の配り順とそのハッシュ。
+ */
+export function shuffled_deck(server_seed: string, client_seed: string, nonce: bigint): any;
+
 /**
  * hero レンジ vs villain レンジの MC シミュレーション。
  */
 export function simulate_range_vs_range_equity(hero_range: string, villain_range: string, board: string, trials: number, seed: bigint): any;
 
+/**
+ * [`simulate_range_vs_range_equity`] の結果を [`binary`] 形式の `Uint32Array` で返す。
+ */
+export function simulate_range_vs_range_equity_binary(hero_range: string, villain_range: string, board: string, trials: number, seed: bigint): Uint32Array;
+
+/**
+ * [`simulate_range_vs_range_equity`] の結果を、ハンドクラスごとの 169 セル（グリッドの並び）で返す。
+ */
+export function simulate_range_vs_range_equity_grid(hero_range: string, villain_range: string, board: string, trials: number, seed: bigint): any;
+
 /**
  * hero vs 相手リストの MC シミュレーション（役分布なし、equity のみ）。
  */
@@ -26,6 +188,25 @@ export function simulate_vs_list_equity(hero: string, board: string, compare: st
  */
 export function simulate_vs_list_with_ranks(hero: string, board: string, compare: string, trials: number, seed: bigint): any;
 
+/**
+ * [`simulate_vs_list_with_ranks`] の結果を [`binary`] 形式の `Uint32Array` で返す。
+ */
+export function simulate_vs_list_with_ranks_binary(hero: string, board: string, compare: string, trials: number, seed: bigint): Uint32Array;
+
+/**
+ * 相手のアクション列から、各アクション後の重み付きレンジ（ベイズ更新）を返す。
+ *
+ * `range` は `"QQ+,AKs,AQs:0.5"`（`:重み` は省略可）、`actions` は `"preflop raise; flop bet"`。
+ * `models` はアクションごとのセミコロン区切りで `"top_pair:0.8,AA:1"` のような頻度。
+ * 空なら組み込みのヒューリスティックを使う。
+ */
+export function update_range(range: string, board: string, actions: string, models: string): any;
+
+/**
+ * 明かされた server seed がコミットメントと一致し、`dealt`（配った順）がデッキの先頭どおりか。
+ */
+export function verify_deal(commitment: string, server_seed: string, client_seed: string, nonce: bigint, dealt: string): any;
+
 /**
  * クレートのバージョン文字列。動作確認用。
  */
@@ -35,16 +216,54 @@ export type InitInput = RequestInfo | URL | Response | BufferSource | WebAssembl
 
 export interface InitOutput {
     readonly memory: WebAssembly.Memory;
+    readonly __wbg_holdemgame_free: (a: number, b: number) => void;
+    readonly __wbg_simulationcache_free: (a: number, b: number) => void;
+    readonly all_in_ev: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: bigint) => void;
+    readonly bb_defense: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: bigint) => void;
+    readonly canonical_flop: (a: number, b: number, c: number) => void;
+    readonly compare_showdown: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => void;
+    readonly deal_commitment: (a: number, b: number, c: number) => void;
+    readonly equity_path: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: bigint) => void;
     readonly evaluate_hands_ranking: (a: number, b: number, c: number, d: number, e: number) => void;
+    readonly flop_report: (a: number, b: number, c: number, d: number, e: number, f: number, g: bigint) => void;
+    readonly generate_server_seed: (a: number) => void;
+    readonly hand_class_combos: (a: number, b: number, c: number, d: number, e: number) => void;
+    readonly hand_class_grid: (a: number, b: number, c: number) => void;
+    readonly hand_class_of: (a: number, b: number, c: number) => void;
+    readonly hand_strength: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => void;
+    readonly holdemgame_all_in: (a: number, b: number) => void;
+    readonly holdemgame_call: (a: number, b: number) => void;
+    readonly holdemgame_check: (a: number, b: number) => void;
+    readonly holdemgame_fold: (a: number, b: number) => void;
+    readonly holdemgame_new: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: bigint) => void;
+    readonly holdemgame_raise_to: (a: number, b: number, c: number) => void;
+    readonly holdemgame_snapshot: (a: number, b: number) => void;
+    readonly next_card_equity: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => void;
+    readonly nut_ranking: (a: number, b: number, c: number, d: number, e: number) => void;
     readonly parse_range_to_hands: (a: number, b: number, c: number, d: number, e: number) => void;
+    readonly settle_pots: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number) => void;
+    readonly shuffled_deck: (a: number, b: number, c: number, d: number, e: number, f: bigint) => void;
     readonly simulate_range_vs_range_equity: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: bigint) => void;
+    readonly simulate_range_vs_range_equity_binary: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: bigint) => void;
+    readonly simulate_range_vs_range_equity_grid: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: bigint) => void;
     readonly simulate_vs_list_equity: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: bigint, j: number) => void;
     readonly simulate_vs_list_with_ranks: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: bigint) => void;
+    readonly simulate_vs_list_with_ranks_binary: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: bigint) => void;
+    readonly simulationcache_clear: (a: number) => void;
+    readonly simulationcache_export_bytes: (a: number, b: number) => void;
+    readonly simulationcache_import_bytes: (a: number, b: number, c: number, d: number) => void;
+    readonly simulationcache_new: (a: number) => number;
+    readonly simulationcache_simulate_range_vs_range_equity: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: bigint) => void;
+    readonly simulationcache_simulate_vs_list_with_ranks: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: bigint) => void;
+    readonly simulationcache_size: (a: number) => number;
+    readonly update_range: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number) => void;
+    readonly verify_deal: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: bigint, i: number, j: number) => void;
     readonly version: (a: number) => void;
     readonly __wbindgen_export: (a: number, b: number) => number;
     readonly __wbindgen_export2: (a: number, b: number, c: number, d: number) => number;
+    readonly __wbindgen_export3: (a: number) => void;
     readonly __wbindgen_add_to_stack_pointer: (a: number) => number;
-    readonly __wbindgen_export3: (a: number, b: number, c: number) => void;
+    readonly __wbindgen_export4: (a: number, b: number, c: number) => void;
 }
 
 export type SyncInitInput = BufferSource | WebAssembly.Module;
@@ -1,13 +1,51 @@
 /* tslint:disable */
 /* eslint-disable */
 export const memory: WebAssembly.Memory;
+export const __wbg_holdemgame_free: (a: number, b: number) => void;
+export const __wbg_simulationcache_free: (a: number, b: number) => void;
+export const all_in_ev: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: bigint) => void;
+export const bb_defense: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: bigint) => void;
+export const canonical_flop: (a: number, b: number, c: number) => void;
+export const compare_showdown: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => void;
+export const deal_commitment: (a: number, b: number, c: number) => void;
+export const equity_path: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: bigint) => void;
 export const evaluate_hands_ranking: (a: number, b: number, c: number, d: number, e: number) => void;
+export const flop_report: (a: number, b: number, c: number, d: number, e: number, f: number, g: bigint) => void;
+export const generate_server_seed: (a: number) => void;
+export const hand_class_combos: (a: number, b: number, c: number, d: number, e: number) => void;
+export const hand_class_grid: (a: number, b: number, c: number) => void;
+export const hand_class_of: (a: number, b: number, c: number) => void;
+export const hand_strength: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => void;
+export const holdemgame_all_in: (a: number, b: number) => void;
+export const holdemgame_call: (a: number, b: number) => void;
+export const holdemgame_check: (a: number, b: number) => void;
+export const holdemgame_fold: (a: number, b: number) => void;
+export const holdemgame_new: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: bigint) => void;
+export const holdemgame_raise_to: (a: number, b: number, c: number) => void;
+export const holdemgame_snapshot: (a: number, b: number) => void;
+export const next_card_equity: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => void;
+export const nut_ranking: (a: number, b: number, c: number, d: number, e: number) => void;
 export const parse_range_to_hands: (a: number, b: number, c: number, d: number, e: number) => void;
+export const settle_pots: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number) => void;
+export const shuffled_deck: (a: number, b: number, c: number, d: number, e: number, f: bigint) => void;
 export const simulate_range_vs_range_equity: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: bigint) => void;
+export const simulate_range_vs_range_equity_binary: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: bigint) => void;
+export const simulate_range_vs_range_equity_grid: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: bigint) => void;
 export const simulate_vs_list_equity: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: bigint, j: number) => void;
 export const simulate_vs_list_with_ranks: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: bigint) => void;
+export const simulate_vs_list_with_ranks_binary: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: bigint) => void;
+export const simulationcache_clear: (a: number) => void;
+export const simulationcache_export_bytes: (a: number, b: number) => void;
+export const simulationcache_import_bytes: (a: number, b: number, c: number, d: number) => void;
+export const simulationcache_new: (a: number) => number;
+export const simulationcache_simulate_range_vs_range_equity: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: bigint) => void;
+export const simulationcache_simulate_vs_list_with_ranks: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: bigint) => void;
+export const simulationcache_size: (a: number) => number;
+export const update_range: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number) => void;
+export const verify_deal: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: bigint, i: number, j: number) => void;
 export const version: (a: number) => void;
 export const __wbindgen_export: (a: number, b: number) => number;
 export const __wbindgen_export2: (a: number, b: number, c: number, d: number) => number;
+export const __wbindgen_export3: (a: number) => void;
 export const __wbindgen_add_to_stack_pointer: (a: number) => number;
-export const __wbindgen_export3: (a: number, b: number, c: number) => void;
+export const __wbindgen_export4: (a: number, b: number, c: number) => void;
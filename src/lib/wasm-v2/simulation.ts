@@ -1,32 +1,136 @@
 import type {
+  AllInEvParams,
+  AllInEvPayload,
+  BbDefenseParams,
+  BbDefensePayload,
+  CanonicalFlopPayload,
   CombinedPayload,
+  CompareShowdownParams,
+  DealVerificationPayload,
+  EquityPathParams,
+  EquityPathPayload,
   EquityPayload,
   EvaluateHandsRankingParams,
+  FlopReportParams,
+  FlopReportPayload,
+  HandClassCell,
+  HandClassCombosParams,
   HandRankingEntry,
+  HandStrengthParams,
+  HandStrengthPayload,
+  HoldemEngineOptions,
+  HoldemEngineSnapshot,
+  NextCardEquityParams,
+  NextCardEquityPayload,
+  NutRankingParams,
+  NutRankingPayload,
   ParseRangeParams,
+  RangeGridCell,
+  RangeUpdatePayload,
+  RangeVsRangeGridPayload,
   RangeVsRangeParams,
   RangeVsRangePayload,
+  SettlementPayload,
+  SettlePotsParams,
+  ShowdownComparison,
+  ShuffledDeckParams,
+  ShuffledDeckPayload,
   SimulateParams,
+  UpdateRangeParams,
+  VerifyDealParams,
 } from "@/lib/wasm-v1/types";
 import init, {
+  HoldemGame,
+  SimulationCache,
+  all_in_ev as wasmAllInEv,
+  bb_defense as wasmBbDefense,
+  canonical_flop as wasmCanonicalFlop,
+  compare_showdown as wasmCompareShowdown,
+  deal_commitment as wasmDealCommitment,
+  equity_path as wasmEquityPath,
   evaluate_hands_ranking as wasmEvaluateHandsRanking,
+  flop_report as wasmFlopReport,
+  generate_server_seed as wasmGenerateServerSeed,
+  hand_class_combos as wasmHandClassCombos,
+  hand_class_grid as wasmHandClassGrid,
+  hand_class_of as wasmHandClassOf,
+  hand_strength as wasmHandStrength,
+  next_card_equity as wasmNextCardEquity,
+  nut_ranking as wasmNutRanking,
   parse_range_to_hands as wasmParseRangeToHands,
+  settle_pots as wasmSettlePots,
+  shuffled_deck as wasmShuffledDeck,
   simulate_range_vs_range_equity as wasmSimulateRangeVsRangeEquity,
+  simulate_range_vs_range_equity_binary as wasmSimulateRangeVsRangeEquityBinary,
+  simulate_range_vs_range_equity_grid as wasmSimulateRangeVsRangeEquityGrid,
   simulate_vs_list_equity as wasmSimulateVsListEquity,
   simulate_vs_list_with_ranks as wasmSimulateVsListWithRanks,
+  simulate_vs_list_with_ranks_binary as wasmSimulateVsListWithRanksBinary,
+  update_range as wasmUpdateRange,
+  verify_deal as wasmVerifyDeal,
 } from "./pkg/pokepra_wasm";
 
 export type {
+  AllInEvParams,
+  AllInEvPayload,
+  AllInEvPlayer,
+  AllInEvPot,
+  BbDefenseClass,
+  BbDefenseParams,
+  BbDefensePayload,
+  CanonicalFlopPayload,
+  CardEquity,
   CombinedPayload,
+  CompareShowdownParams,
+  DealVerificationPayload,
   EquityEntry,
+  EquityPathParams,
+  EquityPathPayload,
   EquityPayload,
   EvaluateHandsRankingParams,
+  FlopEquityEntry,
+  FlopGroup,
+  FlopReportParams,
+  FlopReportPayload,
+  HandClassCell,
+  HandClassCombosParams,
   HandRankingEntry,
+  HandStrengthParams,
+  HandStrengthPayload,
+  HeroNutRank,
+  HoldemEngineOptions,
+  HoldemEngineRound,
+  HoldemEngineSnapshot,
+  HoldemLegalActions,
+  NextCardEquityParams,
+  NextCardEquityPayload,
+  NutClass,
+  NutRankingParams,
+  NutRankingPayload,
+  ObservedAction,
   ParseRangeParams,
+  RangeAction,
   RangeEquityEntry,
+  RangeGridCell,
+  RangeStreet,
+  RangeUpdatePayload,
+  RangeUpdateStep,
+  RangeVsRangeGridPayload,
   RangeVsRangeParams,
   RangeVsRangePayload,
+  SettledPot,
+  SettlementPayload,
+  SettlePotsParams,
+  ShowdownComparison,
+  ShuffledDeckParams,
+  ShuffledDeckPayload,
   SimulateParams,
+  StreetEquity,
+  StrengthBucket,
+  UpdateRangeParams,
+  VerifyDealParams,
+  WeightedHandEntry,
+  WeightedRangeSnapshot,
 } from "@/lib/wasm-v1/types";
 
 const DEFAULT_SEED = 123_456_789n;
@@ -40,6 +144,16 @@ const ensureLoaded = () => {
   return initPromise;
 };
 
+// 正規化した入力で結果を使い回すキャッシュ。`useCache: true` の呼び出しだけが使う。
+let cache: SimulationCache | null = null;
+
+const getCache = () => {
+  if (!cache) {
+    cache = new SimulationCache(0);
+  }
+  return cache;
+};
+
 const yieldToBrowser = () =>
   new Promise<void>((resolve) => {
     setTimeout(resolve, 0);
@@ -63,6 +177,8 @@ const normalizeRangeInput = (range: string | string[][]) => {
 
 type SimulateVsListWithRanksParams = SimulateParams & {
   onProgress?: (pct: number) => void;
+  /** スート同型まで同じ入力の結果を使い回す（seed はキーに含まれない）。 */
+  useCache?: boolean;
 };
 
 export async function simulateVsListWithRanks(
@@ -76,6 +192,7 @@ export async function simulateVsListWithRanks(
     trials,
     seed = DEFAULT_SEED,
     onProgress,
+    useCache = false,
   } = params;
 
   const heroStr = hero.join(" ").trim();
@@ -89,12 +206,16 @@ export async function simulateVsListWithRanks(
   onProgress?.(0);
   await yieldToBrowser();
 
-  const result = wasmSimulateVsListWithRanks(
-    heroStr,
-    boardStr,
-    compareStr,
-    trials,
-    seed,
+  const result = (
+    useCache
+      ? getCache().simulate_vs_list_with_ranks(
+          heroStr,
+          boardStr,
+          compareStr,
+          trials,
+          seed,
+        )
+      : wasmSimulateVsListWithRanks(heroStr, boardStr, compareStr, trials, seed)
   ) as CombinedPayload;
 
   onProgress?.(100);
@@ -112,6 +233,98 @@ export async function evaluateHandsRanking(
   return wasmEvaluateHandsRanking(handsStr, boardStr) as HandRankingEntry[];
 }
 
+/** 5 枚のボードで 2 ハンドを比べ、勝敗と決め手を返す（ショーダウンクイズの解説用）。 */
+export async function compareShowdown(
+  params: CompareShowdownParams,
+): Promise<ShowdownComparison> {
+  await ensureLoaded();
+  const { first, second, board } = params;
+  return wasmCompareShowdown(
+    first.join(" ").trim(),
+    second.join(" ").trim(),
+    board.join(" ").trim(),
+  ) as ShowdownComparison;
+}
+
+/** ボード上のナッツ順（同じ強さのコンボごとのクラス）と hero の順位・パーセンタイル。 */
+export async function nutRanking(
+  params: NutRankingParams,
+): Promise<NutRankingPayload> {
+  await ensureLoaded();
+  const { hero = [], board } = params;
+  // serde の Option は undefined で返るので null にそろえる
+  const result = wasmNutRanking(
+    hero.join(" ").trim(),
+    board.join(" ").trim(),
+  ) as NutRankingPayload;
+  return { ...result, hero: result.hero ?? null };
+}
+
+/** HS / PPot / NPot / EHS / E[HS²]。相手レンジとランアウトを全列挙するので seed はない。 */
+export async function handStrength(
+  params: HandStrengthParams,
+): Promise<HandStrengthPayload> {
+  await ensureLoaded();
+  const { hero, board, villainRange = "" } = params;
+  return wasmHandStrength(
+    hero.join(" ").trim(),
+    board.join(" ").trim(),
+    normalizeRangeInput(villainRange).trim(),
+  ) as HandStrengthPayload;
+}
+
+/** プリフロップ → リバーの equity 推移と、ターン / リバーのベスト・ワーストカード。 */
+export async function equityPath(
+  params: EquityPathParams,
+): Promise<EquityPathPayload> {
+  await ensureLoaded();
+  const { hero, board, compare, trials, seed = DEFAULT_SEED } = params;
+  return wasmEquityPath(
+    hero.join(" ").trim(),
+    board.join(" ").trim(),
+    joinHands(compare).trim(),
+    trials,
+    seed,
+  ) as EquityPathPayload;
+}
+
+/** 次の 1 枚ごとの hero equity。`grid` はカード番号で引ける 52 枠のヒートマップ。 */
+export async function nextCardEquity(
+  params: NextCardEquityParams,
+): Promise<NextCardEquityPayload> {
+  await ensureLoaded();
+  const { hero, villainRange, board } = params;
+  const result = wasmNextCardEquity(
+    hero.join(" ").trim(),
+    normalizeRangeInput(villainRange).trim(),
+    board.join(" ").trim(),
+  ) as NextCardEquityPayload;
+  // serde の None は undefined で返るので null にそろえる
+  return { ...result, grid: result.grid.map((e) => e ?? null) };
+}
+
+/** 全フロップ（スート同型で 1755 通り）での range vs range。重いので trials は小さめに。 */
+export async function flopReport(
+  params: FlopReportParams,
+): Promise<FlopReportPayload> {
+  await ensureLoaded();
+  const { heroRange, villainRange, trials, seed = DEFAULT_SEED } = params;
+  const result = wasmFlopReport(
+    normalizeRangeInput(heroRange).trim(),
+    normalizeRangeInput(villainRange).trim(),
+    trials,
+    seed,
+  ) as FlopReportPayload;
+  // serde の None は undefined で返るので null にそろえる
+  return {
+    ...result,
+    flops: result.flops.map((f) => ({
+      ...f,
+      heroEquity: f.heroEquity ?? null,
+    })),
+  };
+}
+
 type SimulateVsListEquityParams = SimulateParams & {
   onProgress?: (pct: number) => void;
 };
@@ -166,8 +379,43 @@ export async function parseRangeToHands(
   return result;
 }
 
+/** 具体的なコンボ（["As", "Ks"]）をハンドクラス（"AKs"）にする。 */
+export async function handClassOf(hand: string[]): Promise<string> {
+  await ensureLoaded();
+  return wasmHandClassOf(hand.join(" ").trim());
+}
+
+/** ハンドクラスの全コンボ。deadCards を含むコンボは除く。 */
+export async function handClassCombos(
+  params: HandClassCombosParams,
+): Promise<string[][]> {
+  await ensureLoaded();
+  const { handClass, deadCards = [] } = params;
+  return wasmHandClassCombos(
+    handClass.trim(),
+    deadCards.join(" ").trim(),
+  ) as string[][];
+}
+
+/** レンジ表の 169 セル。deadCards はコンボ数の計算から除く。 */
+export async function handClassGrid(
+  deadCards: string[] = [],
+): Promise<HandClassCell[]> {
+  await ensureLoaded();
+  return wasmHandClassGrid(deadCards.join(" ").trim()) as HandClassCell[];
+}
+
+export async function canonicalFlop(
+  board: string[],
+): Promise<CanonicalFlopPayload> {
+  await ensureLoaded();
+  return wasmCanonicalFlop(board.join(" ").trim()) as CanonicalFlopPayload;
+}
+
 type RangeVsRangeParamsWithProgress = RangeVsRangeParams & {
   onProgress?: (pct: number) => void;
+  /** スート同型まで同じ入力の結果を使い回す（seed はキーに含まれない）。 */
+  useCache?: boolean;
 };
 
 export async function simulateRangeVsRangeEquity(
@@ -181,6 +429,7 @@ export async function simulateRangeVsRangeEquity(
     trials,
     seed = DEFAULT_SEED,
     onProgress,
+    useCache = false,
   } = params;
 
   const heroStr = normalizeRangeInput(heroRange).trim();
@@ -194,14 +443,316 @@ export async function simulateRangeVsRangeEquity(
   onProgress?.(0);
   await yieldToBrowser();
 
-  const result = wasmSimulateRangeVsRangeEquity(
-    heroStr,
-    villainStr,
-    boardStr,
-    trials,
-    seed,
+  const result = (
+    useCache
+      ? getCache().simulate_range_vs_range_equity(
+          heroStr,
+          villainStr,
+          boardStr,
+          trials,
+          seed,
+        )
+      : wasmSimulateRangeVsRangeEquity(
+          heroStr,
+          villainStr,
+          boardStr,
+          trials,
+          seed,
+        )
   ) as RangeVsRangePayload;
 
   onProgress?.(100);
   return result;
 }
+
+/**
+ * `simulateRangeVsRangeEquity` と同じ計算を、ハンドクラスごとの 169 セル
+ * （13×13 グリッドの並び）で返す。各セルはコンボ数と、対戦数で重み付けした
+ * equity・コンボ単位の最小・最大。
+ */
+export async function simulateRangeVsRangeEquityGrid(
+  params: RangeVsRangeParams,
+): Promise<RangeVsRangeGridPayload> {
+  await ensureLoaded();
+  const { heroRange, villainRange, board, trials, seed = DEFAULT_SEED } =
+    params;
+  const normalize = (cells: RangeGridCell[]) =>
+    cells.map((c) => ({
+      ...c,
+      equity: c.equity ?? null,
+      min: c.min ?? null,
+      max: c.max ?? null,
+    }));
+  const raw = wasmSimulateRangeVsRangeEquityGrid(
+    normalizeRangeInput(heroRange).trim(),
+    normalizeRangeInput(villainRange).trim(),
+    board.join(" ").trim(),
+    trials,
+    seed,
+  ) as RangeVsRangeGridPayload;
+  return { hero: normalize(raw.hero), villain: normalize(raw.villain) };
+}
+
+/**
+ * `simulateVsListWithRanks` と同じ計算を、バイナリ形式の Uint32Array で返す。
+ * `@/lib/wasm-v1/utils/binary-format` の `decodeVsListWithRanksBinary` で読める。
+ */
+export async function simulateVsListWithRanksBinary(
+  params: SimulateParams,
+): Promise<Uint32Array> {
+  await ensureLoaded();
+  const { hero, board, compare, trials, seed = DEFAULT_SEED } = params;
+  return wasmSimulateVsListWithRanksBinary(
+    hero.join(" ").trim(),
+    board.join(" ").trim(),
+    joinHands(compare).trim(),
+    trials,
+    seed,
+  );
+}
+
+/**
+ * `simulateRangeVsRangeEquity` と同じ計算を、バイナリ形式の Uint32Array で返す。
+ * `decodeRangeVsRangeBinary` で読める。
+ */
+export async function simulateRangeVsRangeEquityBinary(
+  params: RangeVsRangeParams,
+): Promise<Uint32Array> {
+  await ensureLoaded();
+  const {
+    heroRange,
+    villainRange,
+    board,
+    trials,
+    seed = DEFAULT_SEED,
+  } = params;
+  return wasmSimulateRangeVsRangeEquityBinary(
+    normalizeRangeInput(heroRange).trim(),
+    normalizeRangeInput(villainRange).trim(),
+    board.join(" ").trim(),
+    trials,
+    seed,
+  );
+}
+
+/** キャッシュの状態をバイト列にする。IndexedDB などへの保存用。 */
+export async function exportSimulationCache(): Promise<Uint8Array> {
+  await ensureLoaded();
+  return getCache().export_bytes();
+}
+
+/** `exportSimulationCache` で保存したバイト列からキャッシュを復元する。 */
+export async function importSimulationCache(bytes: Uint8Array): Promise<void> {
+  await ensureLoaded();
+  getCache().import_bytes(bytes);
+}
+
+export async function clearSimulationCache(): Promise<void> {
+  await ensureLoaded();
+  getCache().clear();
+}
+
+/**
+ * 各プレイヤーが出した額からメインポット・サイドポットを組み、ショーダウンで精算する。
+ * 役を比べるのはフォールドしていないプレイヤーが 2 人以上のときだけ（ボード 5 枚が必要）。
+ */
+export async function settlePots(
+  params: SettlePotsParams,
+): Promise<SettlementPayload> {
+  await ensureLoaded();
+  const { contributions, folded, hands, board, button, chip = 0 } = params;
+  const raw = wasmSettlePots(
+    new Float64Array(contributions),
+    Uint8Array.from(folded, (f) => (f ? 1 : 0)),
+    hands.map((h) => (h ? h.join("") : "")).join(";"),
+    board.join(" ").trim(),
+    button,
+    chip,
+  ) as SettlementPayload;
+  return { ...raw, showdown: raw.showdown.map((d) => d ?? null) };
+}
+
+/**
+ * マルチウェイのオールインで、サイドポットを考えた各プレイヤーの期待リターン。
+ * ボードが 4 枚以上なら全列挙した厳密値、それ以外は trials 回の Monte Carlo。
+ */
+export async function allInEv(params: AllInEvParams): Promise<AllInEvPayload> {
+  await ensureLoaded();
+  const { players, contributions, board, trials, seed = DEFAULT_SEED } = params;
+  return wasmAllInEv(
+    players
+      .map((p) => (p === null ? "" : typeof p === "string" ? p : p.join("")))
+      .join(";"),
+    new Float64Array(contributions),
+    board.join(" ").trim(),
+    trials,
+    seed,
+  ) as AllInEvPayload;
+}
+
+/**
+ * 相手のアクション列から、各アクション後の重み付きレンジをベイズ更新で求める。
+ * 重みは「そのアクションを選ぶ頻度」を掛けたもので、正規化はしない。
+ */
+export async function updateRange(
+  params: UpdateRangeParams,
+): Promise<RangeUpdatePayload> {
+  await ensureLoaded();
+  const { range, board, actions } = params;
+  const raw = wasmUpdateRange(
+    range,
+    board.join(" ").trim(),
+    actions.map((a) => `${a.street} ${a.action}`).join(";"),
+    actions
+      .map((a) =>
+        Object.entries(a.frequencies ?? {})
+          .map(([key, freq]) => `${key}:${freq}`)
+          .join(","),
+      )
+      .join(";"),
+  ) as RangeUpdatePayload;
+  const normalize = (r: RangeUpdatePayload["initial"]) => ({
+    ...r,
+    combos: r.combos.map((c) => ({ ...c, strength: c.strength ?? null })),
+  });
+  return {
+    initial: normalize(raw.initial),
+    steps: raw.steps.map((s) => ({ ...s, range: normalize(s.range) })),
+  };
+}
+
+/**
+ * オープンに対する BB のハンドクラスごとのコール / フォールドと、ディフェンス率・MDF。
+ * 額は BB 単位。実現率を掛けた equity がポットオッズ以上のクラスをコールとする。
+ */
+export async function bbDefense(
+  params: BbDefenseParams,
+): Promise<BbDefensePayload> {
+  await ensureLoaded();
+  const {
+    openRange,
+    openSize,
+    pot = 1.5,
+    defendRange,
+    realization = 1,
+    trials,
+    seed = DEFAULT_SEED,
+  } = params;
+  return wasmBbDefense(
+    openRange,
+    openSize,
+    pot,
+    defendRange,
+    realization,
+    trials,
+    seed,
+  ) as BbDefensePayload;
+}
+
+/** server seed のハッシュ。配る前に公開するコミットメント。 */
+export async function dealCommitment(serverSeed: string): Promise<string> {
+  await ensureLoaded();
+  return wasmDealCommitment(serverSeed);
+}
+
+/** 32 バイトの乱数から作った server seed（16 進 64 文字）。 */
+export async function generateServerSeed(): Promise<string> {
+  await ensureLoaded();
+  return wasmGenerateServerSeed();
+}
+
+/** server seed・client seed・nonce から決まる 52 枚の配り順。先頭から配る。 */
+export async function shuffledDeck(
+  params: ShuffledDeckParams,
+): Promise<ShuffledDeckPayload> {
+  await ensureLoaded();
+  const { serverSeed, clientSeed, nonce } = params;
+  return wasmShuffledDeck(
+    serverSeed,
+    clientSeed,
+    BigInt(nonce),
+  ) as ShuffledDeckPayload;
+}
+
+/**
+ * 明かされた server seed がコミットメントと一致し、配ったカードがデッキの先頭から
+ * 順番どおりかを確かめる。
+ */
+export async function verifyDeal(
+  params: VerifyDealParams,
+): Promise<DealVerificationPayload> {
+  await ensureLoaded();
+  const { commitment, serverSeed, clientSeed, nonce, dealt } = params;
+  const raw = wasmVerifyDeal(
+    commitment,
+    serverSeed,
+    clientSeed,
+    BigInt(nonce),
+    dealt.join(" ").trim(),
+  ) as DealVerificationPayload;
+  return { ...raw, mismatchAt: raw.mismatchAt ?? null };
+}
+
+/** Rust 側の NLHE エンジン。各アクションは検証してから適用し、適用後の状態を返す。 */
+export type HoldemEngine = {
+  snapshot: () => HoldemEngineSnapshot;
+  fold: () => HoldemEngineSnapshot;
+  check: () => HoldemEngineSnapshot;
+  call: () => HoldemEngineSnapshot;
+  /** ラウンド内の合計ベット額が amount になるようにベット / レイズする。 */
+  raiseTo: (amount: number) => HoldemEngineSnapshot;
+  allIn: () => HoldemEngineSnapshot;
+  /** WASM 側のメモリを解放する。以後は使えない。 */
+  free: () => void;
+};
+
+// serde の None は undefined で返るので null にそろえる
+const normalizeSnapshot = (raw: unknown): HoldemEngineSnapshot => {
+  const s = raw as HoldemEngineSnapshot;
+  return {
+    ...s,
+    legal: s.legal
+      ? {
+          ...s.legal,
+          minRaiseTo: s.legal.minRaiseTo ?? null,
+          maxRaiseTo: s.legal.maxRaiseTo ?? null,
+        }
+      : null,
+    showdown: s.showdown.map((d) => d ?? null),
+  };
+};
+
+/**
+ * 配牌とブラインドの投稿まで済ませたエンジンを作る。
+ * 不正なアクション（最小レイズ未満など）は Error を投げ、状態は変わらない。
+ */
+export async function createHoldemEngine(
+  options: HoldemEngineOptions,
+): Promise<HoldemEngine> {
+  await ensureLoaded();
+  const {
+    stacks,
+    smallBlind = 0.5,
+    bigBlind = 1,
+    ante = 0,
+    dealerIdx = 0,
+    seed = BigInt(Math.floor(Math.random() * Number.MAX_SAFE_INTEGER)),
+  } = options;
+  const game = new HoldemGame(
+    new Float64Array(stacks),
+    smallBlind,
+    bigBlind,
+    ante,
+    dealerIdx,
+    seed,
+  );
+  return {
+    snapshot: () => normalizeSnapshot(game.snapshot()),
+    fold: () => normalizeSnapshot(game.fold()),
+    check: () => normalizeSnapshot(game.check()),
+    call: () => normalizeSnapshot(game.call()),
+    raiseTo: (amount) => normalizeSnapshot(game.raise_to(amount)),
+    allIn: () => normalizeSnapshot(game.all_in()),
+    free: () => game.free(),
+  };
+}
@@ -0,0 +1,113 @@
+// 1 つのシミュレーションを複数 worker に分割して走らせるためのプール。
+// 通常の API は wasm-worker-client の単一 worker を使い、シャード分割 API だけがこちらを使う。
+
+type WorkerResponse<T> =
+  | { id: number; type: "result"; data: T }
+  | { id: number; type: "progress"; pct: number }
+  | { id: number; type: "error"; error: string };
+
+type PendingEntry<T> = {
+  resolve: (data: T) => void;
+  reject: (error: Error) => void;
+};
+
+type PoolWorker = {
+  worker: Worker;
+  pending: Map<number, PendingEntry<unknown>>;
+};
+
+export const MAX_POOL_SIZE = 8;
+
+const pool: PoolWorker[] = [];
+let seq = 0;
+
+const createPoolWorker = (): PoolWorker => {
+  const worker = new Worker(
+    new URL("../wasm-v1/simulation/simulation.worker.ts", import.meta.url),
+    {
+      type: "module",
+    },
+  );
+  const pending = new Map<number, PendingEntry<unknown>>();
+  worker.addEventListener(
+    "message",
+    (event: MessageEvent<WorkerResponse<unknown>>) => {
+      const message = event.data;
+      if (message.type === "progress") return;
+      const entry = pending.get(message.id);
+      if (!entry) return;
+      pending.delete(message.id);
+      if (message.type === "result") {
+        entry.resolve(message.data);
+      } else {
+        entry.reject(new Error(message.error));
+      }
+    },
+  );
+  worker.addEventListener("error", () => {
+    for (const entry of pending.values()) {
+      entry.reject(new Error("WASM worker error"));
+    }
+    pending.clear();
+  });
+  return { worker, pending };
+};
+
+/** `workers` 未指定時は論理コア数（最大 MAX_POOL_SIZE）を使う。 */
+export const resolvePoolSize = (workers?: number) => {
+  const cores =
+    typeof navigator !== "undefined" ? navigator.hardwareConcurrency || 1 : 1;
+  const requested = workers ?? cores;
+  return Math.min(MAX_POOL_SIZE, Math.max(1, Math.floor(requested)));
+};
+
+export const runPoolRequest = async <T>(
+  index: number,
+  request: { type: string; params: unknown },
+): Promise<T> => {
+  if (typeof window === "undefined") {
+    throw new Error("Worker pool is not available in this runtime");
+  }
+  while (pool.length <= index) {
+    pool.push(createPoolWorker());
+  }
+  const { worker, pending } = pool[index];
+
+  const id = seq + 1;
+  seq = id;
+  return new Promise<T>((resolve, reject) => {
+    pending.set(id, {
+      resolve: resolve as (data: unknown) => void,
+      reject,
+    });
+    worker.postMessage({ id, ...request });
+  });
+};
+
+/**
+ * シャード `0..shardCount` をそれぞれ別 worker で実行し、シャード順の結果を返す。
+ * 進捗は「完了したシャードの割合」で通知する。
+ */
+export const runShardedRequests = async <T>(
+  shardCount: number,
+  buildRequest: (shardIndex: number) => { type: string; params: unknown },
+  onProgress?: (pct: number) => void,
+): Promise<T[]> => {
+  let done = 0;
+  onProgress?.(0);
+  return Promise.all(
+    Array.from({ length: shardCount }, async (_, shardIndex) => {
+      const result = await runPoolRequest<T>(
+        shardIndex,
+        buildRequest(shardIndex),
+      );
+      done += 1;
+      onProgress?.(Math.floor((done * 100) / shardCount));
+      return result;
+    }),
+  );
+};
+
+/** 全シャードで共有するシード。worker ごとに別シードを振るとマージ結果が再現しない。 */
+export const createShardSeed = () =>
+  (BigInt(Date.now()) << 16n) ^ BigInt(Math.floor(Math.random() * 0xffff));